@@ -0,0 +1,151 @@
+use std::{env, fs, path::PathBuf, process};
+
+use anyhow::{Context as _, Result, bail};
+use runner_examples::DeployerKind;
+use testing_framework_core::scenario::{
+    Deployer as _, DeployerSpec, NodeControlCapability, Runner, Scenario, ScenarioSpec,
+    SpecRegistry,
+};
+use testing_framework_runner_compose::ComposeDeployer;
+use testing_framework_runner_k8s::K8sDeployer;
+use testing_framework_runner_local::LocalDeployer;
+use testing_framework_workflows::register_spec_components;
+use tracing::{info, warn};
+
+const USAGE: &str = "usage: scenario_runner <scenario.toml|scenario.yaml> [--deployer local|compose|k8s] [--report <report.json>]";
+
+/// Command line: the spec file plus optional overrides.
+struct Args {
+    spec: PathBuf,
+    deployer: Option<DeployerSpec>,
+    report: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err:#}\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    let spec = match ScenarioSpec::from_path(&args.spec) {
+        Ok(spec) => spec,
+        Err(err) => {
+            eprintln!("{err}");
+            process::exit(2);
+        }
+    };
+    let deployer = args.deployer.unwrap_or(spec.deployer);
+
+    match deployer {
+        DeployerSpec::Local => {
+            runner_examples::defaults::init_node_log_dir_defaults(DeployerKind::Local);
+        }
+        DeployerSpec::Compose => {
+            runner_examples::defaults::init_node_log_dir_defaults(DeployerKind::Compose);
+        }
+        DeployerSpec::K8s => {}
+    }
+    tracing_subscriber::fmt::init();
+
+    if deployer == DeployerSpec::Local && env::var("POL_PROOF_DEV_MODE").is_err() {
+        warn!("POL_PROOF_DEV_MODE=true is required for local scenarios");
+        process::exit(1);
+    }
+
+    info!(
+        spec = %args.spec.display(),
+        ?deployer,
+        nodes = spec.topology.nodes,
+        duration_secs = spec.duration_secs,
+        "starting scenario from spec"
+    );
+    if let Err(err) = run_spec(&spec, deployer, args.report).await {
+        warn!("scenario failed: {err:#}");
+        process::exit(1);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args> {
+    let mut spec = None;
+    let mut deployer = None;
+    let mut report = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--deployer" => {
+                let value = args.next().context("--deployer needs a value")?;
+                deployer = Some(match value.as_str() {
+                    "local" => DeployerSpec::Local,
+                    "compose" => DeployerSpec::Compose,
+                    "k8s" => DeployerSpec::K8s,
+                    other => bail!("unknown deployer '{other}'"),
+                });
+            }
+            "--report" => {
+                report = Some(PathBuf::from(args.next().context("--report needs a path")?));
+            }
+            flag if flag.starts_with("--") => bail!("unknown option '{flag}'"),
+            path if spec.is_none() => spec = Some(PathBuf::from(path)),
+            extra => bail!("unexpected argument '{extra}'"),
+        }
+    }
+    Ok(Args {
+        spec: spec.context("missing scenario file")?,
+        deployer,
+        report,
+    })
+}
+
+async fn run_spec(
+    spec: &ScenarioSpec,
+    deployer: DeployerSpec,
+    report_path: Option<PathBuf>,
+) -> Result<()> {
+    let mut registry = SpecRegistry::new();
+    register_spec_components(&mut registry);
+    let mut plan = registry
+        .builder(spec)?
+        .build()
+        .context("building scenario failed")?;
+
+    let runner = deploy(deployer, &plan).await?;
+    info!("running scenario");
+    let (report, outcome) = runner.run_with_report(&mut plan).await;
+
+    if let Some(path) = report_path {
+        fs::write(&path, report.to_json()?)
+            .with_context(|| format!("writing report to {} failed", path.display()))?;
+        info!(path = %path.display(), "scenario report written");
+    }
+    outcome.context("running scenario failed")?;
+    info!("scenario complete");
+    Ok(())
+}
+
+async fn deploy(deployer: DeployerSpec, plan: &Scenario<NodeControlCapability>) -> Result<Runner> {
+    match deployer {
+        DeployerSpec::Local => {
+            info!("deploying local nodes");
+            LocalDeployer::default()
+                .deploy(plan)
+                .await
+                .context("deploying local nodes failed")
+        }
+        DeployerSpec::Compose => {
+            info!("deploying compose stack");
+            ComposeDeployer::new()
+                .deploy(plan)
+                .await
+                .context("deploying compose stack failed")
+        }
+        DeployerSpec::K8s => {
+            info!("deploying k8s stack");
+            K8sDeployer::new()
+                .deploy(plan)
+                .await
+                .context("deploying k8s stack failed")
+        }
+    }
+}
@@ -37,11 +37,11 @@ pub enum NetworkConfigError {
     LoopbackMultiaddr { message: String },
 }
 
-fn default_swarm_config() -> SwarmConfig {
+fn default_swarm_config(node_key: ed25519::SecretKey) -> SwarmConfig {
     SwarmConfig {
         host: std::net::Ipv4Addr::UNSPECIFIED,
         port: 60000,
-        node_key: ed25519::SecretKey::generate(),
+        node_key,
         gossipsub_config: gossipsub::Config::default(),
         kademlia_config: KademliaSettings::default(),
         identify_config: IdentifySettings::default(),
@@ -83,13 +83,12 @@ pub fn create_network_configs(
 
             let port = get_available_udp_port().ok_or(NetworkConfigError::PortAllocationFailed)?;
             Ok(SwarmConfig {
-                node_key,
                 port,
                 chain_sync_config: cryptarchia_sync::Config {
                     peer_response_timeout: PEER_RESPONSE_TIMEOUT,
                 },
                 nat_config: nat_settings(port)?,
-                ..default_swarm_config()
+                ..default_swarm_config(node_key)
             })
         })
         .collect::<Result<_, _>>()?;
@@ -121,13 +120,12 @@ pub fn build_network_config_for_node(
     })?;
 
     let swarm_config = SwarmConfig {
-        node_key,
         port,
         chain_sync_config: cryptarchia_sync::Config {
             peer_response_timeout: PEER_RESPONSE_TIMEOUT,
         },
         nat_config: nat_settings(port)?,
-        ..default_swarm_config()
+        ..default_swarm_config(node_key)
     };
 
     Ok(GeneralNetworkConfig {
@@ -290,6 +290,19 @@ impl ApiClient {
         Ok(())
     }
 
+    /// Submit an arbitrary body to the mempool endpoint. Unlike
+    /// `submit_transaction`, rejections are not logged since callers probing
+    /// validation expect them.
+    pub async fn submit_raw_transaction<T>(&self, body: &T) -> reqwest::Result<()>
+    where
+        T: Serialize + Sync + ?Sized,
+    {
+        self.post_json_response(MEMPOOL_ADD_TX, body)
+            .await?
+            .error_for_status()
+            .map(|_| ())
+    }
+
     /// Execute a custom request built by the caller.
     pub async fn get_headers_raw(&self, builder: RequestBuilder) -> reqwest::Result<Response> {
         builder.send().await
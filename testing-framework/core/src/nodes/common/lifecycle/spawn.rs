@@ -1,7 +1,7 @@
 use std::{
     fs::{self, File},
     io,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use nomos_tracing::logging::local::FileConfig;
@@ -20,19 +20,19 @@ where
 {
     debug!(prefix, base_dir = %base_dir.display(), "configuring node logging");
 
-    if let Some(log_dir) = tf_env::nomos_log_dir() {
-        let _ = fs::create_dir_all(&log_dir);
-
-        set_logger(FileConfig {
-            directory: log_dir,
-            prefix: Some(prefix.into()),
-        });
-    } else {
-        set_logger(FileConfig {
-            directory: base_dir.to_owned(),
-            prefix: Some(prefix.into()),
-        });
-    }
+    let directory = log_directory(base_dir);
+    let _ = fs::create_dir_all(&directory);
+
+    set_logger(FileConfig {
+        directory,
+        prefix: Some(prefix.into()),
+    });
+}
+
+/// Directory that [`configure_logging`] points node tracing logs at.
+#[must_use]
+pub fn log_directory(base_dir: &Path) -> PathBuf {
+    tf_env::nomos_log_dir().unwrap_or_else(|| base_dir.to_owned())
 }
 
 /// Write a YAML config file, allowing a caller-provided injection hook to
@@ -1,9 +1,11 @@
 use std::{
-    io,
+    fs::{self, OpenOptions},
+    io::{self, BufRead as _, BufReader, Write as _},
     net::SocketAddr,
     path::{Path, PathBuf},
-    process::{Child, Command, Stdio},
-    time::Duration,
+    process::{Child, ChildStderr, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use nomos_tracing_service::LoggerLayer;
@@ -13,16 +15,21 @@ use tempfile::TempDir;
 use tokio::time;
 use tracing::{debug, info};
 
-use super::lifecycle::monitor::is_running;
+use super::lifecycle::{kill::kill_child, monitor::is_running};
 use crate::nodes::{
     ApiClient,
-    common::{config::paths::ensure_recovery_paths, lifecycle::spawn::configure_logging},
+    common::{
+        config::paths::ensure_recovery_paths,
+        lifecycle::spawn::{configure_logging, log_directory},
+    },
     create_tempdir, persist_tempdir,
 };
 
 const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
 const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(100);
 const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+/// File in the node's working directory receiving a copy of its stderr.
+const STDERR_LOG_FILE: &str = "stderr.log";
 
 pub type NodeAddresses = (SocketAddr, Option<SocketAddr>);
 pub type PreparedNodeConfig<T> = (TempDir, T, SocketAddr, Option<SocketAddr>);
@@ -71,15 +78,120 @@ pub struct NodeHandle<T> {
     pub(crate) tempdir: TempDir,
     pub(crate) config: T,
     pub(crate) api: ApiClient,
+    pub(crate) launch: NodeLaunch,
+    pub(crate) logs: NodeLogFiles,
+    /// Set once the process was stopped on purpose or its exit reported, so
+    /// [`NodeHandle::unexpected_exit`] reports each exit once.
+    pub(crate) exit_handled: bool,
+}
+
+/// Binary and config file used to (re)launch a node process.
+#[derive(Clone, Debug)]
+pub struct NodeLaunch {
+    pub binary_path: PathBuf,
+    pub config_path: PathBuf,
+}
+
+/// Files a node writes its logs to: the captured stderr and, when file
+/// logging is enabled, the tracing output.
+#[derive(Clone, Debug)]
+pub struct NodeLogFiles {
+    stderr: PathBuf,
+    tracing: Option<(PathBuf, String)>,
+    /// Creation time, rounded down to the second so coarse filesystem
+    /// timestamps do not hide files written right after it.
+    since: SystemTime,
+}
+
+impl NodeLogFiles {
+    /// Log files of a node about to be launched; create this before spawning
+    /// the process.
+    #[must_use]
+    pub fn new(workdir: &Path, tracing: Option<(PathBuf, String)>) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            stderr: workdir.join(STDERR_LOG_FILE),
+            tracing,
+            since: UNIX_EPOCH + Duration::from_secs(now.as_secs()),
+        }
+    }
+
+    /// Existing log files, stderr first. Tracing files are rotated, so every
+    /// file named after the prefix is included, oldest first. The tracing
+    /// directory may be shared (`NOMOS_LOG_DIR`), so files not modified since
+    /// this node was launched are left out as leftovers of earlier runs.
+    #[must_use]
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        if self.stderr.is_file() {
+            paths.push(self.stderr.clone());
+        }
+        let Some((directory, prefix)) = &self.tracing else {
+            return paths;
+        };
+        let Ok(entries) = fs::read_dir(directory) else {
+            return paths;
+        };
+
+        let rotated = format!("{prefix}.");
+        let mut tracing = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name == prefix || name.starts_with(&rotated))
+            })
+            .filter(|path| {
+                fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .is_ok_and(|modified| modified >= self.since)
+            })
+            .collect::<Vec<_>>();
+        tracing.sort();
+        paths.extend(tracing);
+        paths
+    }
+
+    /// Last `lines` lines of the stderr log and of the newest tracing file.
+    #[must_use]
+    pub fn tail(&self, lines: usize) -> String {
+        let paths = self.paths();
+        let newest_tracing = paths.iter().rfind(|path| **path != self.stderr);
+        [Some(&self.stderr), newest_tracing]
+            .into_iter()
+            .flatten()
+            .filter_map(|path| fs::read(path).ok())
+            .map(|bytes| {
+                let contents = String::from_utf8_lossy(&bytes);
+                let all = contents.lines().collect::<Vec<_>>();
+                all[all.len().saturating_sub(lines)..].join("\n")
+            })
+            .filter(|tail| !tail.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 impl<T> NodeHandle<T> {
-    pub fn new(child: Child, tempdir: TempDir, config: T, api: ApiClient) -> Self {
+    pub fn new(
+        child: Child,
+        tempdir: TempDir,
+        config: T,
+        api: ApiClient,
+        launch: NodeLaunch,
+        logs: NodeLogFiles,
+    ) -> Self {
         Self {
             child,
             tempdir,
             config,
             api,
+            launch,
+            logs,
+            exit_handled: false,
         }
     }
 
@@ -103,6 +215,23 @@ impl<T> NodeHandle<T> {
         &self.config
     }
 
+    #[must_use]
+    pub const fn log_files(&self) -> &NodeLogFiles {
+        &self.logs
+    }
+
+    #[must_use]
+    /// OS process id of the current (or last) node process.
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    #[must_use]
+    /// Working directory holding the node's config, data and logs.
+    pub fn workdir(&self) -> &Path {
+        self.tempdir.path()
+    }
+
     /// Returns true if the process exited within the timeout, false otherwise.
     pub async fn wait_for_exit(&mut self, timeout: Duration) -> bool {
         time::timeout(timeout, async {
@@ -116,6 +245,58 @@ impl<T> NodeHandle<T> {
         .await
         .is_ok()
     }
+
+    /// Kills the running process and launches a new one from the same binary,
+    /// config file and working directory, so ports and storage are preserved.
+    /// Callers should wait for readiness afterwards (see
+    /// [`wait_for_node_readiness`]).
+    pub fn relaunch(&mut self) -> Result<(), SpawnNodeError> {
+        self.stop();
+        self.start()
+    }
+
+    /// Kills the running process and reaps it. The working directory is kept
+    /// so the node can be started again with [`NodeHandle::start`].
+    pub fn stop(&mut self) {
+        debug!(
+            config_file = %self.launch.config_path.display(),
+            "stopping node process"
+        );
+        self.exit_handled = true;
+        kill_child(&mut self.child);
+        let _ = self.child.wait();
+    }
+
+    /// Exit status of a process that died without [`NodeHandle::stop`] being
+    /// called. Each exit is returned once.
+    pub fn unexpected_exit(&mut self) -> Option<ExitStatus> {
+        if self.exit_handled {
+            return None;
+        }
+        let status = self.child.try_wait().ok().flatten()?;
+        self.exit_handled = true;
+        Some(status)
+    }
+
+    /// Launches the node process again from its existing config and working
+    /// directory. No-op if the process is still running.
+    pub fn start(&mut self) -> Result<(), SpawnNodeError> {
+        if is_running(&mut self.child) {
+            return Ok(());
+        }
+
+        debug!(
+            config_file = %self.launch.config_path.display(),
+            "starting node process"
+        );
+        self.child = spawn_node_process(
+            &self.launch.binary_path,
+            &self.launch.config_path,
+            self.tempdir.path(),
+        )?;
+        self.exit_handled = false;
+        Ok(())
+    }
 }
 
 /// Apply common setup (recovery paths, logging, data dirs) and return a ready
@@ -166,17 +347,32 @@ where
 
     debug!(config_file = %config_path.display(), binary = %binary_path.display(), "spawning node process");
 
+    let logs = NodeLogFiles::new(
+        dir.path(),
+        enable_logging.then(|| (log_directory(dir.path()), log_prefix.to_owned())),
+    );
     let child = spawn_node_process(&binary_path, &config_path, dir.path())?;
 
-    let mut handle = NodeHandle::new(child, dir, config, ApiClient::new(addr, testing_addr));
+    let launch = NodeLaunch {
+        binary_path,
+        config_path,
+    };
+    let mut handle = NodeHandle::new(
+        child,
+        dir,
+        config,
+        ApiClient::new(addr, testing_addr),
+        launch,
+        logs,
+    );
 
     // Wait for readiness via consensus_info
-    let ready = wait_for_consensus_readiness(&handle.api).await;
+    let ready = wait_for_node_readiness(&handle.api).await;
 
     if let Err(err) = ready {
         // Persist tempdir to aid debugging if readiness fails.
         let _ = persist_tempdir(&mut handle.tempdir, "logos-blockchain-node");
-        return Err(SpawnNodeError::Readiness { source: err });
+        return Err(err);
     }
 
     info!("node readiness confirmed via consensus_info");
@@ -200,20 +396,54 @@ fn spawn_node_process(
     config_path: &Path,
     workdir: &Path,
 ) -> Result<Child, SpawnNodeError> {
-    Command::new(binary_path)
+    let mut child = Command::new(binary_path)
         .arg(config_path)
         .current_dir(workdir)
         .stdin(Stdio::null())
         .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stderr(Stdio::piped())
         .spawn()
         .map_err(|source| SpawnNodeError::Spawn {
             binary: binary_path.to_path_buf(),
             source,
-        })
+        })?;
+
+    if let Some(stderr) = child.stderr.take() {
+        tee_stderr(stderr, &workdir.join(STDERR_LOG_FILE));
+    }
+    Ok(child)
+}
+
+/// Forward a node's stderr to ours while appending a copy to `path`, so
+/// panics are kept next to the tracing logs. Stops when the process exits.
+fn tee_stderr(stderr: ChildStderr, path: &Path) {
+    let mut file = match OpenOptions::new().create(true).append(true).open(path) {
+        Ok(file) => Some(file),
+        Err(err) => {
+            debug!(path = %path.display(), error = ?err, "failed to open node stderr log");
+            None
+        }
+    };
+
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stderr);
+        let mut line = Vec::new();
+        while reader
+            .read_until(b'\n', &mut line)
+            .is_ok_and(|read| read > 0)
+        {
+            let _ = io::stderr().write_all(&line);
+            if let Some(file) = file.as_mut() {
+                let _ = file.write_all(&line);
+            }
+            line.clear();
+        }
+    });
 }
 
-async fn wait_for_consensus_readiness(api: &ApiClient) -> Result<(), time::error::Elapsed> {
+/// Wait until the node answers `consensus_info`, bounded by the startup
+/// timeout.
+pub async fn wait_for_node_readiness(api: &ApiClient) -> Result<(), SpawnNodeError> {
     time::timeout(STARTUP_TIMEOUT, async {
         loop {
             if api.consensus_info().await.is_ok() {
@@ -223,4 +453,5 @@ async fn wait_for_consensus_readiness(api: &ApiClient) -> Result<(), time::error
         }
     })
     .await
+    .map_err(|source| SpawnNodeError::Readiness { source })
 }
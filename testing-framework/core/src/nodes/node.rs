@@ -67,6 +67,30 @@ impl Node {
         self.handle.wait_for_exit(timeout).await
     }
 
+    /// Kill the node process and start it again with the same config, ports
+    /// and data directory. Does not wait for the node to become ready.
+    pub fn relaunch(&mut self) -> Result<(), SpawnNodeError> {
+        self.handle.relaunch()
+    }
+
+    /// Exit status of a process that died on its own, i.e. not through
+    /// [`Node::stop`] or [`Node::relaunch`]. Each exit is returned once.
+    pub fn unexpected_exit(&mut self) -> Option<std::process::ExitStatus> {
+        self.handle.unexpected_exit()
+    }
+
+    /// Kill the node process but keep its data directory for a later
+    /// [`Node::start`].
+    pub fn stop(&mut self) {
+        self.handle.stop();
+    }
+
+    /// Start a stopped node process again. Does not wait for the node to
+    /// become ready.
+    pub fn start(&mut self) -> Result<(), SpawnNodeError> {
+        self.handle.start()
+    }
+
     pub async fn spawn(config: RunConfig, label: &str) -> Result<Self, SpawnNodeError> {
         let log_prefix = format!("{LOGS_PREFIX}-{label}");
         let handle = spawn_node(
@@ -66,11 +66,35 @@ impl RequiresNodeControl for ObservabilityCapability {
     const REQUIRED: bool = false;
 }
 
-/// Interface exposed by runners that can restart nodes at runtime.
+/// Interface exposed by runners that can restart, stop and start nodes at
+/// runtime.
 #[async_trait]
 pub trait NodeControlHandle: Send + Sync {
     async fn restart_node(&self, index: usize) -> Result<(), DynError>;
 
+    /// Stop the node at `index` and keep it down until
+    /// [`NodeControlHandle::resume_node`] is called.
+    async fn stop_node(&self, _index: usize) -> Result<(), DynError> {
+        Err("stop_node not supported by this deployer".into())
+    }
+
+    /// Bring a node previously stopped with [`NodeControlHandle::stop_node`]
+    /// back up, keeping its state.
+    async fn resume_node(&self, _index: usize) -> Result<(), DynError> {
+        Err("resume_node not supported by this deployer".into())
+    }
+
+    /// Isolate the given node groups from each other. Nodes within a group
+    /// can still talk; nodes not listed in any group are left untouched.
+    async fn partition(&self, _groups: &[Vec<usize>]) -> Result<(), DynError> {
+        Err("partition not supported by this deployer".into())
+    }
+
+    /// Remove any partition applied with [`NodeControlHandle::partition`].
+    async fn heal_partition(&self) -> Result<(), DynError> {
+        Err("heal_partition not supported by this deployer".into())
+    }
+
     async fn start_node(&self, _name: &str) -> Result<StartedNode, DynError> {
         Err("start_node not supported by this deployer".into())
     }
@@ -4,8 +4,8 @@ use thiserror::Error;
 use tracing::{debug, info};
 
 use super::{
-    DynError, NodeControlCapability, expectation::Expectation, runtime::context::RunMetrics,
-    workload::Workload,
+    DynError, LinkShaping, NodeControlCapability, ScenarioSeed, expectation::Expectation,
+    runtime::context::RunMetrics, workload::Workload,
 };
 use crate::topology::{
     config::{TopologyBuildError, TopologyBuilder, TopologyConfig},
@@ -39,6 +39,7 @@ pub struct Scenario<Caps = ()> {
     expectations: Vec<Box<dyn Expectation>>,
     duration: Duration,
     capabilities: Caps,
+    link_shaping: LinkShaping,
 }
 
 impl<Caps> Scenario<Caps> {
@@ -48,6 +49,7 @@ impl<Caps> Scenario<Caps> {
         expectations: Vec<Box<dyn Expectation>>,
         duration: Duration,
         capabilities: Caps,
+        link_shaping: LinkShaping,
     ) -> Self {
         Self {
             topology,
@@ -55,6 +57,7 @@ impl<Caps> Scenario<Caps> {
             expectations,
             duration,
             capabilities,
+            link_shaping,
         }
     }
 
@@ -83,10 +86,23 @@ impl<Caps> Scenario<Caps> {
         self.duration
     }
 
+    #[must_use]
+    /// Seed the scenario's random choices derive from.
+    pub const fn seed(&self) -> ScenarioSeed {
+        self.topology.seed()
+    }
+
     #[must_use]
     pub const fn capabilities(&self) -> &Caps {
         &self.capabilities
     }
+
+    #[must_use]
+    /// Link shaping handle; changes made during the run are applied by
+    /// deployers that support shaping.
+    pub const fn link_shaping(&self) -> &LinkShaping {
+        &self.link_shaping
+    }
 }
 
 /// Builder used by callers to describe the desired scenario.
@@ -97,6 +113,8 @@ pub struct Builder<Caps = ()> {
     duration: Duration,
     wallet_users: Option<usize>,
     capabilities: Caps,
+    link_shaping: LinkShaping,
+    seed: Option<ScenarioSeed>,
 }
 
 pub type ScenarioBuilder = Builder<()>;
@@ -119,6 +137,8 @@ impl<Caps: Default> Builder<Caps> {
             duration: Duration::ZERO,
             wallet_users: None,
             capabilities: Caps::default(),
+            link_shaping: LinkShaping::default(),
+            seed: None,
         }
     }
 
@@ -155,6 +175,8 @@ impl<Caps> Builder<Caps> {
             expectations,
             duration,
             wallet_users,
+            link_shaping,
+            seed,
             ..
         } = self;
 
@@ -165,6 +187,8 @@ impl<Caps> Builder<Caps> {
             duration,
             wallet_users,
             capabilities,
+            link_shaping,
+            seed,
         }
     }
 
@@ -178,6 +202,12 @@ impl<Caps> Builder<Caps> {
         &mut self.capabilities
     }
 
+    #[must_use]
+    /// Link shaping handle carried into the built scenario.
+    pub const fn link_shaping(&self) -> &LinkShaping {
+        &self.link_shaping
+    }
+
     #[must_use]
     pub fn with_workload<W>(mut self, workload: W) -> Self
     where
@@ -205,6 +235,14 @@ impl<Caps> Builder<Caps> {
         self
     }
 
+    #[must_use]
+    /// Seed for every random choice in the scenario. When unset, the seed is
+    /// read from `LOGOS_BLOCKCHAIN_TESTS_SEED` or picked at random.
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(ScenarioSeed::new(seed));
+        self
+    }
+
     #[must_use]
     /// Transform the topology builder.
     pub fn map_topology(mut self, f: impl FnOnce(TopologyBuilder) -> TopologyBuilder) -> Self {
@@ -238,8 +276,10 @@ impl<Caps> Builder<Caps> {
             duration,
             wallet_users,
             capabilities,
-            ..
+            link_shaping,
+            seed,
         } = self;
+        let seed = seed.unwrap_or_else(ScenarioSeed::from_env_or_random);
 
         if let Some(users) = wallet_users {
             let user_count =
@@ -255,13 +295,14 @@ impl<Caps> Builder<Caps> {
             topology = topology.with_wallet_config(wallet);
         }
 
-        let generated = topology.build()?;
+        let generated = topology.with_seed(seed).build()?;
         let duration = enforce_min_duration(&generated, duration);
         let run_metrics = RunMetrics::from_topology(&generated, duration);
         initialize_components(&generated, &run_metrics, &mut workloads, &mut expectations)?;
         let workloads: Vec<Arc<dyn Workload>> = workloads.into_iter().map(Arc::from).collect();
 
         info!(
+            %seed,
             nodes = generated.nodes().len(),
             duration_secs = duration.as_secs(),
             workloads = workloads.len(),
@@ -275,6 +316,7 @@ impl<Caps> Builder<Caps> {
             expectations,
             duration,
             capabilities,
+            link_shaping,
         ))
     }
 }
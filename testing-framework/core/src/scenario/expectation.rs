@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 
 use super::{DynError, RunContext, runtime::context::RunMetrics};
@@ -20,5 +22,25 @@ pub trait Expectation: Send + Sync {
         Ok(())
     }
 
+    /// How often the runner should call [`Expectation::monitor`] while
+    /// workloads run; `None` (the default) disables monitoring.
+    fn monitor_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// In-run check polled every [`Expectation::monitor_interval`]. Returning
+    /// an error aborts the run with [`ScenarioError::Monitor`].
+    ///
+    /// [`ScenarioError::Monitor`]: super::ScenarioError::Monitor
+    async fn monitor(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+        Ok(())
+    }
+
     async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError>;
+
+    /// Structured data to attach to this expectation's entry in the
+    /// [`ScenarioReport`](super::ScenarioReport), read after `evaluate`.
+    fn report_details(&self) -> Option<serde_json::Value> {
+        None
+    }
 }
@@ -0,0 +1,134 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use tokio::sync::watch;
+
+/// Delay, jitter and packet loss applied to traffic on a link.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LinkConditions {
+    /// Added one-way delay.
+    pub delay: Duration,
+    /// Random variation applied on top of `delay` (uniform, +/-).
+    pub jitter: Duration,
+    /// Fraction of packets dropped, in `0.0..=1.0`.
+    pub loss: f64,
+}
+
+impl LinkConditions {
+    #[must_use]
+    /// Conditions with a fixed delay and no jitter or loss.
+    pub const fn delay(delay: Duration) -> Self {
+        Self {
+            delay,
+            jitter: Duration::ZERO,
+            loss: 0.0,
+        }
+    }
+
+    #[must_use]
+    /// Conditions that only drop packets.
+    pub const fn loss(loss: f64) -> Self {
+        Self {
+            delay: Duration::ZERO,
+            jitter: Duration::ZERO,
+            loss,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_loss(mut self, loss: f64) -> Self {
+        self.loss = loss;
+        self
+    }
+}
+
+/// Link conditions for a topology: an optional default for every node-to-node
+/// link plus overrides for directed `(from, to)` links by node index.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkShapingConfig {
+    pub default: Option<LinkConditions>,
+    pub links: BTreeMap<(usize, usize), LinkConditions>,
+}
+
+impl LinkShapingConfig {
+    #[must_use]
+    /// Conditions for traffic sent from `from` to `to`, if any.
+    pub fn conditions(&self, from: usize, to: usize) -> Option<LinkConditions> {
+        self.links.get(&(from, to)).copied().or(self.default)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.links.is_empty()
+    }
+}
+
+/// Shared handle to a scenario's link shaping config.
+///
+/// Clones point at the same config; deployers that support shaping subscribe
+/// to it and apply changes made mid-run.
+#[derive(Clone, Debug)]
+pub struct LinkShaping {
+    config: Arc<watch::Sender<LinkShapingConfig>>,
+}
+
+impl Default for LinkShaping {
+    fn default() -> Self {
+        Self::new(LinkShapingConfig::default())
+    }
+}
+
+impl LinkShaping {
+    #[must_use]
+    pub fn new(config: LinkShapingConfig) -> Self {
+        let (config, _) = watch::channel(config);
+        Self {
+            config: Arc::new(config),
+        }
+    }
+
+    #[must_use]
+    /// Copy of the current config.
+    pub fn current(&self) -> LinkShapingConfig {
+        self.config.borrow().clone()
+    }
+
+    #[must_use]
+    /// Conditions currently applied to traffic from `from` to `to`.
+    pub fn conditions(&self, from: usize, to: usize) -> Option<LinkConditions> {
+        self.config.borrow().conditions(from, to)
+    }
+
+    /// Modify the config in place and notify subscribers.
+    pub fn update(&self, f: impl FnOnce(&mut LinkShapingConfig)) {
+        self.config.send_modify(f);
+    }
+
+    /// Set (or clear) the conditions applied to every link.
+    pub fn set_default(&self, conditions: Option<LinkConditions>) {
+        self.update(|config| config.default = conditions);
+    }
+
+    /// Set the conditions for traffic from `from` to `to`.
+    pub fn set_link(&self, from: usize, to: usize, conditions: LinkConditions) {
+        self.update(|config| {
+            config.links.insert((from, to), conditions);
+        });
+    }
+
+    /// Remove all shaping.
+    pub fn clear(&self) {
+        self.update(|config| *config = LinkShapingConfig::default());
+    }
+
+    #[must_use]
+    /// Receiver that wakes up whenever the config changes.
+    pub fn subscribe(&self) -> watch::Receiver<LinkShapingConfig> {
+        self.config.subscribe()
+    }
+}
@@ -5,8 +5,14 @@ pub mod cfgsync;
 mod definition;
 mod expectation;
 pub mod http_probe;
+mod link_shaping;
+mod node_logs;
 mod observability;
+mod resources;
 mod runtime;
+mod seed;
+mod spec;
+mod supervisor;
 mod workload;
 
 pub type DynError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -19,14 +25,27 @@ pub use definition::{
     Builder, Scenario, ScenarioBuildError, ScenarioBuilder, TopologyConfigurator,
 };
 pub use expectation::Expectation;
+pub use link_shaping::{LinkConditions, LinkShaping, LinkShapingConfig};
+pub use node_logs::{NodeLogSource, NodeLogs};
 pub use observability::{ObservabilityCapabilityProvider, ObservabilityInputs};
+pub use resources::{
+    NodeResourceUsage, ResourceSample, ResourceSampler, ResourceSeries, ResourceUsage,
+};
 pub use runtime::{
-    BlockFeed, BlockFeedTask, BlockRecord, BlockStats, CleanupGuard, Deployer, NodeClients,
-    RunContext, RunHandle, RunMetrics, Runner, ScenarioError,
+    BlockFeed, BlockFeedTask, BlockRecord, BlockReport, BlockStats, ChainEvent, ChainTree,
+    CleanupGuard, Deployer, ExpectationReport, ExpectationStatus, ForkEvent, NodeClients,
+    ReorgEvent, RunContext, RunHandle, RunMetrics, Runner, ScenarioError, ScenarioReport,
+    WorkloadReport, WorkloadStatus,
     metrics::{
         CONSENSUS_PROCESSED_BLOCKS, CONSENSUS_TRANSACTIONS_TOTAL, Metrics, MetricsError,
         PrometheusEndpoint, PrometheusInstantSample,
     },
-    spawn_block_feed,
+    spawn_block_feed, spawn_block_feed_with_nodes,
+};
+pub use seed::ScenarioSeed;
+pub use spec::{
+    ComponentSpec, DeployerSpec, NetworkLayoutSpec, ScenarioSpec, SpecBuilder, SpecError,
+    SpecRegistry, TopologySpec,
 };
+pub use supervisor::{NodeExit, NodeSupervisor};
 pub use workload::Workload;
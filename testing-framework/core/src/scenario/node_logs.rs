@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use super::DynError;
+
+/// Log output collected from a single node.
+#[derive(Clone, Debug)]
+pub struct NodeLogs {
+    /// Node label as used by the deployer (e.g. `node-0`).
+    pub label: String,
+    pub contents: String,
+}
+
+/// Interface exposed by runners that can collect the logs written by their
+/// nodes.
+#[async_trait]
+pub trait NodeLogSource: Send + Sync {
+    /// Logs of every node known to the deployer, collected at call time.
+    async fn collect_logs(&self) -> Result<Vec<NodeLogs>, DynError>;
+}
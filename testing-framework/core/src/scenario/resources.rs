@@ -0,0 +1,105 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+
+use super::DynError;
+
+/// One reading of a node's resource usage. Fields the deployer cannot observe
+/// are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResourceUsage {
+    /// CPU time used since the previous reading, in percent of one core.
+    pub cpu_percent: Option<f64>,
+    /// Resident memory in bytes.
+    pub rss_bytes: Option<u64>,
+    /// Open file descriptors.
+    pub open_fds: Option<u64>,
+    /// Bytes written to the node's data directory or container filesystem.
+    pub disk_bytes: Option<u64>,
+}
+
+/// Resource usage of a single node at sampling time.
+#[derive(Clone, Debug)]
+pub struct NodeResourceUsage {
+    /// Node label as used by the deployer (e.g. `node-0`).
+    pub label: String,
+    pub usage: ResourceUsage,
+}
+
+/// Interface exposed by runners that can measure the resources used by their
+/// nodes.
+#[async_trait]
+pub trait ResourceSampler: Send + Sync {
+    /// Current usage of every running node.
+    async fn sample(&self) -> Result<Vec<NodeResourceUsage>, DynError>;
+}
+
+/// A reading placed on the run's timeline.
+#[derive(Clone, Copy, Debug)]
+pub struct ResourceSample {
+    /// Time since sampling started.
+    pub elapsed: Duration,
+    pub usage: ResourceUsage,
+}
+
+/// Per-node resource usage time series, filled in by the runner while the
+/// scenario runs.
+#[derive(Clone)]
+pub struct ResourceSeries {
+    sampler: Arc<dyn ResourceSampler>,
+    series: Arc<Mutex<BTreeMap<String, Vec<ResourceSample>>>>,
+}
+
+impl ResourceSeries {
+    #[must_use]
+    pub fn new(sampler: Arc<dyn ResourceSampler>) -> Self {
+        Self {
+            sampler,
+            series: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    #[must_use]
+    /// Labels of every node sampled so far.
+    pub fn labels(&self) -> Vec<String> {
+        self.lock().keys().cloned().collect()
+    }
+
+    #[must_use]
+    /// Samples recorded for `label`, oldest first.
+    pub fn samples(&self, label: &str) -> Vec<ResourceSample> {
+        self.lock().get(label).cloned().unwrap_or_default()
+    }
+
+    #[must_use]
+    /// Every node's samples, oldest first.
+    pub fn snapshot(&self) -> BTreeMap<String, Vec<ResourceSample>> {
+        self.lock().clone()
+    }
+
+    /// Take one reading from the sampler and append it to each node's series.
+    pub async fn sample(&self, elapsed: Duration) -> Result<(), DynError> {
+        let readings = self.sampler.sample().await?;
+        let mut series = self.lock();
+        for reading in readings {
+            series
+                .entry(reading.label)
+                .or_default()
+                .push(ResourceSample {
+                    elapsed,
+                    usage: reading.usage,
+                });
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Vec<ResourceSample>>> {
+        self.series
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::{
         Arc,
         atomic::{AtomicU64, Ordering},
@@ -14,7 +14,7 @@ use nomos_node::HeaderId;
 use tokio::{sync::broadcast, task::JoinHandle, time::sleep};
 use tracing::{debug, error};
 
-use super::context::CleanupGuard;
+use super::{chain_tree::ChainTree, context::CleanupGuard, node_clients::NodeClients};
 use crate::nodes::ApiClient;
 
 const POLL_INTERVAL: Duration = Duration::from_secs(1);
@@ -28,6 +28,7 @@ pub struct BlockFeed {
 struct BlockFeedInner {
     sender: broadcast::Sender<Arc<BlockRecord>>,
     stats: Arc<BlockStats>,
+    chain: Arc<ChainTree>,
 }
 
 /// Block header + payload snapshot emitted by the feed.
@@ -53,6 +54,13 @@ impl BlockFeed {
         Arc::clone(&self.inner.stats)
     }
 
+    #[must_use]
+    /// Block tree built from every tracked node's view of the chain, with the
+    /// forks and reorgs observed so far.
+    pub fn chain(&self) -> Arc<ChainTree> {
+        Arc::clone(&self.inner.chain)
+    }
+
     fn ingest(&self, header: HeaderId, block: Block<SignedMantleTx>) {
         self.inner.stats.record_block(&block);
         let record = Arc::new(BlockRecord {
@@ -73,17 +81,29 @@ impl BlockFeedTask {
 }
 
 /// Spawn a background task to poll blocks from the given client and broadcast
-/// them.
+/// them. Fork tracking only follows this client; use
+/// [`spawn_block_feed_with_nodes`] to track every node.
 pub async fn spawn_block_feed(client: ApiClient) -> Result<(BlockFeed, BlockFeedTask)> {
+    let nodes = NodeClients::new(vec![client.clone()]);
+    spawn_block_feed_with_nodes(client, nodes).await
+}
+
+/// Spawn a block feed that broadcasts blocks from `client` and tracks forks
+/// and reorgs across every node in `nodes`.
+pub async fn spawn_block_feed_with_nodes(
+    client: ApiClient,
+    nodes: NodeClients,
+) -> Result<(BlockFeed, BlockFeedTask)> {
     let (sender, _) = broadcast::channel(1024);
     let feed = BlockFeed {
         inner: Arc::new(BlockFeedInner {
             sender,
             stats: Arc::new(BlockStats::default()),
+            chain: Arc::new(ChainTree::default()),
         }),
     };
 
-    let mut scanner = BlockScanner::new(client, feed.clone());
+    let mut scanner = BlockScanner::new(client, nodes, feed.clone());
     scanner.catch_up().await?;
 
     let handle = tokio::spawn(async move { scanner.run().await });
@@ -93,16 +113,21 @@ pub async fn spawn_block_feed(client: ApiClient) -> Result<(BlockFeed, BlockFeed
 
 struct BlockScanner {
     client: ApiClient,
+    nodes: NodeClients,
     feed: BlockFeed,
     seen: HashSet<HeaderId>,
+    /// Latest tip fed into the chain tree for each node, by index.
+    observed_tips: HashMap<usize, HeaderId>,
 }
 
 impl BlockScanner {
-    fn new(client: ApiClient, feed: BlockFeed) -> Self {
+    fn new(client: ApiClient, nodes: NodeClients, feed: BlockFeed) -> Self {
         Self {
             client,
+            nodes,
             feed,
             seen: HashSet::new(),
+            observed_tips: HashMap::new(),
         }
     }
 
@@ -111,10 +136,64 @@ impl BlockScanner {
             if let Err(err) = self.catch_up().await {
                 error!(error = %err, error_debug = ?err, "block feed catch up failed");
             }
+            self.observe_nodes().await;
             sleep(POLL_INTERVAL).await;
         }
     }
 
+    /// Feed the headers each node added since the last poll into the chain
+    /// tree, then drop the part of the tree every node has finalized.
+    async fn observe_nodes(&mut self) {
+        let chain = self.feed.chain();
+        let clients = self.nodes.node_clients();
+        let node_count = clients.len();
+        let mut libs = Vec::with_capacity(node_count);
+        for (index, client) in clients.into_iter().enumerate() {
+            let info = match client.consensus_info().await {
+                Ok(info) => info,
+                Err(err) => {
+                    debug!(node = index, error = %err, "chain tracking consensus_info failed");
+                    continue;
+                }
+            };
+            libs.push(info.lib);
+
+            let last_tip = self.observed_tips.get(&index).copied();
+            if last_tip == Some(info.tip) {
+                continue;
+            }
+            // Headers run from the tip back to the previously observed tip, or
+            // to the LIB on the first poll, both included.
+            let to = last_tip.unwrap_or(info.lib);
+            let headers = match client.consensus_headers(Some(info.tip), Some(to)).await {
+                Ok(headers) => headers,
+                Err(err) => {
+                    debug!(node = index, error = %err, "chain tracking consensus_headers failed");
+                    // The previous tip may have been reorged away; walk back to
+                    // the LIB next time.
+                    self.observed_tips.remove(&index);
+                    continue;
+                }
+            };
+            if headers.first() != Some(&info.tip) {
+                debug!(
+                    node = index,
+                    "chain tracking headers do not start at tip; skipping"
+                );
+                continue;
+            }
+
+            chain.observe(index, info.height, &headers);
+            self.observed_tips.insert(index, info.tip);
+        }
+
+        // A node that could not be queried may still sit below the others'
+        // LIBs, so only prune when every node reported one.
+        if libs.len() == node_count {
+            chain.prune(&libs);
+        }
+    }
+
     async fn catch_up(&mut self) -> Result<()> {
         let info = self.client.consensus_info().await?;
         let tip = info.tip;
@@ -181,15 +260,22 @@ impl CleanupGuard for BlockFeedTask {
 /// Accumulates simple counters over observed blocks.
 #[derive(Default)]
 pub struct BlockStats {
+    total_blocks: AtomicU64,
     total_transactions: AtomicU64,
 }
 
 impl BlockStats {
     fn record_block(&self, block: &Block<SignedMantleTx>) {
+        self.total_blocks.fetch_add(1, Ordering::Relaxed);
         self.total_transactions
             .fetch_add(block.transactions().len() as u64, Ordering::Relaxed);
     }
 
+    #[must_use]
+    pub fn total_blocks(&self) -> u64 {
+        self.total_blocks.load(Ordering::Relaxed)
+    }
+
     #[must_use]
     pub fn total_transactions(&self) -> u64 {
         self.total_transactions.load(Ordering::Relaxed)
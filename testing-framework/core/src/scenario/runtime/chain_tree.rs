@@ -0,0 +1,405 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Mutex,
+};
+
+use nomos_node::HeaderId;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+/// Two or more blocks observed on top of the same parent.
+#[derive(Clone, Debug)]
+pub struct ForkEvent {
+    /// Last block shared by the competing branches.
+    pub fork_point: HeaderId,
+    pub height: u64,
+    /// Current node tips that descend from the fork point.
+    pub competing_tips: Vec<HeaderId>,
+    /// Nodes whose tip descends from the fork point, by index.
+    pub nodes: Vec<usize>,
+}
+
+/// A node switched to a tip that does not extend its previous tip.
+#[derive(Clone, Debug)]
+pub struct ReorgEvent {
+    pub node: usize,
+    pub old_tip: HeaderId,
+    pub new_tip: HeaderId,
+    /// Deepest block shared by both tips, when it is still known.
+    pub common_ancestor: Option<HeaderId>,
+    /// Number of blocks rolled back from the old tip.
+    pub depth: u64,
+}
+
+#[derive(Clone, Debug)]
+pub enum ChainEvent {
+    Fork(ForkEvent),
+    Reorg(ReorgEvent),
+}
+
+/// Block tree assembled from the headers every node reports, used to detect
+/// forks and reorgs during a run.
+pub struct ChainTree {
+    inner: Mutex<ChainTreeInner>,
+    events: broadcast::Sender<ChainEvent>,
+}
+
+#[derive(Default)]
+struct ChainTreeInner {
+    parents: HashMap<HeaderId, HeaderId>,
+    children: HashMap<HeaderId, Vec<HeaderId>>,
+    heights: HashMap<HeaderId, u64>,
+    tips: BTreeMap<usize, HeaderId>,
+    forks: Vec<ForkEvent>,
+    reorgs: Vec<ReorgEvent>,
+}
+
+impl Default for ChainTree {
+    fn default() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            inner: Mutex::new(ChainTreeInner::default()),
+            events,
+        }
+    }
+}
+
+impl ChainTree {
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+        self.events.subscribe()
+    }
+
+    #[must_use]
+    pub fn forks(&self) -> Vec<ForkEvent> {
+        self.lock().forks.clone()
+    }
+
+    #[must_use]
+    pub fn reorgs(&self) -> Vec<ReorgEvent> {
+        self.lock().reorgs.clone()
+    }
+
+    #[must_use]
+    /// Deepest reorg observed on any node so far.
+    pub fn max_reorg_depth(&self) -> u64 {
+        self.lock()
+            .reorgs
+            .iter()
+            .map(|reorg| reorg.depth)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[must_use]
+    /// Latest tip reported by each node, by index.
+    pub fn tips(&self) -> BTreeMap<usize, HeaderId> {
+        self.lock().tips.clone()
+    }
+
+    #[must_use]
+    /// Whether `header` is known but no longer an ancestor of any node's
+    /// latest tip, i.e. every node has reorged away from it. Unknown headers
+    /// are not orphaned.
+    pub fn is_orphaned(&self, header: HeaderId) -> bool {
+        let inner = self.lock();
+        inner.heights.contains_key(&header)
+            && !inner.tips.is_empty()
+            && !inner
+                .tips
+                .values()
+                .any(|tip| inner.is_ancestor(header, *tip))
+    }
+
+    /// Record a node's view of the chain: `headers` runs from its tip (at
+    /// `height`) back towards a block already recorded for it or its last
+    /// irreversible block.
+    pub(crate) fn observe(&self, node: usize, height: u64, headers: &[HeaderId]) {
+        let Some(&tip) = headers.first() else {
+            return;
+        };
+
+        let events = {
+            let mut inner = self.lock();
+            let mut events = Vec::new();
+            let mut fork_points = Vec::new();
+
+            for (offset, pair) in headers.windows(2).enumerate() {
+                let (child, parent) = (pair[0], pair[1]);
+                if inner.link(child, parent) {
+                    fork_points.push(parent);
+                }
+                inner
+                    .heights
+                    .insert(child, height.saturating_sub(offset as u64));
+            }
+            if let Some(&last) = headers.last() {
+                let last_height = height.saturating_sub(headers.len() as u64 - 1);
+                inner.heights.insert(last, last_height);
+            }
+
+            if let Some(old_tip) = inner.tips.insert(node, tip) {
+                if old_tip != tip && !inner.is_ancestor(old_tip, tip) {
+                    let lib = headers.last().copied();
+                    let reorg = inner.reorg(node, old_tip, tip, lib);
+                    inner.reorgs.push(reorg.clone());
+                    events.push(ChainEvent::Reorg(reorg));
+                }
+            }
+
+            for fork_point in fork_points {
+                let fork = inner.fork(fork_point);
+                inner.forks.push(fork.clone());
+                events.push(ChainEvent::Fork(fork));
+            }
+
+            events
+        };
+
+        for event in events {
+            match &event {
+                ChainEvent::Fork(fork) => info!(
+                    fork_point = ?fork.fork_point,
+                    height = fork.height,
+                    tips = fork.competing_tips.len(),
+                    nodes = ?fork.nodes,
+                    "chain fork observed"
+                ),
+                ChainEvent::Reorg(reorg) => warn!(
+                    node = reorg.node,
+                    depth = reorg.depth,
+                    old_tip = ?reorg.old_tip,
+                    new_tip = ?reorg.new_tip,
+                    "chain reorg observed"
+                ),
+            }
+            let _ = self.events.send(event);
+        }
+    }
+
+    /// Forget blocks below the lowest of the given last irreversible blocks;
+    /// no node can fork or reorg below it. Does nothing while any of them is
+    /// unknown.
+    pub(crate) fn prune(&self, libs: &[HeaderId]) {
+        let mut inner = self.lock();
+        let Some(floor) = libs
+            .iter()
+            .map(|lib| inner.heights.get(lib).copied())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|heights| heights.into_iter().min())
+        else {
+            return;
+        };
+
+        let ChainTreeInner {
+            parents,
+            children,
+            heights,
+            ..
+        } = &mut *inner;
+        heights.retain(|_, height| *height >= floor);
+        parents.retain(|child, parent| heights.contains_key(child) && heights.contains_key(parent));
+        children.retain(|parent, _| heights.contains_key(parent));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ChainTreeInner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ChainTreeInner {
+    /// Link `child` to `parent`; returns true when this creates a new fork.
+    fn link(&mut self, child: HeaderId, parent: HeaderId) -> bool {
+        if self.parents.insert(child, parent).is_some() {
+            return false;
+        }
+        let siblings = self.children.entry(parent).or_default();
+        siblings.push(child);
+        siblings.len() > 1
+    }
+
+    fn ancestors(&self, from: HeaderId) -> impl Iterator<Item = HeaderId> + '_ {
+        std::iter::successors(Some(from), |header| self.parents.get(header).copied())
+    }
+
+    fn is_ancestor(&self, ancestor: HeaderId, of: HeaderId) -> bool {
+        let floor = self.heights.get(&ancestor).copied();
+        self.ancestors(of)
+            .take_while(|header| match (floor, self.heights.get(header)) {
+                (Some(floor), Some(height)) => *height >= floor,
+                _ => true,
+            })
+            .any(|header| header == ancestor)
+    }
+
+    fn reorg(
+        &self,
+        node: usize,
+        old_tip: HeaderId,
+        new_tip: HeaderId,
+        lib: Option<HeaderId>,
+    ) -> ReorgEvent {
+        let new_branch = self.ancestors(new_tip).collect::<HashSet<_>>();
+        let common_ancestor = self
+            .ancestors(old_tip)
+            .find(|header| new_branch.contains(header));
+        let old_height = self.heights.get(&old_tip).copied().unwrap_or(0);
+        // Without a known ancestor the rollback reached at least the reported
+        // last irreversible block.
+        let floor = common_ancestor
+            .or(lib)
+            .and_then(|header| self.heights.get(&header).copied())
+            .unwrap_or(0);
+
+        ReorgEvent {
+            node,
+            old_tip,
+            new_tip,
+            common_ancestor,
+            depth: old_height.saturating_sub(floor),
+        }
+    }
+
+    fn fork(&self, fork_point: HeaderId) -> ForkEvent {
+        let mut competing_tips = Vec::new();
+        let mut nodes = Vec::new();
+        for (node, tip) in &self.tips {
+            if self.ancestors(*tip).any(|header| header == fork_point) {
+                nodes.push(*node);
+                if !competing_tips.contains(tip) {
+                    competing_tips.push(*tip);
+                }
+            }
+        }
+
+        ForkEvent {
+            fork_point,
+            height: self.heights.get(&fork_point).copied().unwrap_or(0),
+            competing_tips,
+            nodes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(byte: u8) -> HeaderId {
+        HeaderId::from([byte; 32])
+    }
+
+    #[test]
+    fn linear_chain_records_no_forks_or_reorgs() {
+        let tree = ChainTree::default();
+        let mut events = tree.subscribe();
+
+        tree.observe(0, 2, &[header(2), header(1), header(0)]);
+        tree.observe(1, 2, &[header(2), header(1), header(0)]);
+        tree.observe(0, 3, &[header(3), header(2), header(1)]);
+
+        assert!(tree.forks().is_empty());
+        assert!(tree.reorgs().is_empty());
+        assert_eq!(tree.max_reorg_depth(), 0);
+        assert_eq!(tree.tips().get(&0), Some(&header(3)));
+        assert_eq!(tree.tips().get(&1), Some(&header(2)));
+        assert!(!tree.is_orphaned(header(1)));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn one_block_fork_switch_is_a_depth_one_reorg() {
+        let tree = ChainTree::default();
+        let genesis = header(0);
+        let (a1, b1, b2) = (header(0xa1), header(0xb1), header(0xb2));
+
+        tree.observe(0, 1, &[a1, genesis]);
+        tree.observe(1, 1, &[b1, genesis]);
+
+        let forks = tree.forks();
+        assert_eq!(forks.len(), 1);
+        assert_eq!(forks[0].fork_point, genesis);
+        assert_eq!(forks[0].height, 0);
+        assert_eq!(forks[0].nodes, vec![0, 1]);
+        assert!(forks[0].competing_tips.contains(&a1));
+        assert!(forks[0].competing_tips.contains(&b1));
+        assert!(tree.reorgs().is_empty());
+
+        let mut events = tree.subscribe();
+        tree.observe(0, 2, &[b2, b1, genesis]);
+
+        let reorgs = tree.reorgs();
+        assert_eq!(reorgs.len(), 1);
+        assert_eq!(reorgs[0].node, 0);
+        assert_eq!(reorgs[0].old_tip, a1);
+        assert_eq!(reorgs[0].new_tip, b2);
+        assert_eq!(reorgs[0].common_ancestor, Some(genesis));
+        assert_eq!(reorgs[0].depth, 1);
+        assert!(matches!(
+            events.try_recv(),
+            Ok(ChainEvent::Reorg(ReorgEvent {
+                node: 0,
+                depth: 1,
+                ..
+            }))
+        ));
+        assert!(tree.is_orphaned(a1));
+        assert!(!tree.is_orphaned(b1));
+    }
+
+    #[test]
+    fn deep_reorg_measures_depth_from_common_ancestor() {
+        let tree = ChainTree::default();
+        let genesis = header(0);
+        let branch_a = [header(0xa3), header(0xa2), header(0xa1), genesis];
+        let branch_b = [
+            header(0xb4),
+            header(0xb3),
+            header(0xb2),
+            header(0xb1),
+            genesis,
+        ];
+
+        tree.observe(0, 3, &branch_a);
+        tree.observe(1, 4, &branch_b);
+        tree.observe(0, 4, &branch_b);
+
+        let reorgs = tree.reorgs();
+        assert_eq!(reorgs.len(), 1);
+        assert_eq!(reorgs[0].old_tip, header(0xa3));
+        assert_eq!(reorgs[0].new_tip, header(0xb4));
+        assert_eq!(reorgs[0].common_ancestor, Some(genesis));
+        assert_eq!(reorgs[0].depth, 3);
+        assert_eq!(tree.max_reorg_depth(), 3);
+        assert_eq!(tree.tips().get(&0), tree.tips().get(&1));
+    }
+
+    #[test]
+    fn prune_drops_blocks_below_the_lowest_lib() {
+        let tree = ChainTree::default();
+        let chain = [header(4), header(3), header(2), header(1), header(0)];
+
+        tree.observe(0, 4, &chain);
+        tree.observe(1, 3, &chain[1..]);
+
+        tree.prune(&[header(3), header(9)]);
+        assert_eq!(tree.lock().heights.len(), 5);
+
+        tree.prune(&[header(3), header(2)]);
+        {
+            let inner = tree.lock();
+            assert_eq!(inner.heights.len(), 3);
+            assert!(!inner.heights.contains_key(&header(1)));
+            assert!(!inner.parents.contains_key(&header(2)));
+            assert!(!inner.children.contains_key(&header(1)));
+        }
+        assert!(!tree.is_orphaned(header(2)));
+
+        tree.observe(1, 5, &[header(0xb5), header(0xb4), header(3)]);
+        assert!(tree.reorgs().is_empty());
+        assert_eq!(tree.forks().len(), 1);
+        assert_eq!(tree.forks()[0].fork_point, header(3));
+    }
+}
@@ -1,9 +1,16 @@
 use std::{sync::Arc, time::Duration};
 
-use super::{block_feed::BlockFeed, metrics::Metrics, node_clients::ClusterClient};
+use rand::{Rng, rngs::StdRng};
+
+use super::{
+    block_feed::BlockFeed, metrics::Metrics, node_clients::ClusterClient, report::ScenarioReport,
+};
 use crate::{
     nodes::ApiClient,
-    scenario::{NodeClients, NodeControlHandle},
+    scenario::{
+        LinkShaping, NodeClients, NodeControlHandle, NodeLogSource, NodeSupervisor,
+        ResourceSampler, ResourceSeries, ScenarioSeed,
+    },
     topology::{
         configs::wallet::WalletAccount, deployment::Topology, generation::GeneratedTopology,
     },
@@ -18,6 +25,10 @@ pub struct RunContext {
     telemetry: Metrics,
     block_feed: BlockFeed,
     node_control: Option<Arc<dyn NodeControlHandle>>,
+    link_shaping: Option<LinkShaping>,
+    node_logs: Option<Arc<dyn NodeLogSource>>,
+    node_supervisor: Option<Arc<dyn NodeSupervisor>>,
+    resource_usage: Option<ResourceSeries>,
 }
 
 impl RunContext {
@@ -44,14 +55,63 @@ impl RunContext {
             telemetry,
             block_feed,
             node_control,
+            link_shaping: None,
+            node_logs: None,
+            node_supervisor: None,
+            resource_usage: None,
         }
     }
 
+    #[must_use]
+    /// Expose the scenario's link shaping handle to workloads; set by
+    /// deployers that apply shaping.
+    pub fn with_link_shaping(mut self, link_shaping: LinkShaping) -> Self {
+        self.link_shaping = Some(link_shaping);
+        self
+    }
+
+    #[must_use]
+    /// Expose the nodes' logs to expectations; set by deployers that can
+    /// collect them.
+    pub fn with_node_logs(mut self, node_logs: Arc<dyn NodeLogSource>) -> Self {
+        self.node_logs = Some(node_logs);
+        self
+    }
+
+    #[must_use]
+    /// Let the runner watch for nodes exiting unexpectedly; set by deployers
+    /// that can observe their node processes.
+    pub fn with_node_supervisor(mut self, supervisor: Arc<dyn NodeSupervisor>) -> Self {
+        self.node_supervisor = Some(supervisor);
+        self
+    }
+
+    #[must_use]
+    /// Let the runner record each node's resource usage during the run; set
+    /// by deployers that can measure their nodes.
+    pub fn with_resource_sampler(mut self, sampler: Arc<dyn ResourceSampler>) -> Self {
+        self.resource_usage = Some(ResourceSeries::new(sampler));
+        self
+    }
+
     #[must_use]
     pub const fn descriptors(&self) -> &GeneratedTopology {
         &self.descriptors
     }
 
+    #[must_use]
+    /// Seed of the scenario being run.
+    pub const fn seed(&self) -> ScenarioSeed {
+        self.descriptors.seed()
+    }
+
+    #[must_use]
+    /// Seeded generator for one consumer of randomness, e.g. a workload's
+    /// name. Calls with the same `stream` start the same sequence.
+    pub fn rng(&self, stream: &str) -> StdRng {
+        self.seed().rng(stream)
+    }
+
     #[must_use]
     pub const fn topology(&self) -> Option<&Topology> {
         self.cluster.as_ref()
@@ -63,8 +123,9 @@ impl RunContext {
     }
 
     #[must_use]
-    pub fn random_node_client(&self) -> Option<ApiClient> {
-        self.node_clients.any_client()
+    /// Random node client drawn from `rng`, typically one from [`Self::rng`].
+    pub fn random_node_client(&self, rng: &mut impl Rng) -> Option<ApiClient> {
+        self.node_clients.any_client_with(rng)
     }
 
     #[must_use]
@@ -102,6 +163,27 @@ impl RunContext {
         self.node_control.clone()
     }
 
+    #[must_use]
+    pub const fn link_shaping(&self) -> Option<&LinkShaping> {
+        self.link_shaping.as_ref()
+    }
+
+    #[must_use]
+    pub fn node_logs(&self) -> Option<Arc<dyn NodeLogSource>> {
+        self.node_logs.clone()
+    }
+
+    #[must_use]
+    pub fn node_supervisor(&self) -> Option<Arc<dyn NodeSupervisor>> {
+        self.node_supervisor.clone()
+    }
+
+    #[must_use]
+    /// Resource usage recorded so far, if the deployer can measure its nodes.
+    pub const fn resource_usage(&self) -> Option<&ResourceSeries> {
+        self.resource_usage.as_ref()
+    }
+
     #[must_use]
     pub const fn cluster_client(&self) -> ClusterClient<'_> {
         self.node_clients.cluster_client()
@@ -112,6 +194,7 @@ impl RunContext {
 pub struct RunHandle {
     run_context: Arc<RunContext>,
     cleanup_guard: Option<Box<dyn CleanupGuard>>,
+    report: Option<ScenarioReport>,
 }
 
 impl Drop for RunHandle {
@@ -129,6 +212,7 @@ impl RunHandle {
         Self {
             run_context: Arc::new(context),
             cleanup_guard,
+            report: None,
         }
     }
 
@@ -137,10 +221,12 @@ impl RunHandle {
     pub(crate) fn from_shared(
         context: Arc<RunContext>,
         cleanup_guard: Option<Box<dyn CleanupGuard>>,
+        report: Option<ScenarioReport>,
     ) -> Self {
         Self {
             run_context: context,
             cleanup_guard,
+            report,
         }
     }
 
@@ -149,6 +235,13 @@ impl RunHandle {
     pub fn context(&self) -> &RunContext {
         &self.run_context
     }
+
+    #[must_use]
+    /// Report of the run that produced this handle, if it came from
+    /// [`Runner::run`](super::Runner::run).
+    pub const fn report(&self) -> Option<&ScenarioReport> {
+        self.report.as_ref()
+    }
 }
 
 /// Derived metrics about the current run timing.
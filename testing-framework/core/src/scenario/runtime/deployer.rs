@@ -12,6 +12,14 @@ pub enum ScenarioError {
     ExpectationCapture(#[source] DynError),
     #[error("expectations failed:\n{0}")]
     Expectations(#[source] DynError),
+    #[error("expectation `{expectation}` failed during the run: {source}")]
+    Monitor {
+        expectation: String,
+        #[source]
+        source: DynError,
+    },
+    #[error("nodes exited unexpectedly:\n{0}")]
+    NodeExit(String),
 }
 
 /// Deploys a scenario into a target environment and returns a `Runner`.
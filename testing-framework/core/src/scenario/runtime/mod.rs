@@ -1,12 +1,22 @@
 mod block_feed;
+mod chain_tree;
 pub mod context;
 mod deployer;
 pub mod metrics;
 mod node_clients;
+mod report;
 mod runner;
 
-pub use block_feed::{BlockFeed, BlockFeedTask, BlockRecord, BlockStats, spawn_block_feed};
+pub use block_feed::{
+    BlockFeed, BlockFeedTask, BlockRecord, BlockStats, spawn_block_feed,
+    spawn_block_feed_with_nodes,
+};
+pub use chain_tree::{ChainEvent, ChainTree, ForkEvent, ReorgEvent};
 pub use context::{CleanupGuard, RunContext, RunHandle, RunMetrics};
 pub use deployer::{Deployer, ScenarioError};
 pub use node_clients::NodeClients;
+pub use report::{
+    BlockReport, ExpectationReport, ExpectationStatus, ScenarioReport, WorkloadReport,
+    WorkloadStatus,
+};
 pub use runner::Runner;
@@ -3,7 +3,7 @@ use std::{
     sync::{Arc, RwLock},
 };
 
-use rand::{Rng as _, seq::SliceRandom as _, thread_rng};
+use rand::{Rng, seq::SliceRandom as _};
 
 use crate::{
     nodes::ApiClient,
@@ -12,6 +12,10 @@ use crate::{
 };
 
 /// Collection of API clients for the node set.
+///
+/// Random picks take the caller's generator, e.g. one from
+/// [`RunContext::rng`](crate::scenario::RunContext::rng), so each consumer
+/// replays its own choices for a given scenario seed.
 #[derive(Clone, Default)]
 pub struct NodeClients {
     inner: Arc<RwLock<NodeClientsInner>>,
@@ -53,13 +57,12 @@ impl NodeClients {
     }
 
     #[must_use]
-    /// Choose a random node client if present.
-    pub fn random_node(&self) -> Option<ApiClient> {
+    /// Choose a random node client if present, drawing from `rng`.
+    pub fn random_node_with(&self, rng: &mut impl Rng) -> Option<ApiClient> {
         let nodes = self.node_clients();
         if nodes.is_empty() {
             return None;
         }
-        let mut rng = thread_rng();
         let idx = rng.gen_range(0..nodes.len());
         nodes.get(idx).cloned()
     }
@@ -75,8 +78,8 @@ impl NodeClients {
     }
 
     #[must_use]
-    /// Choose any random client from nodes.
-    pub fn any_client(&self) -> Option<ApiClient> {
+    /// Choose any random client from nodes, drawing from `rng`.
+    pub fn any_client_with(&self, rng: &mut impl Rng) -> Option<ApiClient> {
         let guard = self
             .inner
             .read()
@@ -86,11 +89,18 @@ impl NodeClients {
         if total == 0 {
             return None;
         }
-        let mut rng = thread_rng();
         let choice = rng.gen_range(0..total);
         guard.nodes.get(choice).cloned()
     }
 
+    #[must_use]
+    /// All clients in a random order drawn from `rng`.
+    pub fn shuffled_clients_with(&self, rng: &mut impl Rng) -> Vec<ApiClient> {
+        let mut clients = self.all_clients();
+        clients.shuffle(rng);
+        clients
+    }
+
     #[must_use]
     /// Convenience wrapper for fan-out queries.
     pub const fn cluster_client(&self) -> ClusterClient<'_> {
@@ -127,9 +137,11 @@ impl<'a> ClusterClient<'a> {
         Self { node_clients }
     }
 
-    /// Try all node clients until one call succeeds, shuffling order each time.
+    /// Try all node clients until one call succeeds, in an order shuffled by
+    /// `rng` each time.
     pub async fn try_all_clients<T, E>(
         &self,
+        rng: &mut (impl Rng + Send),
         mut f: impl for<'b> FnMut(
             &'b ApiClient,
         ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'b>>
@@ -138,13 +150,11 @@ impl<'a> ClusterClient<'a> {
     where
         E: Into<DynError>,
     {
-        let mut clients = self.node_clients.all_clients();
+        let clients = self.node_clients.shuffled_clients_with(rng);
         if clients.is_empty() {
             return Err("cluster client has no api clients".into());
         }
 
-        clients.shuffle(&mut thread_rng());
-
         let mut last_err = None;
         for client in &clients {
             match f(client).await {
@@ -0,0 +1,289 @@
+use std::{fmt::Write as _, time::Duration};
+
+use serde::Serialize;
+use serde_with::{DurationSecondsWithFrac, serde_as};
+
+use crate::scenario::NodeExit;
+
+/// Structured outcome of a scenario run, suitable for CI artifacts.
+#[serde_as]
+#[derive(Clone, Debug, Serialize)]
+pub struct ScenarioReport {
+    /// Deployer that provisioned the run (`local`, `compose`, `k8s`, ...).
+    pub deployer: Option<String>,
+    pub nodes: usize,
+    /// Seed of the run; set `LOGOS_BLOCKCHAIN_TESTS_SEED` to it to replay the
+    /// run's random choices.
+    pub seed: u64,
+    /// Wall-clock start of the run, in milliseconds since the Unix epoch.
+    pub started_at_unix_ms: u64,
+    /// Configured run duration.
+    #[serde_as(as = "DurationSecondsWithFrac<f64>")]
+    pub run_duration: Duration,
+    /// Time spent in `Runner::run`, including cooldown and evaluation.
+    #[serde_as(as = "DurationSecondsWithFrac<f64>")]
+    pub elapsed: Duration,
+    pub workloads: Vec<WorkloadReport>,
+    pub expectations: Vec<ExpectationReport>,
+    pub blocks: BlockReport,
+    /// Nodes that exited without being asked to.
+    pub node_exits: Vec<NodeExit>,
+    /// Error that ended the run, if any.
+    pub error: Option<String>,
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Serialize)]
+pub struct WorkloadReport {
+    pub name: String,
+    #[serde(flatten)]
+    pub status: WorkloadStatus,
+    /// Time from spawn until the workload returned, if it did.
+    #[serde_as(as = "Option<DurationSecondsWithFrac<f64>>")]
+    pub elapsed: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum WorkloadStatus {
+    /// Returned `Ok` before the run ended.
+    Completed,
+    /// Still running when the runner aborted it at the end of the run.
+    Stopped,
+    Failed {
+        error: String,
+    },
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Serialize)]
+pub struct ExpectationReport {
+    pub name: String,
+    #[serde(flatten)]
+    pub status: ExpectationStatus,
+    #[serde_as(as = "Option<DurationSecondsWithFrac<f64>>")]
+    pub elapsed: Option<Duration>,
+    /// Extra data published by the expectation via
+    /// [`Expectation::report_details`](crate::scenario::Expectation::report_details).
+    pub details: Option<serde_json::Value>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ExpectationStatus {
+    Passed,
+    Failed {
+        error: String,
+    },
+    /// Not evaluated because the run failed earlier.
+    Skipped,
+}
+
+/// Counters collected by the block feed over the run.
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct BlockReport {
+    pub blocks: u64,
+    pub transactions: u64,
+    pub forks: usize,
+    pub reorgs: usize,
+    pub max_reorg_depth: u64,
+}
+
+impl ScenarioReport {
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Serialize the report as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    #[must_use]
+    /// Render the report as a JUnit XML document with one test case per
+    /// workload and expectation.
+    pub fn to_junit_xml(&self) -> String {
+        let suite = match &self.deployer {
+            Some(deployer) => format!("scenario.{deployer}"),
+            None => "scenario".to_owned(),
+        };
+        let tests = self.workloads.len() + self.expectations.len();
+        let failures = self
+            .workloads
+            .iter()
+            .filter(|workload| matches!(workload.status, WorkloadStatus::Failed { .. }))
+            .count()
+            + self
+                .expectations
+                .iter()
+                .filter(|expectation| {
+                    matches!(expectation.status, ExpectationStatus::Failed { .. })
+                })
+                .count();
+        let skipped = self
+            .expectations
+            .iter()
+            .filter(|expectation| expectation.status == ExpectationStatus::Skipped)
+            .count();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{:.3}\">",
+            escape_xml(&suite),
+            self.elapsed.as_secs_f64()
+        );
+        xml.push_str("    <properties>\n");
+        for (name, value) in [
+            ("nodes", self.nodes.to_string()),
+            ("seed", self.seed.to_string()),
+            (
+                "run_duration_secs",
+                format!("{:.3}", self.run_duration.as_secs_f64()),
+            ),
+            ("blocks", self.blocks.blocks.to_string()),
+            ("transactions", self.blocks.transactions.to_string()),
+            ("forks", self.blocks.forks.to_string()),
+            ("reorgs", self.blocks.reorgs.to_string()),
+            ("max_reorg_depth", self.blocks.max_reorg_depth.to_string()),
+            ("node_exits", self.node_exits.len().to_string()),
+        ] {
+            let _ = writeln!(xml, "      <property name=\"{name}\" value=\"{value}\"/>");
+        }
+        xml.push_str("    </properties>\n");
+
+        for workload in &self.workloads {
+            let failure = match &workload.status {
+                WorkloadStatus::Failed { error } => Some(error.as_str()),
+                WorkloadStatus::Completed | WorkloadStatus::Stopped => None,
+            };
+            push_test_case(
+                &mut xml,
+                "workload",
+                &workload.name,
+                workload.elapsed,
+                failure,
+                false,
+            );
+        }
+        for expectation in &self.expectations {
+            let failure = match &expectation.status {
+                ExpectationStatus::Failed { error } => Some(error.as_str()),
+                ExpectationStatus::Passed | ExpectationStatus::Skipped => None,
+            };
+            push_test_case(
+                &mut xml,
+                "expectation",
+                &expectation.name,
+                expectation.elapsed,
+                failure,
+                expectation.status == ExpectationStatus::Skipped,
+            );
+        }
+
+        if let Some(error) = &self.error {
+            let _ = writeln!(xml, "    <system-err>{}</system-err>", escape_xml(error));
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+fn push_test_case(
+    xml: &mut String,
+    classname: &str,
+    name: &str,
+    elapsed: Option<Duration>,
+    failure: Option<&str>,
+    skipped: bool,
+) {
+    let time = elapsed.unwrap_or_default().as_secs_f64();
+    let _ = write!(
+        xml,
+        "    <testcase classname=\"{classname}\" name=\"{}\" time=\"{time:.3}\"",
+        escape_xml(name)
+    );
+    if let Some(error) = failure {
+        let _ = writeln!(
+            xml,
+            ">\n      <failure message=\"{}\"/>\n    </testcase>",
+            escape_xml(error)
+        );
+    } else if skipped {
+        xml.push_str(">\n      <skipped/>\n    </testcase>\n");
+    } else {
+        xml.push_str("/>\n");
+    }
+}
+
+/// Escape `value` for use in XML text or attributes. Control characters XML
+/// 1.0 cannot represent (e.g. the ANSI escapes in colored error output) are
+/// replaced with U+FFFD.
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => escaped.push('\u{fffd}'),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_with_error(error: &str) -> ScenarioReport {
+        ScenarioReport {
+            deployer: Some("local".to_owned()),
+            nodes: 2,
+            seed: 7,
+            started_at_unix_ms: 0,
+            run_duration: Duration::from_secs(60),
+            elapsed: Duration::from_secs(61),
+            workloads: vec![WorkloadReport {
+                name: "tx_workload".to_owned(),
+                status: WorkloadStatus::Completed,
+                elapsed: Some(Duration::from_secs(10)),
+            }],
+            expectations: vec![ExpectationReport {
+                name: "consensus_liveness".to_owned(),
+                status: ExpectationStatus::Failed {
+                    error: error.to_owned(),
+                },
+                elapsed: Some(Duration::from_secs(1)),
+                details: None,
+            }],
+            blocks: BlockReport::default(),
+            node_exits: Vec::new(),
+            error: Some(error.to_owned()),
+        }
+    }
+
+    #[test]
+    fn junit_xml_escapes_markup_quotes_and_control_characters() {
+        let error = "height <3> & \"lagging\" 'node-1'\r\n\tat \u{1b}[31mred\u{1b}[0m\u{0}";
+
+        let xml = report_with_error(error).to_junit_xml();
+
+        let escaped = "height &lt;3&gt; &amp; &quot;lagging&quot; &apos;node-1&apos;&#13;&#10;&#9;at \
+                       \u{fffd}[31mred\u{fffd}[0m\u{fffd}";
+        assert!(xml.contains(&format!("<failure message=\"{escaped}\"/>")));
+        assert!(xml.contains(&format!("<system-err>{escaped}</system-err>")));
+        assert!(xml.contains("tests=\"2\" failures=\"1\" skipped=\"0\""));
+        assert!(
+            !xml.chars().any(|ch| ch.is_control() && ch != '\n'),
+            "raw control characters in {xml:?}"
+        );
+    }
+}
@@ -1,28 +1,45 @@
-use std::{any::Any, panic::AssertUnwindSafe, sync::Arc, time::Duration};
+use std::{
+    any::Any,
+    panic::AssertUnwindSafe,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 use futures::FutureExt as _;
 use tokio::{
-    task::JoinSet,
-    time::{sleep, timeout},
+    task::{JoinHandle, JoinSet},
+    time::{sleep, sleep_until, timeout},
 };
 
-use super::deployer::ScenarioError;
+use super::{
+    deployer::ScenarioError,
+    report::{
+        BlockReport, ExpectationReport, ExpectationStatus, ScenarioReport, WorkloadReport,
+        WorkloadStatus,
+    },
+};
 use crate::scenario::{
-    DynError, Expectation, Scenario,
+    DynError, Expectation, NodeExit, NodeSupervisor, Scenario,
     runtime::context::{CleanupGuard, RunContext, RunHandle},
 };
 
 type WorkloadOutcome = Result<(), DynError>;
+/// Workload index, time it took to return, and its outcome.
+type WorkloadResult = (usize, Duration, WorkloadOutcome);
 
 const COOLDOWN_BLOCK_INTERVAL_MULTIPLIER: f64 = 5.0;
 const MIN_NODE_CONTROL_COOLDOWN: Duration = Duration::from_secs(30);
 const DEFAULT_BLOCK_FEED_SETTLE_WAIT: Duration = Duration::from_secs(1);
 const MIN_BLOCK_FEED_SETTLE_WAIT: Duration = Duration::from_secs(2);
+const MIN_MONITOR_INTERVAL: Duration = Duration::from_millis(100);
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
 
 /// Represents a fully prepared environment capable of executing a scenario.
 pub struct Runner {
     context: Arc<RunContext>,
     cleanup_guard: Option<Box<dyn CleanupGuard>>,
+    deployer: Option<&'static str>,
 }
 
 impl Runner {
@@ -32,9 +49,17 @@ impl Runner {
         Self {
             context: Arc::new(context),
             cleanup_guard,
+            deployer: None,
         }
     }
 
+    /// Tag the runner with the deployer that produced it, for reporting.
+    #[must_use]
+    pub const fn with_deployer(mut self, deployer: &'static str) -> Self {
+        self.deployer = Some(deployer);
+        self
+    }
+
     /// Access the underlying run context.
     #[must_use]
     pub fn context(&self) -> Arc<RunContext> {
@@ -47,51 +72,81 @@ impl Runner {
         }
     }
 
-    pub(crate) fn into_run_handle(mut self) -> RunHandle {
-        RunHandle::from_shared(Arc::clone(&self.context), self.cleanup_guard.take())
+    pub(crate) fn into_run_handle(mut self, report: ScenarioReport) -> RunHandle {
+        RunHandle::from_shared(
+            Arc::clone(&self.context),
+            self.cleanup_guard.take(),
+            Some(report),
+        )
     }
 
     /// Executes the scenario by driving workloads first and then evaluating all
     /// expectations. On any failure it cleans up resources and propagates the
     /// error to the caller.
-    pub async fn run<Caps>(
+    pub async fn run<Caps>(self, scenario: &mut Scenario<Caps>) -> Result<RunHandle, ScenarioError>
+    where
+        Caps: Send + Sync,
+    {
+        self.run_with_report(scenario).await.1
+    }
+
+    /// Like [`Runner::run`], but also returns the [`ScenarioReport`] when the
+    /// run fails so callers can publish per-expectation results.
+    pub async fn run_with_report<Caps>(
         mut self,
         scenario: &mut Scenario<Caps>,
-    ) -> Result<RunHandle, ScenarioError>
+    ) -> (ScenarioReport, Result<RunHandle, ScenarioError>)
     where
         Caps: Send + Sync,
     {
         let context = self.context();
-        if let Err(error) =
-            Self::prepare_expectations(scenario.expectations_mut(), context.as_ref()).await
-        {
-            self.cleanup();
-            return Err(error);
-        }
-
-        if let Err(error) = Self::run_workloads(&context, scenario).await {
-            self.cleanup();
-            return Err(error);
-        }
-
-        Self::settle_before_expectations(&context).await;
-
-        if let Err(error) =
-            Self::run_expectations(scenario.expectations_mut(), context.as_ref()).await
-        {
-            self.cleanup();
-            return Err(error);
+        let mut report = RunReporter::new(self.deployer, scenario);
+
+        let outcome = Self::run_phases(&context, scenario, &mut report).await;
+        let report = report.finish(&context, outcome.as_ref().err());
+
+        match outcome {
+            Ok(()) => (report.clone(), Ok(self.into_run_handle(report))),
+            Err(error) => {
+                tracing::error!(
+                    seed = report.seed,
+                    "scenario failed; set LOGOS_BLOCKCHAIN_TESTS_SEED={} to replay it",
+                    report.seed
+                );
+                self.cleanup();
+                (report, Err(error))
+            }
         }
+    }
 
-        Ok(self.into_run_handle())
+    async fn run_phases<Caps>(
+        context: &Arc<RunContext>,
+        scenario: &mut Scenario<Caps>,
+        report: &mut RunReporter,
+    ) -> Result<(), ScenarioError>
+    where
+        Caps: Send + Sync,
+    {
+        let _sampling = ResourceSampling::spawn(context.as_ref());
+        let mut supervision = Supervision::new(context.as_ref());
+        Self::prepare_expectations(scenario.expectations_mut(), context.as_ref(), report).await?;
+        Self::run_workloads(context, scenario, &mut supervision, report).await?;
+        Self::settle_before_expectations(context).await;
+        // Catch exits that happened during the cooldown or settle window.
+        supervision.poll(report).await?;
+        Self::run_expectations(scenario.expectations_mut(), context.as_ref(), report).await
     }
 
     async fn prepare_expectations(
         expectations: &mut [Box<dyn Expectation>],
         context: &RunContext,
+        report: &mut RunReporter,
     ) -> Result<(), ScenarioError> {
-        for expectation in expectations {
+        for (index, expectation) in expectations.iter_mut().enumerate() {
             if let Err(source) = expectation.start_capture(context).await {
+                report.expectations[index].status = ExpectationStatus::Failed {
+                    error: format!("capture failed: {source}"),
+                };
                 return Err(ScenarioError::ExpectationCapture(source));
             }
         }
@@ -100,30 +155,31 @@ impl Runner {
 
     /// Spawns every workload, waits until the configured duration elapses (or a
     /// workload fails), and then aborts the remaining tasks.
+    /// Expectation monitors and the node supervisor are polled alongside the
+    /// workloads; a failing monitor or an unexpected node exit aborts the run
+    /// early.
     async fn run_workloads<Caps>(
         context: &Arc<RunContext>,
-        scenario: &Scenario<Caps>,
+        scenario: &mut Scenario<Caps>,
+        supervision: &mut Supervision,
+        report: &mut RunReporter,
     ) -> Result<(), ScenarioError>
     where
         Caps: Send + Sync,
     {
-        if scenario.workloads().is_empty() {
-            let duration = scenario.duration();
-            if !duration.is_zero() {
-                sleep(duration).await;
-            }
-
-            if let Some(cooldown) = Self::cooldown_duration(context.as_ref()) {
-                if !cooldown.is_zero() {
-                    sleep(cooldown).await;
-                }
-            }
-
-            return Ok(());
-        }
-
+        let duration = scenario.duration();
         let mut workloads = Self::spawn_workloads(scenario, context);
-        let _ = Self::drive_until_timer(&mut workloads, scenario.duration()).await?;
+        let mut monitors = Monitors::new(scenario.expectations_mut());
+
+        let _ = Self::drive_until_timer(
+            &mut workloads,
+            &mut monitors,
+            supervision,
+            context.as_ref(),
+            duration,
+            report,
+        )
+        .await?;
 
         // Keep workloads running during the cooldown window so that late
         // inclusions (especially DA parent-linked ops) still have a chance to
@@ -131,15 +187,19 @@ impl Runner {
         // end of cooldown to prevent leaking tasks across runs.
         if let Some(cooldown) = Self::cooldown_duration(context.as_ref()) {
             if !cooldown.is_zero() {
-                if workloads.is_empty() {
-                    sleep(cooldown).await;
-                } else {
-                    let _ = Self::drive_until_timer(&mut workloads, cooldown).await?;
-                }
+                let _ = Self::drive_until_timer(
+                    &mut workloads,
+                    &mut monitors,
+                    supervision,
+                    context.as_ref(),
+                    cooldown,
+                    report,
+                )
+                .await?;
             }
         }
 
-        Self::drain_workloads(&mut workloads).await
+        Self::drain_workloads(&mut workloads, report).await
     }
 
     async fn settle_before_expectations(context: &Arc<RunContext>) {
@@ -163,10 +223,24 @@ impl Runner {
     async fn run_expectations(
         expectations: &mut [Box<dyn Expectation>],
         context: &RunContext,
+        report: &mut RunReporter,
     ) -> Result<(), ScenarioError> {
         let mut failures: Vec<(String, DynError)> = Vec::new();
-        for expectation in expectations {
-            if let Err(source) = expectation.evaluate(context).await {
+        for (index, expectation) in expectations.iter_mut().enumerate() {
+            let started = Instant::now();
+            let outcome = expectation.evaluate(context).await;
+
+            let entry = &mut report.expectations[index];
+            entry.elapsed = Some(started.elapsed());
+            entry.details = expectation.report_details();
+            entry.status = match &outcome {
+                Ok(()) => ExpectationStatus::Passed,
+                Err(source) => ExpectationStatus::Failed {
+                    error: source.to_string(),
+                },
+            };
+
+            if let Err(source) = outcome {
                 failures.push((expectation.name().to_owned(), source));
             }
         }
@@ -221,37 +295,68 @@ impl Runner {
     fn spawn_workloads<Caps>(
         scenario: &Scenario<Caps>,
         context: &Arc<RunContext>,
-    ) -> JoinSet<WorkloadOutcome>
+    ) -> JoinSet<WorkloadResult>
     where
         Caps: Send + Sync,
     {
         let mut workloads = JoinSet::new();
-        for workload in scenario.workloads() {
+        for (index, workload) in scenario.workloads().iter().enumerate() {
             let workload = Arc::clone(workload);
             let ctx = Arc::clone(context);
 
             workloads.spawn(async move {
+                let started = Instant::now();
                 let outcome = AssertUnwindSafe(async { workload.start(ctx.as_ref()).await })
                     .catch_unwind()
                     .await;
 
-                outcome.unwrap_or_else(|panic| {
+                let outcome = outcome.unwrap_or_else(|panic| {
                     Err(format!("workload panicked: {}", panic_message(panic)).into())
-                })
+                });
+                (index, started.elapsed(), outcome)
             });
         }
 
         workloads
     }
 
-    /// Polls workload tasks until the timeout fires or one reports an error.
+    /// Polls workload tasks, expectation monitors and the node supervisor until
+    /// the timeout fires or one of them reports an error. Returns early once
+    /// every workload has finished, unless monitors or the supervisor still
+    /// need to run.
     async fn drive_until_timer(
-        workloads: &mut JoinSet<WorkloadOutcome>,
+        workloads: &mut JoinSet<WorkloadResult>,
+        monitors: &mut Monitors<'_>,
+        supervision: &mut Supervision,
+        context: &RunContext,
         duration: Duration,
+        report: &mut RunReporter,
     ) -> Result<bool, ScenarioError> {
+        if workloads.is_empty() && !monitors.is_active() && !supervision.is_active() {
+            sleep(duration).await;
+            return Ok(true);
+        }
+
         let run_future = async {
-            while let Some(result) = workloads.join_next().await {
-                Self::map_join_result(result)?;
+            loop {
+                let next_monitor = monitors.next_due();
+                let next_supervision = supervision.next_due();
+                tokio::select! {
+                    Some(result) = workloads.join_next(), if !workloads.is_empty() => {
+                        Self::map_join_result(result, report)?;
+                    }
+                    () = sleep_until(next_monitor.unwrap_or_else(Instant::now).into()),
+                        if next_monitor.is_some() =>
+                    {
+                        monitors.poll_due(context, report).await?;
+                    }
+                    () = sleep_until(next_supervision.unwrap_or_else(Instant::now).into()),
+                        if next_supervision.is_some() =>
+                    {
+                        supervision.poll(report).await?;
+                    }
+                    else => break,
+                }
             }
             Ok(())
         };
@@ -267,12 +372,13 @@ impl Runner {
     /// Aborts and drains any remaining workload tasks so we do not leak work
     /// across scenario runs.
     async fn drain_workloads(
-        workloads: &mut JoinSet<WorkloadOutcome>,
+        workloads: &mut JoinSet<WorkloadResult>,
+        report: &mut RunReporter,
     ) -> Result<(), ScenarioError> {
         workloads.abort_all();
 
         while let Some(result) = workloads.join_next().await {
-            Self::map_join_result(result)?;
+            Self::map_join_result(result, report)?;
         }
 
         Ok(())
@@ -281,10 +387,21 @@ impl Runner {
     /// Converts the outcome of a workload task into the canonical scenario
     /// error, tolerating cancellation when the runner aborts unfinished tasks.
     fn map_join_result(
-        result: Result<WorkloadOutcome, tokio::task::JoinError>,
+        result: Result<WorkloadResult, tokio::task::JoinError>,
+        report: &mut RunReporter,
     ) -> Result<(), ScenarioError> {
         match result {
-            Ok(outcome) => outcome.map_err(ScenarioError::Workload),
+            Ok((index, elapsed, outcome)) => {
+                let entry = &mut report.workloads[index];
+                entry.elapsed = Some(elapsed);
+                entry.status = match &outcome {
+                    Ok(()) => WorkloadStatus::Completed,
+                    Err(source) => WorkloadStatus::Failed {
+                        error: source.to_string(),
+                    },
+                };
+                outcome.map_err(ScenarioError::Workload)
+            }
             Err(join_err) if join_err.is_cancelled() => Ok(()),
             Err(join_err) => Err(ScenarioError::Workload(
                 format!("workload task failed: {join_err}").into(),
@@ -293,6 +410,244 @@ impl Runner {
     }
 }
 
+/// Expectations that asked to be monitored during the run, with the time each
+/// monitor is next due.
+struct Monitors<'a> {
+    expectations: &'a mut [Box<dyn Expectation>],
+    schedule: Vec<Option<(Duration, Instant)>>,
+}
+
+impl<'a> Monitors<'a> {
+    fn new(expectations: &'a mut [Box<dyn Expectation>]) -> Self {
+        let now = Instant::now();
+        let schedule = expectations
+            .iter()
+            .map(|expectation| {
+                expectation.monitor_interval().map(|interval| {
+                    let interval = interval.max(MIN_MONITOR_INTERVAL);
+                    (interval, now + interval)
+                })
+            })
+            .collect();
+
+        Self {
+            expectations,
+            schedule,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.schedule.iter().any(Option::is_some)
+    }
+
+    fn next_due(&self) -> Option<Instant> {
+        self.schedule.iter().flatten().map(|(_, due)| *due).min()
+    }
+
+    /// Run every monitor whose interval has elapsed.
+    async fn poll_due(
+        &mut self,
+        context: &RunContext,
+        report: &mut RunReporter,
+    ) -> Result<(), ScenarioError> {
+        let now = Instant::now();
+        let monitors = self.expectations.iter_mut().zip(&mut self.schedule);
+        for (index, (expectation, slot)) in monitors.enumerate() {
+            let Some((interval, due)) = slot else {
+                continue;
+            };
+            if *due > now {
+                continue;
+            }
+
+            if let Err(source) = expectation.monitor(context).await {
+                report.expectations[index].status = ExpectationStatus::Failed {
+                    error: format!("monitor failed: {source}"),
+                };
+                return Err(ScenarioError::Monitor {
+                    expectation: expectation.name().to_owned(),
+                    source,
+                });
+            }
+            *due = Instant::now() + *interval;
+        }
+        Ok(())
+    }
+}
+
+/// Polls the deployer's node supervisor, if it has one, for nodes that exited
+/// without being asked to.
+struct Supervision {
+    supervisor: Option<Arc<dyn NodeSupervisor>>,
+    due: Instant,
+}
+
+impl Supervision {
+    fn new(context: &RunContext) -> Self {
+        Self {
+            supervisor: context.node_supervisor(),
+            due: Instant::now() + SUPERVISOR_POLL_INTERVAL,
+        }
+    }
+
+    const fn is_active(&self) -> bool {
+        self.supervisor.is_some()
+    }
+
+    fn next_due(&self) -> Option<Instant> {
+        self.supervisor.as_ref().map(|_| self.due)
+    }
+
+    /// Fails the run if any node exited unexpectedly since the last poll. A
+    /// supervisor that cannot be queried only logs, so a flaky backend does
+    /// not fail an otherwise healthy run.
+    async fn poll(&mut self, report: &mut RunReporter) -> Result<(), ScenarioError> {
+        let Some(supervisor) = &self.supervisor else {
+            return Ok(());
+        };
+        let exits = supervisor.poll_exits().await;
+        self.due = Instant::now() + SUPERVISOR_POLL_INTERVAL;
+
+        let exits = match exits {
+            Ok(exits) => exits,
+            Err(err) => {
+                tracing::warn!(error = %err, "node supervisor poll failed");
+                return Ok(());
+            }
+        };
+        if exits.is_empty() {
+            return Ok(());
+        }
+
+        for exit in &exits {
+            tracing::error!(
+                node = %exit.label,
+                status = %exit.status,
+                log_tail = %exit.log_tail,
+                "node exited unexpectedly"
+            );
+        }
+        let message = exits
+            .iter()
+            .map(|exit| format!("- {}: {}", exit.label, exit.status))
+            .collect::<Vec<_>>()
+            .join("\n");
+        report.node_exits.extend(exits);
+        Err(ScenarioError::NodeExit(message))
+    }
+}
+
+/// Records every node's resource usage in the background for as long as the
+/// run lasts; sampling stops when this is dropped.
+struct ResourceSampling {
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ResourceSampling {
+    fn spawn(context: &RunContext) -> Self {
+        let handle = context.resource_usage().cloned().map(|series| {
+            tokio::spawn(async move {
+                let started = Instant::now();
+                loop {
+                    if let Err(err) = series.sample(started.elapsed()).await {
+                        tracing::debug!(error = %err, "resource sampling failed");
+                    }
+                    sleep(RESOURCE_SAMPLE_INTERVAL).await;
+                }
+            })
+        });
+        Self { handle }
+    }
+}
+
+impl Drop for ResourceSampling {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// Collects per-workload and per-expectation results while the runner
+/// executes a scenario.
+struct RunReporter {
+    deployer: Option<&'static str>,
+    nodes: usize,
+    seed: u64,
+    run_duration: Duration,
+    started_at: SystemTime,
+    started: Instant,
+    workloads: Vec<WorkloadReport>,
+    expectations: Vec<ExpectationReport>,
+    node_exits: Vec<NodeExit>,
+}
+
+impl RunReporter {
+    fn new<Caps>(deployer: Option<&'static str>, scenario: &Scenario<Caps>) -> Self {
+        let workloads = scenario
+            .workloads()
+            .iter()
+            .map(|workload| WorkloadReport {
+                name: workload.name().to_owned(),
+                status: WorkloadStatus::Stopped,
+                elapsed: None,
+            })
+            .collect();
+        let expectations = scenario
+            .expectations()
+            .iter()
+            .map(|expectation| ExpectationReport {
+                name: expectation.name().to_owned(),
+                status: ExpectationStatus::Skipped,
+                elapsed: None,
+                details: None,
+            })
+            .collect();
+
+        Self {
+            deployer,
+            nodes: scenario.topology().nodes().len(),
+            seed: scenario.seed().value(),
+            run_duration: scenario.duration(),
+            started_at: SystemTime::now(),
+            started: Instant::now(),
+            workloads,
+            expectations,
+            node_exits: Vec::new(),
+        }
+    }
+
+    fn finish(self, context: &RunContext, error: Option<&ScenarioError>) -> ScenarioReport {
+        let block_feed = context.block_feed();
+        let stats = block_feed.stats();
+        let chain = block_feed.chain();
+        let started_at_unix_ms = self
+            .started_at
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+
+        ScenarioReport {
+            deployer: self.deployer.map(str::to_owned),
+            nodes: self.nodes,
+            seed: self.seed,
+            started_at_unix_ms,
+            run_duration: self.run_duration,
+            elapsed: self.started.elapsed(),
+            workloads: self.workloads,
+            expectations: self.expectations,
+            blocks: BlockReport {
+                blocks: stats.total_blocks(),
+                transactions: stats.total_transactions(),
+                forks: chain.forks().len(),
+                reorgs: chain.reorgs().len(),
+                max_reorg_depth: chain.max_reorg_depth(),
+            },
+            node_exits: self.node_exits,
+            error: error.map(ToString::to_string),
+        }
+    }
+}
+
 /// Attempts to turn a panic payload into a readable string for diagnostics.
 fn panic_message(panic: Box<dyn Any + Send>) -> String {
     panic.downcast::<String>().map_or_else(
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use rand::{RngCore as _, SeedableRng as _, rngs::StdRng, thread_rng};
+use testing_framework_env as tf_env;
+
+/// Seed every random choice of a scenario is derived from: node ids and keys,
+/// node picks, submission order and workload decisions. Re-running a scenario
+/// with the same seed replays those choices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScenarioSeed(u64);
+
+impl ScenarioSeed {
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    #[must_use]
+    /// Seed from `LOGOS_BLOCKCHAIN_TESTS_SEED` when set, a fresh random seed
+    /// otherwise.
+    pub fn from_env_or_random() -> Self {
+        tf_env::nomos_tests_seed().map_or_else(Self::random, Self::new)
+    }
+
+    #[must_use]
+    pub fn random() -> Self {
+        Self(thread_rng().next_u64())
+    }
+
+    #[must_use]
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    #[must_use]
+    /// Generator for one consumer of randomness. Each `stream` gets its own
+    /// sequence, so consumers running concurrently do not change each other's
+    /// choices.
+    pub fn rng(self, stream: &str) -> StdRng {
+        StdRng::seed_from_u64(splitmix64(self.0) ^ fnv1a(stream.as_bytes()))
+    }
+}
+
+impl fmt::Display for ScenarioSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Stable across Rust releases, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
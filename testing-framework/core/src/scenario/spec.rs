@@ -0,0 +1,237 @@
+use std::{collections::HashMap, fs, io, path::Path, time::Duration};
+
+use serde::{Deserialize, de::DeserializeOwned};
+use thiserror::Error;
+
+use super::{Builder, DynError, NodeControlCapability, ScenarioBuilder};
+use crate::topology::{
+    config::{TopologyBuilder, TopologyConfig},
+    configs::network::Libp2pNetworkLayout,
+};
+
+/// Builder type spec scenarios are assembled on. Node control is always
+/// enabled so chaos workloads can be listed and every deployer accepts the
+/// scenario.
+pub type SpecBuilder = Builder<NodeControlCapability>;
+
+type Applier =
+    Box<dyn Fn(SpecBuilder, &ComponentSpec) -> Result<SpecBuilder, DynError> + Send + Sync>;
+
+#[derive(Debug, Error)]
+pub enum SpecError {
+    #[error("failed to read scenario file {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("unsupported scenario file extension for {path} (expected .toml, .yaml or .yml)")]
+    UnknownFormat { path: String },
+    #[error("invalid TOML scenario: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("invalid YAML scenario: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("unknown workload kind '{kind}' (known: {known})")]
+    UnknownWorkload { kind: String, known: String },
+    #[error("unknown expectation kind '{kind}' (known: {known})")]
+    UnknownExpectation { kind: String, known: String },
+    #[error("invalid {section} '{kind}': {source}")]
+    Component {
+        section: &'static str,
+        kind: String,
+        #[source]
+        source: DynError,
+    },
+}
+
+/// Declarative scenario description, loaded from a TOML or YAML file and
+/// turned into a [`Builder`] by a [`SpecRegistry`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioSpec {
+    pub topology: TopologySpec,
+    /// Number of funded wallets to seed.
+    #[serde(default)]
+    pub wallets: Option<usize>,
+    pub duration_secs: u64,
+    /// Seed for the scenario's random choices; see [`Builder::with_seed`].
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Deployer to run the scenario with; runners may override it.
+    #[serde(default)]
+    pub deployer: DeployerSpec,
+    #[serde(default)]
+    pub workloads: Vec<ComponentSpec>,
+    #[serde(default)]
+    pub expectations: Vec<ComponentSpec>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopologySpec {
+    pub nodes: usize,
+    #[serde(default)]
+    pub network: NetworkLayoutSpec,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkLayoutSpec {
+    #[default]
+    Star,
+    Chain,
+    Full,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeployerSpec {
+    #[default]
+    Local,
+    Compose,
+    K8s,
+}
+
+/// A workload or expectation entry: the `kind` it was registered under plus
+/// its kind-specific parameters.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ComponentSpec {
+    pub kind: String,
+    #[serde(flatten)]
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ComponentSpec {
+    /// Deserialize the entry's parameters into `T`.
+    pub fn params<T: DeserializeOwned>(&self) -> Result<T, DynError> {
+        Ok(serde_json::from_value(serde_json::Value::Object(
+            self.params.clone(),
+        ))?)
+    }
+}
+
+impl ScenarioSpec {
+    /// Load a spec from a `.toml`, `.yaml` or `.yml` file.
+    pub fn from_path(path: &Path) -> Result<Self, SpecError> {
+        let display = path.display().to_string();
+        let contents = fs::read_to_string(path).map_err(|source| SpecError::Read {
+            path: display.clone(),
+            source,
+        })?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str(&contents),
+            Some("yaml" | "yml") => Self::from_yaml_str(&contents),
+            _ => Err(SpecError::UnknownFormat { path: display }),
+        }
+    }
+
+    pub fn from_toml_str(contents: &str) -> Result<Self, SpecError> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    pub fn from_yaml_str(contents: &str) -> Result<Self, SpecError> {
+        Ok(serde_yaml::from_str(contents)?)
+    }
+
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_secs)
+    }
+}
+
+/// Maps workload and expectation kinds to functions that add them to a
+/// builder. Crates providing workloads register theirs; runners then build
+/// scenarios from specs without knowing the concrete types.
+#[derive(Default)]
+pub struct SpecRegistry {
+    workloads: HashMap<String, Applier>,
+    expectations: HashMap<String, Applier>,
+}
+
+impl SpecRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the workload `kind`, replacing any previous registration.
+    pub fn register_workload(
+        &mut self,
+        kind: impl Into<String>,
+        apply: impl Fn(SpecBuilder, &ComponentSpec) -> Result<SpecBuilder, DynError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self {
+        self.workloads.insert(kind.into(), Box::new(apply));
+        self
+    }
+
+    /// Register the expectation `kind`, replacing any previous registration.
+    pub fn register_expectation(
+        &mut self,
+        kind: impl Into<String>,
+        apply: impl Fn(SpecBuilder, &ComponentSpec) -> Result<SpecBuilder, DynError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self {
+        self.expectations.insert(kind.into(), Box::new(apply));
+        self
+    }
+
+    /// Assemble a builder from `spec`. Workloads are added before
+    /// expectations, each in file order.
+    pub fn builder(&self, spec: &ScenarioSpec) -> Result<SpecBuilder, SpecError> {
+        let mut config = TopologyConfig::with_node_numbers(spec.topology.nodes);
+        config.network_params.libp2p_network_layout = match spec.topology.network {
+            NetworkLayoutSpec::Star => Libp2pNetworkLayout::Star,
+            NetworkLayoutSpec::Chain => Libp2pNetworkLayout::Chain,
+            NetworkLayoutSpec::Full => Libp2pNetworkLayout::Full,
+        };
+        let mut builder = ScenarioBuilder::new(TopologyBuilder::new(config))
+            .with_run_duration(spec.duration())
+            .enable_node_control();
+        if let Some(wallets) = spec.wallets {
+            builder = builder.wallets(wallets);
+        }
+        if let Some(seed) = spec.seed {
+            builder = builder.with_seed(seed);
+        }
+
+        for component in &spec.workloads {
+            let apply =
+                self.workloads
+                    .get(&component.kind)
+                    .ok_or_else(|| SpecError::UnknownWorkload {
+                        kind: component.kind.clone(),
+                        known: known_kinds(&self.workloads),
+                    })?;
+            builder = apply(builder, component).map_err(|source| SpecError::Component {
+                section: "workload",
+                kind: component.kind.clone(),
+                source,
+            })?;
+        }
+        for component in &spec.expectations {
+            let apply = self.expectations.get(&component.kind).ok_or_else(|| {
+                SpecError::UnknownExpectation {
+                    kind: component.kind.clone(),
+                    known: known_kinds(&self.expectations),
+                }
+            })?;
+            builder = apply(builder, component).map_err(|source| SpecError::Component {
+                section: "expectation",
+                kind: component.kind.clone(),
+                source,
+            })?;
+        }
+        Ok(builder)
+    }
+}
+
+fn known_kinds(appliers: &HashMap<String, Applier>) -> String {
+    let mut kinds = appliers.keys().map(String::as_str).collect::<Vec<_>>();
+    kinds.sort_unstable();
+    kinds.join(", ")
+}
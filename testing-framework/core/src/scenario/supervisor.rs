@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::DynError;
+
+/// A node that exited without being asked to through
+/// [`NodeControlHandle`](super::NodeControlHandle).
+#[derive(Clone, Debug, Serialize)]
+pub struct NodeExit {
+    /// Node label as used by the deployer (e.g. `node-0`).
+    pub label: String,
+    /// Exit status as reported by the backend (exit code, signal, reason).
+    pub status: String,
+    /// Last lines the node logged before exiting.
+    pub log_tail: String,
+}
+
+/// Interface exposed by runners that can watch their node processes for
+/// unexpected exits.
+#[async_trait]
+pub trait NodeSupervisor: Send + Sync {
+    /// Unexpected exits observed since the previous call; each exit is
+    /// reported once.
+    async fn poll_exits(&self) -> Result<Vec<NodeExit>, DynError>;
+}
@@ -18,10 +18,13 @@ use testing_framework_config::topology::{
 };
 use thiserror::Error;
 
-use crate::topology::{
-    configs::{GeneralConfig, time::default_time_config},
-    generation::{GeneratedNodeConfig, GeneratedTopology},
-    utils::{TopologyResolveError, create_kms_configs, resolve_ids, resolve_ports},
+use crate::{
+    scenario::ScenarioSeed,
+    topology::{
+        configs::{GeneralConfig, time::default_time_config},
+        generation::{GeneratedNodeConfig, GeneratedTopology},
+        utils::{TopologyResolveError, create_kms_configs, resolve_ids, resolve_ports},
+    },
 };
 
 #[derive(Debug, Error)]
@@ -105,6 +108,7 @@ pub struct TopologyBuilder {
     config: TopologyConfig,
     ids: Option<Vec<[u8; 32]>>,
     blend_ports: Option<Vec<u16>>,
+    seed: Option<ScenarioSeed>,
 }
 
 impl TopologyBuilder {
@@ -115,6 +119,7 @@ impl TopologyBuilder {
             config,
             ids: None,
             blend_ports: None,
+            seed: None,
         }
     }
 
@@ -125,6 +130,14 @@ impl TopologyBuilder {
         self
     }
 
+    #[must_use]
+    /// Seed used to generate node IDs when none are provided; random when
+    /// unset.
+    pub const fn with_seed(mut self, seed: ScenarioSeed) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     #[must_use]
     /// Override blend ports for nodes in order.
     pub fn with_blend_ports(mut self, ports: Vec<u16>) -> Self {
@@ -158,11 +171,14 @@ impl TopologyBuilder {
             config,
             ids,
             blend_ports,
+            seed,
         } = self;
 
         let n_participants = participant_count(&config)?;
+        let seed = seed.unwrap_or_else(ScenarioSeed::random);
 
-        let (ids, blend_ports) = resolve_and_validate_vectors(ids, blend_ports, n_participants)?;
+        let (ids, blend_ports) =
+            resolve_and_validate_vectors(ids, blend_ports, n_participants, seed)?;
 
         let BaseConfigs {
             mut consensus_configs,
@@ -206,7 +222,11 @@ impl TopologyBuilder {
             &time_config,
         )?;
 
-        Ok(GeneratedTopology { config, nodes })
+        Ok(GeneratedTopology {
+            config,
+            nodes,
+            seed,
+        })
     }
 
     #[must_use]
@@ -228,8 +248,9 @@ fn resolve_and_validate_vectors(
     ids: Option<Vec<[u8; 32]>>,
     blend_ports: Option<Vec<u16>>,
     n_participants: usize,
+    seed: ScenarioSeed,
 ) -> Result<(Vec<[u8; 32]>, Vec<u16>), TopologyBuildError> {
-    let ids = resolve_ids(ids, n_participants)?;
+    let ids = resolve_ids(ids, n_participants, &mut seed.rng("node_ids"))?;
     let blend_ports = resolve_ports(blend_ports, n_participants, "Blend")?;
 
     Ok((ids, blend_ports))
@@ -84,6 +84,12 @@ impl Topology {
         &self.nodes
     }
 
+    #[must_use]
+    /// Take ownership of the spawned node processes.
+    pub fn into_nodes(self) -> DeployedNodes {
+        self.nodes
+    }
+
     pub async fn wait_network_ready(&self) -> Result<(), ReadinessError> {
         let listen_ports = self.node_listen_ports();
         if listen_ports.len() <= 1 {
@@ -1,12 +1,16 @@
 use std::{collections::HashSet, time::Duration};
 
+use nomos_libp2p::Multiaddr;
 use reqwest::{Client, Url};
 
-use crate::topology::{
-    config::TopologyConfig,
-    configs::{GeneralConfig, wallet::WalletAccount},
-    deployment::{SpawnTopologyError, Topology},
-    readiness::{HttpNetworkReadiness, ReadinessCheck, ReadinessError},
+use crate::{
+    scenario::ScenarioSeed,
+    topology::{
+        config::TopologyConfig,
+        configs::{GeneralConfig, wallet::WalletAccount},
+        deployment::{SpawnTopologyError, Topology},
+        readiness::{HttpNetworkReadiness, ReadinessCheck, ReadinessError},
+    },
 };
 
 /// Fully generated configuration for an individual node.
@@ -47,6 +51,7 @@ impl GeneratedNodeConfig {
 pub struct GeneratedTopology {
     pub(crate) config: TopologyConfig,
     pub(crate) nodes: Vec<GeneratedNodeConfig>,
+    pub(crate) seed: ScenarioSeed,
 }
 
 impl GeneratedTopology {
@@ -56,6 +61,13 @@ impl GeneratedTopology {
         &self.config
     }
 
+    #[must_use]
+    /// Seed the topology was generated from; runtime randomness derives from
+    /// it too.
+    pub const fn seed(&self) -> ScenarioSeed {
+        self.seed
+    }
+
     #[must_use]
     /// All node configs.
     pub fn nodes(&self) -> &[GeneratedNodeConfig] {
@@ -81,6 +93,22 @@ impl GeneratedTopology {
         &self.config.wallet_config.accounts
     }
 
+    #[must_use]
+    /// Copy of the topology with every node's initial peer addresses passed
+    /// through `rewrite(node_index, addr)`.
+    pub fn with_initial_peers(
+        &self,
+        mut rewrite: impl FnMut(usize, &Multiaddr) -> Multiaddr,
+    ) -> Self {
+        let mut topology = self.clone();
+        for node in &mut topology.nodes {
+            let index = node.index;
+            let peers = &mut node.general.network_config.backend.initial_peers;
+            *peers = peers.iter().map(|addr| rewrite(index, addr)).collect();
+        }
+        topology
+    }
+
     pub async fn spawn_local(&self) -> Result<Topology, SpawnTopologyError> {
         let configs = self
             .iter()
@@ -110,6 +138,13 @@ impl GeneratedTopology {
         wait_for_network_readiness(self, &client, &endpoints, &labels).await
     }
 
+    #[must_use]
+    /// Minimum peer count of each node implied by its initial peers, in
+    /// topology order.
+    pub fn expected_peer_counts(&self) -> Vec<usize> {
+        find_expected_peer_counts(&self.listen_ports(), &self.initial_peer_ports())
+    }
+
     fn listen_ports(&self) -> Vec<u16> {
         self.nodes
             .iter()
@@ -172,10 +207,7 @@ async fn wait_for_network_readiness(
         return Ok(());
     }
 
-    let listen_ports = topology.listen_ports();
-    let initial_peer_ports = topology.initial_peer_ports();
-    let expected_peer_counts =
-        crate::topology::generation::find_expected_peer_counts(&listen_ports, &initial_peer_ports);
+    let expected_peer_counts = topology.expected_peer_counts();
 
     let network_check = HttpNetworkReadiness {
         client,
@@ -3,7 +3,7 @@ use std::{collections::HashMap, iter};
 use groth16::fr_to_bytes;
 use key_management_system_service::{backend::preload::PreloadKMSBackendSettings, keys::Key};
 use nomos_utils::net::get_available_udp_port;
-use rand::{Rng, thread_rng};
+use rand::Rng;
 use thiserror::Error;
 
 use crate::topology::configs::{blend::GeneralBlendConfig, wallet::WalletAccount};
@@ -58,6 +58,7 @@ pub enum TopologyResolveError {
 pub fn resolve_ids(
     ids: Option<Vec<[u8; 32]>>,
     count: usize,
+    rng: &mut impl Rng,
 ) -> Result<Vec<[u8; 32]>, TopologyResolveError> {
     match ids {
         Some(ids) => {
@@ -72,7 +73,7 @@ pub fn resolve_ids(
         None => {
             let mut generated = vec![[0; 32]; count];
             for id in &mut generated {
-                thread_rng().fill(id);
+                rng.fill(id);
             }
             Ok(generated)
         }
@@ -9,6 +9,7 @@ use testing_framework_core::scenario::{
     BlockFeedTask, CleanupGuard, Deployer, ObservabilityCapabilityProvider, RequiresNodeControl,
     Runner, Scenario,
 };
+use tokio::task::JoinHandle;
 
 use crate::{errors::ComposeRunnerError, lifecycle::cleanup::RunnerCleanup};
 
@@ -56,19 +57,26 @@ where
 pub(super) struct ComposeCleanupGuard {
     environment: RunnerCleanup,
     block_feed: Option<BlockFeedTask>,
+    link_shaper: JoinHandle<()>,
 }
 
 impl ComposeCleanupGuard {
-    const fn new(environment: RunnerCleanup, block_feed: BlockFeedTask) -> Self {
+    const fn new(
+        environment: RunnerCleanup,
+        block_feed: BlockFeedTask,
+        link_shaper: JoinHandle<()>,
+    ) -> Self {
         Self {
             environment,
             block_feed: Some(block_feed),
+            link_shaper,
         }
     }
 }
 
 impl CleanupGuard for ComposeCleanupGuard {
     fn cleanup(mut self: Box<Self>) {
+        self.link_shaper.abort();
         if let Some(block_feed) = self.block_feed.take() {
             CleanupGuard::cleanup(Box::new(block_feed));
         }
@@ -79,8 +87,13 @@ impl CleanupGuard for ComposeCleanupGuard {
 pub(super) fn make_cleanup_guard(
     environment: RunnerCleanup,
     block_feed: BlockFeedTask,
+    link_shaper: JoinHandle<()>,
 ) -> Box<dyn CleanupGuard> {
-    Box::new(ComposeCleanupGuard::new(environment, block_feed))
+    Box::new(ComposeCleanupGuard::new(
+        environment,
+        block_feed,
+        link_shaper,
+    ))
 }
 
 #[cfg(test)]
@@ -15,7 +15,12 @@ use super::{
     setup::{DeploymentContext, DeploymentSetup},
 };
 use crate::{
-    docker::control::ComposeNodeControl,
+    docker::{
+        control::{ComposeNodeControl, spawn_link_shaper},
+        logs::ComposeNodeLogs,
+        resources::ComposeResourceSampler,
+        supervisor::{ComposeSupervisor, HeldNodes},
+    },
     errors::ComposeRunnerError,
     infrastructure::{
         environment::StackEnvironment,
@@ -79,7 +84,8 @@ impl DeploymentOrchestrator {
             .build_node_clients(&descriptors, &host_ports, &host, &mut environment)
             .await?;
         let telemetry = observability.telemetry_handle()?;
-        let node_control = self.maybe_node_control::<Caps>(&environment);
+        let held_nodes = HeldNodes::default();
+        let node_control = self.maybe_node_control::<Caps>(&environment, &held_nodes);
 
         log_observability_endpoints(&observability);
         log_profiling_urls(&host, &host_ports);
@@ -87,9 +93,30 @@ impl DeploymentOrchestrator {
         maybe_print_endpoints(&observability, &host, &host_ports);
 
         let (block_feed, block_feed_guard) = client_builder
-            .start_block_feed(&node_clients, &mut environment)
+            .start_block_feed(&node_clients, descriptors.seed(), &mut environment)
             .await?;
-        let cleanup_guard = make_cleanup_guard(environment.into_cleanup()?, block_feed_guard);
+        let node_logs = Arc::new(ComposeNodeLogs::new(
+            environment.compose_path().to_path_buf(),
+            environment.project_name().to_owned(),
+            node_count,
+        ));
+        let supervisor = Arc::new(ComposeSupervisor::new(
+            environment.compose_path().to_path_buf(),
+            environment.project_name().to_owned(),
+            held_nodes,
+        ));
+        let resources = Arc::new(ComposeResourceSampler::new(
+            environment.compose_path().to_path_buf(),
+            environment.project_name().to_owned(),
+        ));
+        let link_shaper = spawn_link_shaper(
+            environment.compose_path().to_path_buf(),
+            environment.project_name().to_owned(),
+            node_count,
+            scenario.link_shaping(),
+        );
+        let cleanup_guard =
+            make_cleanup_guard(environment.into_cleanup()?, block_feed_guard, link_shaper);
 
         let context = RunContext::new(
             descriptors,
@@ -99,7 +126,11 @@ impl DeploymentOrchestrator {
             telemetry,
             block_feed,
             node_control,
-        );
+        )
+        .with_link_shaping(scenario.link_shaping().clone())
+        .with_node_logs(node_logs)
+        .with_node_supervisor(supervisor)
+        .with_resource_sampler(resources);
 
         info!(
             nodes = node_count,
@@ -109,21 +140,23 @@ impl DeploymentOrchestrator {
             "compose deployment ready; handing control to scenario runner"
         );
 
-        Ok(Runner::new(context, Some(cleanup_guard)))
+        Ok(Runner::new(context, Some(cleanup_guard)).with_deployer("compose"))
     }
 
     fn maybe_node_control<Caps>(
         &self,
         environment: &StackEnvironment,
+        held_nodes: &HeldNodes,
     ) -> Option<Arc<dyn NodeControlHandle>>
     where
         Caps: RequiresNodeControl + Send + Sync,
     {
         Caps::REQUIRED.then(|| {
-            Arc::new(ComposeNodeControl {
-                compose_file: environment.compose_path().to_path_buf(),
-                project_name: environment.project_name().to_owned(),
-            }) as Arc<dyn NodeControlHandle>
+            Arc::new(ComposeNodeControl::new(
+                environment.compose_path().to_path_buf(),
+                environment.project_name().to_owned(),
+                Arc::clone(held_nodes),
+            )) as Arc<dyn NodeControlHandle>
         })
     }
 }
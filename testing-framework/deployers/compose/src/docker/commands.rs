@@ -98,6 +98,55 @@ pub async fn compose_down(
     .await
 }
 
+/// Run a docker command with a timeout and return its stdout.
+pub async fn docker_command_output(
+    mut command: Command,
+    timeout_duration: Duration,
+    description: &str,
+) -> Result<Vec<u8>, ComposeCommandError> {
+    debug!(description, ?command, "running docker command for output");
+    let output = timeout(timeout_duration, command.output())
+        .await
+        .map_err(|_| ComposeCommandError::Timeout {
+            command: description.to_owned(),
+            timeout: timeout_duration,
+        })?
+        .map_err(|source| ComposeCommandError::Spawn {
+            command: description.to_owned(),
+            source,
+        })?;
+
+    if !output.status.success() {
+        return Err(ComposeCommandError::Failed {
+            command: description.to_owned(),
+            status: output.status,
+        });
+    }
+    Ok(output.stdout)
+}
+
+/// IDs of every container in the compose project, running or not.
+pub async fn compose_container_ids(
+    compose_file: &Path,
+    project_name: &str,
+    timeout_duration: Duration,
+) -> Result<Vec<String>, ComposeCommandError> {
+    let mut cmd = Command::new("docker");
+    cmd.arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("-p")
+        .arg(project_name)
+        .arg("ps")
+        .arg("--all")
+        .arg("--quiet");
+    let ids = docker_command_output(cmd, timeout_duration, "docker compose ps").await?;
+    Ok(String::from_utf8_lossy(&ids)
+        .split_whitespace()
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
 /// Dump docker compose logs to stderr for debugging failures.
 pub async fn dump_compose_logs(compose_file: &Path, project: &str, root: &Path) {
     let mut cmd = Command::new("docker");
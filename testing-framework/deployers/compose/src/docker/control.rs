@@ -1,20 +1,61 @@
 use std::{
     path::{Path, PathBuf},
+    sync::Mutex,
     time::Duration,
 };
 
-use testing_framework_core::scenario::{DynError, NodeControlHandle};
-use tokio::process::Command;
-use tracing::info;
+use testing_framework_core::scenario::{
+    DynError, LinkConditions, LinkShaping, LinkShapingConfig, NodeControlHandle,
+};
+use tokio::{process::Command, task::JoinHandle};
+use tracing::{info, warn};
 
-use crate::{docker::commands::run_docker_command, errors::ComposeRunnerError};
+use crate::{
+    docker::{commands::run_docker_command, supervisor::HeldNodes},
+    errors::ComposeRunnerError,
+};
 
-const COMPOSE_RESTART_TIMEOUT: Duration = Duration::from_secs(120);
+const COMPOSE_SERVICE_TIMEOUT: Duration = Duration::from_secs(120);
+const COMPOSE_EXEC_TIMEOUT: Duration = Duration::from_secs(30);
+/// iptables chain holding the partition rules inside each node container.
+const PARTITION_CHAIN: &str = "TF-PARTITION";
+/// Interface carrying node-to-node traffic inside each container.
+const SHAPING_DEVICE: &str = "eth0";
+/// Offset for per-peer tc class ids, keeping them clear of the default class.
+const SHAPING_CLASS_OFFSET: usize = 0x10;
 
 pub async fn restart_compose_service(
     compose_file: &Path,
     project_name: &str,
     service: &str,
+) -> Result<(), ComposeRunnerError> {
+    run_compose_service_command(compose_file, project_name, service, "restart").await
+}
+
+/// Stop a compose service without removing its container, so volumes and
+/// state survive until it is started again.
+pub async fn stop_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<(), ComposeRunnerError> {
+    run_compose_service_command(compose_file, project_name, service, "stop").await
+}
+
+/// Start a compose service previously stopped with [`stop_compose_service`].
+pub async fn start_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<(), ComposeRunnerError> {
+    run_compose_service_command(compose_file, project_name, service, "start").await
+}
+
+async fn run_compose_service_command(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    action: &'static str,
 ) -> Result<(), ComposeRunnerError> {
     let mut command = Command::new("docker");
     command
@@ -23,35 +64,290 @@ pub async fn restart_compose_service(
         .arg(compose_file)
         .arg("-p")
         .arg(project_name)
-        .arg("restart")
+        .arg(action)
         .arg(service);
 
-    let description = "docker compose restart";
-    info!(service, project = project_name, compose_file = %compose_file.display(), "restarting compose service");
+    let description = format!("docker compose {action}");
+    info!(service, project = project_name, action, compose_file = %compose_file.display(), "running compose service command");
+    run_docker_command(
+        command,
+        testing_framework_core::adjust_timeout(COMPOSE_SERVICE_TIMEOUT),
+        &description,
+    )
+    .await
+    .map_err(ComposeRunnerError::Compose)
+}
+
+/// Run a shell script inside a running compose service container.
+pub async fn exec_in_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    script: &str,
+) -> Result<(), ComposeRunnerError> {
+    let mut command = Command::new("docker");
+    command
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("-p")
+        .arg(project_name)
+        .arg("exec")
+        .arg("-T")
+        .arg(service)
+        .arg("sh")
+        .arg("-c")
+        .arg(script);
+
     run_docker_command(
         command,
-        testing_framework_core::adjust_timeout(COMPOSE_RESTART_TIMEOUT),
-        description,
+        testing_framework_core::adjust_timeout(COMPOSE_EXEC_TIMEOUT),
+        "docker compose exec",
     )
     .await
     .map_err(ComposeRunnerError::Compose)
 }
 
-/// Compose-specific node control handle for restarting nodes.
+/// Script that drops all traffic between the container and `peers` (compose
+/// service names). Replaces any previous partition rules.
+fn partition_script(peers: &[String]) -> String {
+    let mut script = format!(
+        "iptables -N {PARTITION_CHAIN} 2>/dev/null || true; \
+         iptables -C INPUT -j {PARTITION_CHAIN} 2>/dev/null || iptables -I INPUT -j {PARTITION_CHAIN}; \
+         iptables -C OUTPUT -j {PARTITION_CHAIN} 2>/dev/null || iptables -I OUTPUT -j {PARTITION_CHAIN}; \
+         iptables -F {PARTITION_CHAIN}"
+    );
+    for peer in peers {
+        script.push_str(&format!(
+            " && iptables -A {PARTITION_CHAIN} -s {peer} -j DROP \
+             && iptables -A {PARTITION_CHAIN} -d {peer} -j DROP"
+        ));
+    }
+    script
+}
+
+fn heal_script() -> String {
+    format!("iptables -F {PARTITION_CHAIN} 2>/dev/null || true")
+}
+
+fn node_service(index: usize) -> String {
+    format!("node-{index}")
+}
+
+/// Script that replaces the container's egress qdisc with one netem leaf per
+/// shaped peer. An empty config only removes previous shaping.
+fn link_shaping_script(index: usize, node_count: usize, config: &LinkShapingConfig) -> String {
+    let mut script = format!("tc qdisc del dev {SHAPING_DEVICE} root 2>/dev/null || true");
+    let shaped = (0..node_count)
+        .filter(|peer| *peer != index)
+        .filter_map(|peer| {
+            config
+                .conditions(index, peer)
+                .map(|conditions| (peer, conditions))
+        })
+        .collect::<Vec<_>>();
+    if shaped.is_empty() {
+        return script;
+    }
+
+    script.push_str(&format!(
+        "; tc qdisc add dev {SHAPING_DEVICE} root handle 1: htb default 1 \
+         && tc class add dev {SHAPING_DEVICE} parent 1: classid 1:1 htb rate 10gbit"
+    ));
+    for (peer, conditions) in shaped {
+        let class = peer + SHAPING_CLASS_OFFSET;
+        let service = node_service(peer);
+        script.push_str(&format!(
+            " && tc class add dev {SHAPING_DEVICE} parent 1: classid 1:{class:x} htb rate 10gbit \
+             && tc qdisc add dev {SHAPING_DEVICE} parent 1:{class:x} handle {class:x}: netem {netem} \
+             && tc filter add dev {SHAPING_DEVICE} protocol ip parent 1: prio 1 u32 \
+                match ip dst $(getent hosts {service} | awk '{{print $1}}' | head -n1)/32 flowid 1:{class:x}",
+            netem = netem_args(conditions),
+        ));
+    }
+    script
+}
+
+fn netem_args(conditions: LinkConditions) -> String {
+    format!(
+        "delay {}us {}us loss {:.4}%",
+        conditions.delay.as_micros(),
+        conditions.jitter.as_micros(),
+        conditions.loss.clamp(0.0, 1.0) * 100.0
+    )
+}
+
+/// Apply the scenario's link shaping to every node with `tc netem`, and keep
+/// re-applying it whenever the config changes. Abort the returned task to
+/// stop following updates.
+pub(crate) fn spawn_link_shaper(
+    compose_file: PathBuf,
+    project_name: String,
+    node_count: usize,
+    shaping: &LinkShaping,
+) -> JoinHandle<()> {
+    let mut updates = shaping.subscribe();
+    tokio::spawn(async move {
+        let mut applied = false;
+        loop {
+            let config = updates.borrow_and_update().clone();
+            if applied || !config.is_empty() {
+                info!(project = %project_name, ?config, "applying compose link shaping");
+                for index in 0..node_count {
+                    let script = link_shaping_script(index, node_count, &config);
+                    if let Err(err) = exec_in_compose_service(
+                        &compose_file,
+                        &project_name,
+                        &node_service(index),
+                        &script,
+                    )
+                    .await
+                    {
+                        warn!(node = index, error = %err, "failed to apply link shaping");
+                    }
+                }
+                applied = !config.is_empty();
+            }
+
+            if updates.changed().await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Compose-specific node control handle for restarting, stopping and
+/// starting nodes.
 pub struct ComposeNodeControl {
-    pub(crate) compose_file: PathBuf,
-    pub(crate) project_name: String,
+    compose_file: PathBuf,
+    project_name: String,
+    partitioned: Mutex<Vec<usize>>,
+    held: HeldNodes,
+}
+
+impl ComposeNodeControl {
+    pub(crate) fn new(compose_file: PathBuf, project_name: String, held: HeldNodes) -> Self {
+        Self {
+            compose_file,
+            project_name,
+            partitioned: Mutex::new(Vec::new()),
+            held,
+        }
+    }
+
+    /// Mark a node as stopped on purpose (`true`) or back under supervision.
+    fn hold(&self, index: usize, held: bool) {
+        let mut nodes = self
+            .held
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if held {
+            nodes.insert(index);
+        } else {
+            nodes.remove(&index);
+        }
+    }
+
+    async fn exec(&self, index: usize, script: &str) -> Result<(), ComposeRunnerError> {
+        exec_in_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &node_service(index),
+            script,
+        )
+        .await
+    }
 }
 
 #[async_trait::async_trait]
 impl NodeControlHandle for ComposeNodeControl {
     async fn restart_node(&self, index: usize) -> Result<(), DynError> {
-        restart_compose_service(
+        self.hold(index, true);
+        let result = restart_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &format!("node-{index}"),
+        )
+        .await;
+        self.hold(index, false);
+        result.map_err(|err| format!("node restart failed: {err}").into())
+    }
+
+    async fn stop_node(&self, index: usize) -> Result<(), DynError> {
+        self.hold(index, true);
+        stop_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &format!("node-{index}"),
+        )
+        .await
+        .map_err(|err| format!("node stop failed: {err}").into())
+    }
+
+    async fn resume_node(&self, index: usize) -> Result<(), DynError> {
+        start_compose_service(
             &self.compose_file,
             &self.project_name,
             &format!("node-{index}"),
         )
         .await
-        .map_err(|err| format!("node restart failed: {err}").into())
+        .map_err(|err| format!("node resume failed: {err}"))?;
+        self.hold(index, false);
+        Ok(())
+    }
+
+    async fn partition(&self, groups: &[Vec<usize>]) -> Result<(), DynError> {
+        self.heal_partition().await?;
+
+        info!(groups = ?groups, project = %self.project_name, "partitioning compose nodes");
+        // Record targets up front so a partially applied partition still heals.
+        *self
+            .partitioned
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = groups.concat();
+
+        for (group_index, group) in groups.iter().enumerate() {
+            let peers = groups
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != group_index)
+                .flat_map(|(_, nodes)| nodes.iter().copied().map(node_service))
+                .collect::<Vec<_>>();
+            let script = partition_script(&peers);
+
+            for &index in group {
+                self.exec(index, &script)
+                    .await
+                    .map_err(|err| format!("node partition failed: {err}"))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn heal_partition(&self) -> Result<(), DynError> {
+        let partitioned = self
+            .partitioned
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        if partitioned.is_empty() {
+            return Ok(());
+        }
+
+        info!(nodes = ?partitioned, project = %self.project_name, "healing compose partition");
+        for index in partitioned {
+            self.exec(index, &heal_script())
+                .await
+                .map_err(|err| format!("partition heal failed: {err}"))?;
+            // Forget a node only once it is healed, so a failed heal can be
+            // retried.
+            self.partitioned
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .retain(|node| *node != index);
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,84 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use testing_framework_core::{
+    adjust_timeout,
+    scenario::{DynError, NodeLogSource, NodeLogs},
+};
+use tokio::process::Command;
+use tracing::debug;
+
+use crate::docker::commands::{ComposeCommandError, docker_command_output};
+
+const COMPOSE_LOGS_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Collects node logs with `docker compose logs`, one service per node.
+pub struct ComposeNodeLogs {
+    compose_file: PathBuf,
+    project_name: String,
+    node_count: usize,
+}
+
+impl ComposeNodeLogs {
+    pub const fn new(compose_file: PathBuf, project_name: String, node_count: usize) -> Self {
+        Self {
+            compose_file,
+            project_name,
+            node_count,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeLogSource for ComposeNodeLogs {
+    async fn collect_logs(&self) -> Result<Vec<NodeLogs>, DynError> {
+        let mut logs = Vec::with_capacity(self.node_count);
+        for index in 0..self.node_count {
+            let label = format!("node-{index}");
+            let contents =
+                compose_service_logs(&self.compose_file, &self.project_name, &label, None).await?;
+            logs.push(NodeLogs { label, contents });
+        }
+        Ok(logs)
+    }
+}
+
+/// Logs of a compose service without prefixes, optionally only the last
+/// `tail` lines.
+pub async fn compose_service_logs(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    tail: Option<usize>,
+) -> Result<String, ComposeCommandError> {
+    let mut command = Command::new("docker");
+    command
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("-p")
+        .arg(project_name)
+        .arg("logs")
+        .arg("--no-color")
+        .arg("--no-log-prefix");
+    if let Some(tail) = tail {
+        command.arg("--tail").arg(tail.to_string());
+    }
+    command.arg(service);
+
+    debug!(
+        service,
+        project = project_name,
+        ?tail,
+        "collecting compose service logs"
+    );
+    let stdout = docker_command_output(
+        command,
+        adjust_timeout(COMPOSE_LOGS_TIMEOUT),
+        &format!("docker compose logs {service}"),
+    )
+    .await?;
+    Ok(String::from_utf8_lossy(&stdout).into_owned())
+}
@@ -1,6 +1,9 @@
 pub mod commands;
 pub mod control;
+pub mod logs;
 pub mod platform;
+pub mod resources;
+pub mod supervisor;
 pub mod workspace;
 
 use std::{env, process::Stdio, time::Duration};
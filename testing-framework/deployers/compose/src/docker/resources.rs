@@ -0,0 +1,219 @@
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use testing_framework_core::{
+    adjust_timeout,
+    scenario::{DynError, NodeResourceUsage, ResourceSampler, ResourceUsage},
+};
+use tokio::process::Command;
+use tracing::debug;
+
+use crate::docker::commands::{compose_container_ids, docker_command_output};
+
+const DOCKER_STATS_TIMEOUT: Duration = Duration::from_secs(30);
+const DOCKER_EXEC_TIMEOUT: Duration = Duration::from_secs(10);
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+/// Counts the node's open files; the node runs as PID 1 in its container.
+const COUNT_FDS_SCRIPT: &str = "ls /proc/1/fd | wc -l";
+
+/// Samples node containers with `docker stats` (CPU, memory),
+/// `docker inspect --size` (writable layer size) and a file descriptor count
+/// taken inside each running container.
+pub struct ComposeResourceSampler {
+    compose_file: PathBuf,
+    project_name: String,
+}
+
+/// A node container as reported by `docker inspect`.
+#[derive(Debug, PartialEq, Eq)]
+struct NodeContainer {
+    id: String,
+    service: String,
+    running: bool,
+    size_rw: Option<u64>,
+}
+
+impl ComposeResourceSampler {
+    pub const fn new(compose_file: PathBuf, project_name: String) -> Self {
+        Self {
+            compose_file,
+            project_name,
+        }
+    }
+
+    async fn containers(ids: &[String]) -> Result<Vec<NodeContainer>, DynError> {
+        let mut inspect = Command::new("docker");
+        inspect
+            .arg("inspect")
+            .arg("--size")
+            .arg("--format")
+            .arg(format!(
+                "{{{{.Id}}}} {{{{index .Config.Labels \"{SERVICE_LABEL}\"}}}} {{{{.State.Status}}}} {{{{.SizeRw}}}}"
+            ))
+            .args(ids);
+        let output = docker_command_output(
+            inspect,
+            adjust_timeout(DOCKER_STATS_TIMEOUT),
+            "docker inspect",
+        )
+        .await?;
+        Ok(String::from_utf8_lossy(&output)
+            .lines()
+            .filter_map(parse_container)
+            .filter(|container| container.service.starts_with("node-"))
+            .collect())
+    }
+
+    /// CPU percent and memory bytes by container ID, as passed to
+    /// `docker stats`.
+    async fn stats(ids: &[&str]) -> Result<HashMap<String, ContainerStats>, DynError> {
+        let mut stats = Command::new("docker");
+        stats
+            .arg("stats")
+            .arg("--no-stream")
+            .arg("--format")
+            .arg("{{.Container}} {{.CPUPerc}} {{.MemUsage}}")
+            .args(ids);
+        let output =
+            docker_command_output(stats, adjust_timeout(DOCKER_STATS_TIMEOUT), "docker stats")
+                .await?;
+        Ok(String::from_utf8_lossy(&output)
+            .lines()
+            .filter_map(parse_stats)
+            .collect())
+    }
+
+    async fn open_fds(id: &str) -> Option<u64> {
+        let mut exec = Command::new("docker");
+        exec.arg("exec")
+            .arg(id)
+            .arg("sh")
+            .arg("-c")
+            .arg(COUNT_FDS_SCRIPT);
+        match docker_command_output(exec, adjust_timeout(DOCKER_EXEC_TIMEOUT), "docker exec").await
+        {
+            Ok(output) => String::from_utf8_lossy(&output).trim().parse().ok(),
+            Err(err) => {
+                debug!(container = id, error = %err, "failed to count open fds");
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ContainerStats {
+    cpu_percent: Option<f64>,
+    memory_bytes: Option<u64>,
+}
+
+#[async_trait]
+impl ResourceSampler for ComposeResourceSampler {
+    async fn sample(&self) -> Result<Vec<NodeResourceUsage>, DynError> {
+        let timeout = adjust_timeout(DOCKER_STATS_TIMEOUT);
+        let ids = compose_container_ids(&self.compose_file, &self.project_name, timeout).await?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let containers = Self::containers(&ids).await?;
+        let running = containers
+            .iter()
+            .filter(|container| container.running)
+            .map(|container| container.id.as_str())
+            .collect::<Vec<_>>();
+        let stats = if running.is_empty() {
+            HashMap::new()
+        } else {
+            Self::stats(&running).await?
+        };
+
+        let mut usage = Vec::with_capacity(containers.len());
+        for container in containers {
+            let stats = stats
+                .iter()
+                .find(|(id, _)| container.id.starts_with(id.as_str()))
+                .map(|(_, stats)| *stats);
+            let open_fds = if container.running {
+                Self::open_fds(&container.id).await
+            } else {
+                None
+            };
+            usage.push(NodeResourceUsage {
+                label: container.service,
+                usage: ResourceUsage {
+                    cpu_percent: stats.and_then(|stats| stats.cpu_percent),
+                    rss_bytes: stats.and_then(|stats| stats.memory_bytes),
+                    open_fds,
+                    disk_bytes: container.size_rw,
+                },
+            });
+        }
+        Ok(usage)
+    }
+}
+
+fn parse_container(line: &str) -> Option<NodeContainer> {
+    let mut fields = line.split_whitespace();
+    Some(NodeContainer {
+        id: fields.next()?.to_owned(),
+        service: fields.next()?.to_owned(),
+        running: fields.next()? == "running",
+        size_rw: fields.next().and_then(|size| size.parse().ok()),
+    })
+}
+
+/// Parses `<container> <cpu>% <used> / <limit>` from `docker stats`.
+fn parse_stats(line: &str) -> Option<(String, ContainerStats)> {
+    let mut fields = line.split_whitespace();
+    let id = fields.next()?.to_owned();
+    let cpu_percent = fields
+        .next()
+        .and_then(|cpu| cpu.trim_end_matches('%').parse().ok());
+    let memory_bytes = fields.next().and_then(parse_size);
+    Some((
+        id,
+        ContainerStats {
+            cpu_percent,
+            memory_bytes,
+        },
+    ))
+}
+
+/// Parses docker's human-readable sizes, e.g. `12.5MiB` or `1.2GB`.
+fn parse_size(size: &str) -> Option<u64> {
+    let split = size
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(size.len());
+    let (value, unit) = size.split_at(split);
+    let value = value.parse::<f64>().ok()?;
+    let multiplier = match unit {
+        "B" | "" => 1.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((value * multiplier).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_stats_reads_cpu_and_memory_usage() {
+        let (id, stats) = parse_stats("3f1c2a 12.50% 256.5MiB / 7.6GiB").expect("stats line");
+
+        assert_eq!(id, "3f1c2a");
+        assert_eq!(stats.cpu_percent, Some(12.5));
+        assert_eq!(stats.memory_bytes, Some(268_959_744));
+        assert_eq!(parse_size("1.5kB"), Some(1_500));
+        assert_eq!(parse_size("0B"), Some(0));
+        assert_eq!(parse_size("12 parsecs"), None);
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use testing_framework_core::{
+    adjust_timeout,
+    scenario::{DynError, NodeExit, NodeSupervisor},
+};
+use tokio::process::Command;
+
+use crate::docker::{
+    commands::{compose_container_ids, docker_command_output},
+    logs::compose_service_logs,
+};
+
+const COMPOSE_INSPECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Log lines attached to an unexpected exit.
+const EXIT_LOG_TAIL_LINES: usize = 50;
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// Node indices currently stopped or restarting through node control, so
+/// the supervisor does not report them as exits.
+pub type HeldNodes = Arc<Mutex<HashSet<usize>>>;
+
+/// Watches node containers for exits: restarts by the `on-failure` policy,
+/// and containers left exited while node control did not stop them.
+pub struct ComposeSupervisor {
+    compose_file: PathBuf,
+    project_name: String,
+    held: HeldNodes,
+    services: Mutex<HashMap<String, ServiceWatch>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ServiceWatch {
+    restarts: u64,
+    exit_reported: bool,
+}
+
+/// Container state as reported by `docker inspect`.
+#[derive(Debug, PartialEq, Eq)]
+struct ContainerState {
+    service: String,
+    restarts: u64,
+    status: String,
+    exit_code: i64,
+    oom_killed: bool,
+}
+
+impl ComposeSupervisor {
+    pub const fn new(compose_file: PathBuf, project_name: String, held: HeldNodes) -> Self {
+        Self {
+            compose_file,
+            project_name,
+            held,
+            services: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn container_states(&self) -> Result<Vec<ContainerState>, DynError> {
+        let timeout = adjust_timeout(COMPOSE_INSPECT_TIMEOUT);
+        let ids = compose_container_ids(&self.compose_file, &self.project_name, timeout).await?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut inspect = Command::new("docker");
+        inspect
+            .arg("inspect")
+            .arg("--format")
+            .arg(format!(
+                "{{{{index .Config.Labels \"{SERVICE_LABEL}\"}}}} {{{{.RestartCount}}}} {{{{.State.Status}}}} {{{{.State.ExitCode}}}} {{{{.State.OOMKilled}}}}"
+            ))
+            .args(&ids);
+        let output = docker_command_output(inspect, timeout, "docker inspect").await?;
+        Ok(String::from_utf8_lossy(&output)
+            .lines()
+            .filter_map(parse_container_state)
+            .collect())
+    }
+
+    /// Compare container states with the previous poll and return the
+    /// services that exited, with a description of how.
+    fn detect_exits(&self, states: Vec<ContainerState>) -> Vec<(String, String)> {
+        let held = self
+            .held
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        let mut services = self
+            .services
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut exits = Vec::new();
+        for state in states {
+            let Some(index) = node_index(&state.service) else {
+                continue;
+            };
+            // The first poll sets the baseline for the restart counter.
+            let watch = services
+                .entry(state.service.clone())
+                .or_insert(ServiceWatch {
+                    restarts: state.restarts,
+                    exit_reported: false,
+                });
+
+            if state.restarts > watch.restarts {
+                let restarts = state.restarts - watch.restarts;
+                watch.restarts = state.restarts;
+                exits.push((
+                    state.service,
+                    format!("restarted {restarts} time(s) by the container restart policy"),
+                ));
+                continue;
+            }
+
+            match state.status.as_str() {
+                "exited" | "dead" if !held.contains(&index) && !watch.exit_reported => {
+                    watch.exit_reported = true;
+                    let mut status = format!(
+                        "container {} with exit code {}",
+                        state.status, state.exit_code
+                    );
+                    if state.oom_killed {
+                        status.push_str(" (OOM killed)");
+                    }
+                    exits.push((state.service, status));
+                }
+                "running" => watch.exit_reported = false,
+                _ => {}
+            }
+        }
+        exits
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeSupervisor for ComposeSupervisor {
+    async fn poll_exits(&self) -> Result<Vec<NodeExit>, DynError> {
+        let states = self.container_states().await?;
+        let mut exits = Vec::new();
+        for (service, status) in self.detect_exits(states) {
+            let log_tail = compose_service_logs(
+                &self.compose_file,
+                &self.project_name,
+                &service,
+                Some(EXIT_LOG_TAIL_LINES),
+            )
+            .await
+            .unwrap_or_else(|err| format!("<failed to collect logs: {err}>"));
+            exits.push(NodeExit {
+                label: service,
+                status,
+                log_tail,
+            });
+        }
+        Ok(exits)
+    }
+}
+
+fn node_index(service: &str) -> Option<usize> {
+    service.strip_prefix("node-")?.parse().ok()
+}
+
+fn parse_container_state(line: &str) -> Option<ContainerState> {
+    let mut fields = line.split_whitespace();
+    Some(ContainerState {
+        service: fields.next()?.to_owned(),
+        restarts: fields.next()?.parse().ok()?,
+        status: fields.next()?.to_owned(),
+        exit_code: fields.next()?.parse().ok()?,
+        oom_killed: fields.next()? == "true",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(service: &str, restarts: u64, status: &str, exit_code: i64) -> ContainerState {
+        ContainerState {
+            service: service.to_owned(),
+            restarts,
+            status: status.to_owned(),
+            exit_code,
+            oom_killed: false,
+        }
+    }
+
+    #[test]
+    fn detect_exits_ignores_held_nodes_and_reports_each_exit_once() {
+        let supervisor = ComposeSupervisor::new(
+            PathBuf::from("compose.yml"),
+            "tf".to_owned(),
+            HeldNodes::default(),
+        );
+        assert_eq!(
+            parse_container_state("node-1 2 running 0 false"),
+            Some(state("node-1", 2, "running", 0))
+        );
+        assert!(
+            supervisor
+                .detect_exits(vec![
+                    state("node-0", 0, "running", 0),
+                    state("node-1", 0, "running", 0)
+                ])
+                .is_empty()
+        );
+
+        supervisor.held.lock().unwrap().insert(0);
+        let exits = supervisor.detect_exits(vec![
+            state("node-0", 0, "exited", 137),
+            state("node-1", 1, "running", 0),
+        ]);
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].0, "node-1");
+
+        supervisor.held.lock().unwrap().clear();
+        let exits = supervisor.detect_exits(vec![state("node-0", 0, "exited", 137)]);
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].1, "container exited with exit code 137");
+        assert!(
+            supervisor
+                .detect_exits(vec![state("node-0", 0, "exited", 137)])
+                .is_empty()
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use testing_framework_core::scenario::{BlockFeed, BlockFeedTask, NodeClients, spawn_block_feed};
+use rand::rngs::StdRng;
+use testing_framework_core::scenario::{
+    BlockFeed, BlockFeedTask, NodeClients, ScenarioSeed, spawn_block_feed_with_nodes,
+};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
@@ -8,9 +11,11 @@ use crate::errors::ComposeRunnerError;
 
 const BLOCK_FEED_MAX_ATTEMPTS: usize = 5;
 const BLOCK_FEED_RETRY_DELAY: Duration = Duration::from_secs(1);
+const BLOCK_FEED_SEED_STREAM: &str = "block_feed";
 
 async fn spawn_block_feed_with(
     node_clients: &NodeClients,
+    rng: &mut StdRng,
 ) -> Result<(BlockFeed, BlockFeedTask), ComposeRunnerError> {
     debug!(
         nodes = node_clients.node_clients().len(),
@@ -18,21 +23,23 @@ async fn spawn_block_feed_with(
     );
 
     let block_source_client = node_clients
-        .random_node()
+        .random_node_with(rng)
         .ok_or(ComposeRunnerError::BlockFeedMissing)?;
 
-    spawn_block_feed(block_source_client)
+    spawn_block_feed_with_nodes(block_source_client, node_clients.clone())
         .await
         .map_err(|source| ComposeRunnerError::BlockFeed { source })
 }
 
 pub async fn spawn_block_feed_with_retry(
     node_clients: &NodeClients,
+    seed: ScenarioSeed,
 ) -> Result<(BlockFeed, BlockFeedTask), ComposeRunnerError> {
+    let mut rng = seed.rng(BLOCK_FEED_SEED_STREAM);
     let mut last_err = None;
     for attempt in 1..=BLOCK_FEED_MAX_ATTEMPTS {
         info!(attempt, "starting block feed");
-        match spawn_block_feed_with(node_clients).await {
+        match spawn_block_feed_with(node_clients, &mut rng).await {
             Ok(result) => {
                 info!(attempt, "block feed established");
                 return Ok(result);
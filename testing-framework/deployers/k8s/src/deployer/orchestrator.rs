@@ -1,14 +1,17 @@
+use std::sync::Arc;
+
 use anyhow::Error;
 use async_trait::async_trait;
 use kube::Client;
 use testing_framework_core::{
     scenario::{
-        BlockFeedTask, CleanupGuard, Deployer, MetricsError, ObservabilityCapability,
-        ObservabilityInputs, RunContext, Runner, Scenario,
+        BlockFeedTask, CleanupGuard, Deployer, MetricsError, NodeControlCapability,
+        NodeControlHandle, ObservabilityCapability, ObservabilityInputs, RequiresNodeControl,
+        RunContext, Runner, Scenario,
     },
     topology::generation::GeneratedTopology,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::{
     infrastructure::{
@@ -20,8 +23,15 @@ use crate::{
         },
         helm::HelmError,
     },
-    lifecycle::{block_feed::spawn_block_feed_with, cleanup::RunnerCleanup},
-    wait::{ClusterWaitError, PortForwardHandle},
+    lifecycle::{
+        block_feed::spawn_block_feed_with,
+        cleanup::RunnerCleanup,
+        logs::{K8sNodeLogs, SharedPodLogs},
+        node_control::{K8sNodeControl, SharedPortForwards},
+        resources::K8sResourceSampler,
+        supervisor::K8sSupervisor,
+    },
+    wait::{ClusterWaitError, NodeConfigPorts},
 };
 
 /// Deploys a scenario into Kubernetes using Helm charts and port-forwards.
@@ -109,6 +119,18 @@ impl Deployer<ObservabilityCapability> for K8sDeployer {
     }
 }
 
+#[async_trait]
+impl Deployer<NodeControlCapability> for K8sDeployer {
+    type Error = K8sRunnerError;
+
+    async fn deploy(
+        &self,
+        scenario: &Scenario<NodeControlCapability>,
+    ) -> Result<Runner, Self::Error> {
+        deploy_with_observability(self, scenario, None).await
+    }
+}
+
 async fn fail_cluster(cluster: &mut Option<ClusterEnvironment>, reason: &str) {
     if let Some(env) = cluster.as_mut() {
         env.fail(reason).await;
@@ -133,12 +155,18 @@ async fn deploy_with_observability<Caps>(
     deployer: &K8sDeployer,
     scenario: &Scenario<Caps>,
     observability: Option<&ObservabilityCapability>,
-) -> Result<Runner, K8sRunnerError> {
+) -> Result<Runner, K8sRunnerError>
+where
+    Caps: RequiresNodeControl,
+{
     let observability = resolve_observability_inputs(observability)?;
 
     let descriptors = scenario.topology().clone();
     let node_count = descriptors.nodes().len();
     ensure_supported_topology(&descriptors)?;
+    if !scenario.link_shaping().current().is_empty() {
+        warn!("k8s runner does not support link shaping; configured conditions are ignored");
+    }
 
     let client = init_kube_client().await?;
 
@@ -187,8 +215,9 @@ async fn deploy_with_observability<Caps>(
 
     maybe_print_endpoints(&observability, &node_clients);
 
-    finalize_runner(
+    finalize_runner::<Caps>(
         &mut cluster,
+        &port_specs,
         descriptors,
         node_clients,
         scenario.duration(),
@@ -339,8 +368,9 @@ fn maybe_print_endpoints(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn finalize_runner(
+fn finalize_runner<Caps>(
     cluster: &mut Option<ClusterEnvironment>,
+    port_specs: &PortSpecs,
     descriptors: GeneratedTopology,
     node_clients: testing_framework_core::scenario::NodeClients,
     duration: std::time::Duration,
@@ -348,12 +378,35 @@ fn finalize_runner(
     block_feed: testing_framework_core::scenario::BlockFeed,
     block_feed_guard: BlockFeedTask,
     node_count: usize,
-) -> Result<Runner, K8sRunnerError> {
+) -> Result<Runner, K8sRunnerError>
+where
+    Caps: RequiresNodeControl,
+{
     let environment = cluster
         .take()
         .ok_or_else(|| K8sRunnerError::InternalInvariant {
             message: "cluster should still be available".to_owned(),
         })?;
+    let retired_logs = SharedPodLogs::default();
+    let node_control =
+        maybe_node_control::<Caps>(&environment, &port_specs.nodes, Arc::clone(&retired_logs));
+    let node_logs = Arc::new(K8sNodeLogs::new(
+        environment.client().clone(),
+        environment.namespace().to_owned(),
+        environment.release().to_owned(),
+        node_count,
+        retired_logs,
+    ));
+    let supervisor = Arc::new(K8sSupervisor::new(
+        environment.client().clone(),
+        environment.namespace().to_owned(),
+        environment.release().to_owned(),
+    ));
+    let resources = Arc::new(K8sResourceSampler::new(
+        environment.client().clone(),
+        environment.namespace().to_owned(),
+        environment.release().to_owned(),
+    ));
     let (cleanup, port_forwards) = environment.into_cleanup()?;
 
     let cleanup_guard: Box<dyn CleanupGuard> = Box::new(K8sCleanupGuard::new(
@@ -369,8 +422,11 @@ fn finalize_runner(
         duration,
         telemetry,
         block_feed,
-        None,
-    );
+        node_control,
+    )
+    .with_node_logs(node_logs)
+    .with_node_supervisor(supervisor)
+    .with_resource_sampler(resources);
 
     info!(
         nodes = node_count,
@@ -378,20 +434,42 @@ fn finalize_runner(
         "k8s deployment ready; handing control to scenario runner"
     );
 
-    Ok(Runner::new(context, Some(cleanup_guard)))
+    Ok(Runner::new(context, Some(cleanup_guard)).with_deployer("k8s"))
+}
+
+fn maybe_node_control<Caps>(
+    environment: &ClusterEnvironment,
+    node_ports: &[NodeConfigPorts],
+    retired_logs: SharedPodLogs,
+) -> Option<Arc<dyn NodeControlHandle>>
+where
+    Caps: RequiresNodeControl,
+{
+    Caps::REQUIRED.then(|| {
+        let (api_ports, testing_ports) = environment.node_ports();
+        Arc::new(K8sNodeControl::new(
+            environment.client().clone(),
+            environment.namespace().to_owned(),
+            environment.release().to_owned(),
+            node_ports.to_vec(),
+            (api_ports.to_vec(), testing_ports.to_vec()),
+            environment.port_forwards(),
+            retired_logs,
+        )) as Arc<dyn NodeControlHandle>
+    })
 }
 
 struct K8sCleanupGuard {
     cleanup: RunnerCleanup,
     block_feed: Option<BlockFeedTask>,
-    port_forwards: Vec<PortForwardHandle>,
+    port_forwards: SharedPortForwards,
 }
 
 impl K8sCleanupGuard {
     const fn new(
         cleanup: RunnerCleanup,
         block_feed: BlockFeedTask,
-        port_forwards: Vec<PortForwardHandle>,
+        port_forwards: SharedPortForwards,
     ) -> Self {
         Self {
             cleanup,
@@ -406,7 +484,12 @@ impl CleanupGuard for K8sCleanupGuard {
         if let Some(block_feed) = self.block_feed.take() {
             CleanupGuard::cleanup(Box::new(block_feed));
         }
-        kill_port_forwards(&mut self.port_forwards);
+        kill_port_forwards(
+            &mut self
+                .port_forwards
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
         CleanupGuard::cleanup(Box::new(self.cleanup));
     }
 }
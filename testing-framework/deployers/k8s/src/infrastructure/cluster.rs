@@ -1,4 +1,7 @@
-use std::env;
+use std::{
+    env,
+    sync::{Arc, Mutex},
+};
 
 use kube::Client;
 use reqwest::Url;
@@ -13,7 +16,9 @@ use uuid::Uuid;
 
 use crate::{
     infrastructure::assets::RunnerAssets,
-    lifecycle::{cleanup::RunnerCleanup, logs::dump_namespace_logs},
+    lifecycle::{
+        cleanup::RunnerCleanup, logs::dump_namespace_logs, node_control::SharedPortForwards,
+    },
     wait::{
         ClusterPorts, ClusterReady, NodeConfigPorts, PortForwardHandle, wait_for_cluster_ready,
     },
@@ -33,7 +38,7 @@ pub struct ClusterEnvironment {
     node_host: String,
     node_api_ports: Vec<u16>,
     node_testing_ports: Vec<u16>,
-    port_forwards: Vec<PortForwardHandle>,
+    port_forwards: SharedPortForwards,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -62,7 +67,7 @@ impl ClusterEnvironment {
             node_host: ports.node_host.clone(),
             node_api_ports,
             node_testing_ports,
-            port_forwards,
+            port_forwards: Arc::new(Mutex::new(port_forwards)),
         }
     }
 
@@ -74,7 +79,12 @@ impl ClusterEnvironment {
             "k8s stack failure; collecting diagnostics"
         );
         dump_namespace_logs(&self.client, &self.namespace).await;
-        kill_port_forwards(&mut self.port_forwards);
+        kill_port_forwards(
+            &mut self
+                .port_forwards
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
         if let Some(guard) = self.cleanup.take() {
             CleanupGuard::cleanup(Box::new(guard));
         }
@@ -82,23 +92,29 @@ impl ClusterEnvironment {
 
     pub fn into_cleanup(
         self,
-    ) -> Result<(RunnerCleanup, Vec<PortForwardHandle>), ClusterEnvironmentError> {
+    ) -> Result<(RunnerCleanup, SharedPortForwards), ClusterEnvironmentError> {
         let cleanup = self
             .cleanup
             .ok_or(ClusterEnvironmentError::MissingCleanupGuard)?;
         Ok((cleanup, self.port_forwards))
     }
 
-    #[allow(dead_code)]
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
     pub fn namespace(&self) -> &str {
         &self.namespace
     }
 
-    #[allow(dead_code)]
     pub fn release(&self) -> &str {
         &self.release
     }
 
+    pub fn port_forwards(&self) -> SharedPortForwards {
+        Arc::clone(&self.port_forwards)
+    }
+
     pub fn node_ports(&self) -> (&[u16], &[u16]) {
         (&self.node_api_ports, &self.node_testing_ports)
     }
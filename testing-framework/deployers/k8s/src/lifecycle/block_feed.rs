@@ -1,4 +1,6 @@
-use testing_framework_core::scenario::{BlockFeed, BlockFeedTask, NodeClients, spawn_block_feed};
+use testing_framework_core::scenario::{
+    BlockFeed, BlockFeedTask, NodeClients, spawn_block_feed_with_nodes,
+};
 use tracing::{debug, info};
 
 use crate::deployer::K8sRunnerError;
@@ -15,11 +17,10 @@ pub async fn spawn_block_feed_with(
         .node_clients()
         .into_iter()
         .next()
-        .or_else(|| node_clients.any_client())
         .ok_or(K8sRunnerError::BlockFeedMissing)?;
 
     info!("starting block feed");
-    spawn_block_feed(block_source_client)
+    spawn_block_feed_with_nodes(block_source_client, node_clients.clone())
         .await
         .map_err(|source| K8sRunnerError::BlockFeed { source })
 }
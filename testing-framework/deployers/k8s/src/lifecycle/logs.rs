@@ -1,9 +1,16 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
 use k8s_openapi::api::core::v1::Pod;
 use kube::{
     Api, Client,
     api::{ListParams, LogParams},
 };
-use tracing::{info, warn};
+use testing_framework_core::scenario::{DynError, NodeLogSource, NodeLogs};
+use tracing::{debug, info, warn};
 
 pub async fn dump_namespace_logs(client: &Client, namespace: &str) {
     let pod_names = match list_pod_names(client, namespace).await {
@@ -42,3 +49,91 @@ async fn stream_pod_logs(client: &Client, namespace: &str, pod_name: &str) {
         Err(err) => warn!(pod = pod_name, error = ?err, "failed to fetch pod logs"),
     }
 }
+
+/// Logs of node pods deleted during the run, by node index, shared between
+/// the node control handle that deletes them and the log source.
+pub type SharedPodLogs = Arc<Mutex<HashMap<usize, String>>>;
+
+/// Collects node logs from the pods of a Helm release, labelled
+/// `node-{index}`. Logs of pods replaced by a restart are taken from
+/// `retired`.
+pub struct K8sNodeLogs {
+    client: Client,
+    namespace: String,
+    release: String,
+    node_count: usize,
+    retired: SharedPodLogs,
+}
+
+impl K8sNodeLogs {
+    pub const fn new(
+        client: Client,
+        namespace: String,
+        release: String,
+        node_count: usize,
+        retired: SharedPodLogs,
+    ) -> Self {
+        Self {
+            client,
+            namespace,
+            release,
+            node_count,
+            retired,
+        }
+    }
+
+    async fn node_logs(&self, pods: &Api<Pod>, index: usize) -> Result<String, kube::Error> {
+        let selector = format!(
+            "app.kubernetes.io/instance={},nomos/logical-role=node,nomos/node-index={index}",
+            self.release
+        );
+        let list = pods.list(&ListParams::default().labels(&selector)).await?;
+
+        let mut contents = self
+            .retired
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&index)
+            .cloned()
+            .unwrap_or_default();
+        for pod_name in list.into_iter().filter_map(|pod| pod.metadata.name) {
+            contents.push_str(&pod_logs(pods, &pod_name).await?);
+        }
+        Ok(contents)
+    }
+}
+
+/// Full log of a pod's node container, preceded by its previous instance's
+/// log when the kubelet restarted it.
+pub async fn pod_logs(pods: &Api<Pod>, pod_name: &str) -> Result<String, kube::Error> {
+    // A container restarted by the kubelet keeps its crash output in the
+    // previous instance's log.
+    let previous = LogParams {
+        previous: true,
+        ..Default::default()
+    };
+    let mut contents = match pods.logs(pod_name, &previous).await {
+        Ok(log) => log,
+        Err(err) => {
+            debug!(pod = %pod_name, error = ?err, "no previous container logs");
+            String::new()
+        }
+    };
+    contents.push_str(&pods.logs(pod_name, &LogParams::default()).await?);
+    Ok(contents)
+}
+
+#[async_trait]
+impl NodeLogSource for K8sNodeLogs {
+    async fn collect_logs(&self) -> Result<Vec<NodeLogs>, DynError> {
+        let pods: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+        let mut logs = Vec::with_capacity(self.node_count);
+        for index in 0..self.node_count {
+            logs.push(NodeLogs {
+                label: format!("node-{index}"),
+                contents: self.node_logs(&pods, index).await?,
+            });
+        }
+        Ok(logs)
+    }
+}
@@ -1,4 +1,7 @@
 pub mod block_feed;
 pub mod cleanup;
 pub mod logs;
+pub mod node_control;
+pub mod resources;
+pub mod supervisor;
 pub mod wait;
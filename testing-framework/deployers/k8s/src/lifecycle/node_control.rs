@@ -0,0 +1,249 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use k8s_openapi::api::core::v1::Pod;
+use kube::{
+    Api, Client,
+    api::{DeleteParams, ListParams},
+};
+use testing_framework_core::scenario::{DynError, NodeControlHandle, http_probe::NODE_ROLE};
+use tokio::time::{Instant, sleep};
+use tracing::{info, warn};
+
+use crate::lifecycle::{
+    logs::{SharedPodLogs, pod_logs},
+    wait::{
+        ClusterWaitError, FORWARDS_PER_NODE, NodeConfigPorts, PortForwardHandle, PortForwardSpawn,
+        deployment_timeout, port_forward_service_on, wait_for_node_http_port_forward,
+    },
+};
+
+const POD_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Port-forward handles shared between the node control handle and the
+/// cleanup guard.
+pub type SharedPortForwards = Arc<Mutex<Vec<PortForwardHandle>>>;
+
+/// K8s-specific node control handle: restarts a node by deleting its pod and
+/// waiting for the deployment to replace it. The deleted pod's logs are kept
+/// in `retired_logs` first.
+pub struct K8sNodeControl {
+    client: Client,
+    namespace: String,
+    release: String,
+    node_ports: Vec<NodeConfigPorts>,
+    node_api_ports: Vec<u16>,
+    node_testing_ports: Vec<u16>,
+    port_forwards: SharedPortForwards,
+    retired_logs: SharedPodLogs,
+}
+
+impl K8sNodeControl {
+    pub fn new(
+        client: Client,
+        namespace: String,
+        release: String,
+        node_ports: Vec<NodeConfigPorts>,
+        (node_api_ports, node_testing_ports): (Vec<u16>, Vec<u16>),
+        port_forwards: SharedPortForwards,
+        retired_logs: SharedPodLogs,
+    ) -> Self {
+        Self {
+            client,
+            namespace,
+            release,
+            node_ports,
+            node_api_ports,
+            node_testing_ports,
+            port_forwards,
+            retired_logs,
+        }
+    }
+
+    async fn restart(&self, index: usize) -> Result<(), ClusterWaitError> {
+        let selector = format!(
+            "app.kubernetes.io/instance={},nomos/logical-role=node,nomos/node-index={index}",
+            self.release
+        );
+        let pods: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+
+        let previous = list_pod_names(&pods, &selector).await?;
+        info!(index, pods = ?previous, namespace = %self.namespace, "deleting k8s node pod");
+        for name in &previous {
+            self.retire_logs(&pods, index, name).await;
+            pods.delete(name, &DeleteParams::default())
+                .await
+                .map_err(|source| ClusterWaitError::PodDelete {
+                    name: name.clone(),
+                    source,
+                })?;
+        }
+
+        wait_for_replacement_pod(&pods, &selector, &previous).await?;
+
+        if self.is_port_forwarded() {
+            self.restore_port_forwards(index).await?;
+        }
+
+        info!(index, "k8s node restarted");
+        Ok(())
+    }
+
+    /// Keep a pod's logs before it is deleted; they are gone from the API
+    /// afterwards.
+    async fn retire_logs(&self, pods: &Api<Pod>, index: usize, pod_name: &str) {
+        match pod_logs(pods, pod_name).await {
+            Ok(log) => self
+                .retired_logs
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .entry(index)
+                .or_default()
+                .push_str(&log),
+            Err(err) => {
+                warn!(index, pod = pod_name, error = ?err, "failed to keep logs of restarted pod");
+            }
+        }
+    }
+
+    fn is_port_forwarded(&self) -> bool {
+        !self
+            .port_forwards
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .is_empty()
+    }
+
+    /// `kubectl port-forward` is bound to the deleted pod, so re-create the
+    /// forwards on the same local ports to keep existing API clients valid.
+    async fn restore_port_forwards(&self, index: usize) -> Result<(), ClusterWaitError> {
+        let ports = self.node_ports[index];
+        let api_port = self.node_api_ports[index];
+        let testing_port = self.node_testing_ports[index];
+        let namespace = self.namespace.clone();
+        let service = format!("{}-node-{index}", self.release);
+
+        {
+            let mut forwards = self
+                .port_forwards
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            for handle in forwards
+                .iter_mut()
+                .skip(index * FORWARDS_PER_NODE)
+                .take(FORWARDS_PER_NODE)
+            {
+                handle.shutdown();
+            }
+        }
+
+        let (api_forward, testing_forward) = tokio::task::spawn_blocking(move || {
+            let PortForwardSpawn { handle: api, .. } =
+                port_forward_service_on(&namespace, &service, api_port, ports.api)?;
+            let PortForwardSpawn {
+                handle: testing, ..
+            } = port_forward_service_on(&namespace, &service, testing_port, ports.testing)?;
+            Ok::<_, ClusterWaitError>((api, testing))
+        })
+        .await
+        .map_err(|source| ClusterWaitError::PortForwardTask {
+            source: source.into(),
+        })??;
+
+        {
+            let mut forwards = self
+                .port_forwards
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let base = index * FORWARDS_PER_NODE;
+            forwards[base] = api_forward;
+            forwards[base + 1] = testing_forward;
+        }
+
+        wait_for_node_http_port_forward(&[api_port], NODE_ROLE).await
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeControlHandle for K8sNodeControl {
+    async fn restart_node(&self, index: usize) -> Result<(), DynError> {
+        if index >= self.node_ports.len() {
+            return Err(format!(
+                "node index {index} out of range (nodes={})",
+                self.node_ports.len()
+            )
+            .into());
+        }
+
+        self.restart(index)
+            .await
+            .map_err(|err| format!("node restart failed: {err}").into())
+    }
+}
+
+async fn list_pod_names(pods: &Api<Pod>, selector: &str) -> Result<Vec<String>, ClusterWaitError> {
+    let list = pods
+        .list(&ListParams::default().labels(selector))
+        .await
+        .map_err(|source| ClusterWaitError::PodList {
+            selector: selector.to_owned(),
+            source,
+        })?;
+    Ok(list
+        .into_iter()
+        .filter_map(|pod| pod.metadata.name)
+        .collect())
+}
+
+async fn wait_for_replacement_pod(
+    pods: &Api<Pod>,
+    selector: &str,
+    previous: &[String],
+) -> Result<(), ClusterWaitError> {
+    let previous: HashSet<&str> = previous.iter().map(String::as_str).collect();
+    let timeout = deployment_timeout();
+    let deadline = Instant::now() + timeout;
+
+    while Instant::now() < deadline {
+        let list = pods
+            .list(&ListParams::default().labels(selector))
+            .await
+            .map_err(|source| ClusterWaitError::PodList {
+                selector: selector.to_owned(),
+                source,
+            })?;
+
+        let replaced = list.into_iter().any(|pod| {
+            let fresh = pod
+                .metadata
+                .name
+                .as_deref()
+                .is_some_and(|name| !previous.contains(name));
+            fresh && pod.metadata.deletion_timestamp.is_none() && is_pod_ready(&pod)
+        });
+        if replaced {
+            return Ok(());
+        }
+
+        sleep(POD_POLL_INTERVAL).await;
+    }
+
+    Err(ClusterWaitError::PodReplacementTimeout {
+        selector: selector.to_owned(),
+        timeout,
+    })
+}
+
+fn is_pod_ready(pod: &Pod) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|status| status.conditions.as_ref())
+        .is_some_and(|conditions| {
+            conditions
+                .iter()
+                .any(|condition| condition.type_ == "Ready" && condition.status == "True")
+        })
+}
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use kube::{
+    Api, Client,
+    api::{ApiResource, DynamicObject, ListParams},
+};
+use testing_framework_core::scenario::{
+    DynError, NodeResourceUsage, ResourceSampler, ResourceUsage,
+};
+
+const NODE_INDEX_LABEL: &str = "nomos/node-index";
+
+/// Samples node pods through the metrics API (`metrics.k8s.io`, served by
+/// metrics-server). Memory is the containers' working set; open files and disk
+/// usage are not exposed there and stay unset.
+pub struct K8sResourceSampler {
+    client: Client,
+    namespace: String,
+    release: String,
+}
+
+impl K8sResourceSampler {
+    pub fn new(client: Client, namespace: String, release: String) -> Self {
+        Self {
+            client,
+            namespace,
+            release,
+        }
+    }
+}
+
+#[async_trait]
+impl ResourceSampler for K8sResourceSampler {
+    async fn sample(&self) -> Result<Vec<NodeResourceUsage>, DynError> {
+        let resource = ApiResource {
+            group: "metrics.k8s.io".to_owned(),
+            version: "v1beta1".to_owned(),
+            api_version: "metrics.k8s.io/v1beta1".to_owned(),
+            kind: "PodMetrics".to_owned(),
+            plural: "pods".to_owned(),
+        };
+        let metrics: Api<DynamicObject> =
+            Api::namespaced_with(self.client.clone(), &self.namespace, &resource);
+        let selector = format!(
+            "app.kubernetes.io/instance={},nomos/logical-role=node",
+            self.release
+        );
+        let list = metrics
+            .list(&ListParams::default().labels(&selector))
+            .await?;
+
+        Ok(list
+            .items
+            .into_iter()
+            .filter_map(|pod| {
+                let index = pod.metadata.labels.as_ref()?.get(NODE_INDEX_LABEL)?;
+                let label = format!("node-{index}");
+                let containers = pod.data.get("containers")?.as_array()?;
+
+                let mut cpu_cores = None;
+                let mut memory_bytes = None;
+                for usage in containers
+                    .iter()
+                    .filter_map(|container| container.get("usage"))
+                {
+                    if let Some(cpu) = usage.get("cpu").and_then(|cpu| cpu.as_str()) {
+                        *cpu_cores.get_or_insert(0.0) += parse_cpu(cpu).unwrap_or(0.0);
+                    }
+                    if let Some(memory) = usage.get("memory").and_then(|memory| memory.as_str()) {
+                        *memory_bytes.get_or_insert(0) += parse_memory(memory).unwrap_or(0);
+                    }
+                }
+                Some(NodeResourceUsage {
+                    label,
+                    usage: ResourceUsage {
+                        cpu_percent: cpu_cores.map(|cores| cores * 100.0),
+                        rss_bytes: memory_bytes,
+                        open_fds: None,
+                        disk_bytes: None,
+                    },
+                })
+            })
+            .collect())
+    }
+}
+
+/// CPU quantity in cores, e.g. `250m` or `1234567n`.
+fn parse_cpu(quantity: &str) -> Option<f64> {
+    let (value, scale) = match quantity.char_indices().last()? {
+        (at, 'n') => (&quantity[..at], 1e-9),
+        (at, 'u') => (&quantity[..at], 1e-6),
+        (at, 'm') => (&quantity[..at], 1e-3),
+        _ => (quantity, 1.0),
+    };
+    Some(value.parse::<f64>().ok()? * scale)
+}
+
+/// Memory quantity in bytes, e.g. `123456Ki` or `1G`.
+fn parse_memory(quantity: &str) -> Option<u64> {
+    let split = quantity
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(quantity.len());
+    let (value, unit) = quantity.split_at(split);
+    let multiplier: u64 = match unit {
+        "" => 1,
+        "k" => 1_000,
+        "M" => 1_000_000,
+        "G" => 1_000_000_000,
+        "Ki" => 1 << 10,
+        "Mi" => 1 << 20,
+        "Gi" => 1 << 30,
+        "Ti" => 1 << 40,
+        _ => return None,
+    };
+    value.parse::<u64>().ok()?.checked_mul(multiplier)
+}
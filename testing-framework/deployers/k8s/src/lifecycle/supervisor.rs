@@ -0,0 +1,121 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+use k8s_openapi::api::core::v1::{ContainerStatus, Pod};
+use kube::{
+    Api, Client,
+    api::{ListParams, LogParams},
+};
+use testing_framework_core::scenario::{DynError, NodeExit, NodeSupervisor};
+
+/// Log lines attached to an unexpected exit.
+const EXIT_LOG_TAIL_LINES: i64 = 50;
+const NODE_INDEX_LABEL: &str = "nomos/node-index";
+
+/// Watches node pods for container restarts. Pods replaced through node
+/// control start with fresh counters and terminating pods are skipped, so
+/// only crashes are reported.
+pub struct K8sSupervisor {
+    client: Client,
+    namespace: String,
+    release: String,
+    /// Container restarts already reported, by pod name.
+    reported: Mutex<HashMap<String, i32>>,
+}
+
+impl K8sSupervisor {
+    pub fn new(client: Client, namespace: String, release: String) -> Self {
+        Self {
+            client,
+            namespace,
+            release,
+            reported: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Pods whose containers restarted since the previous poll, with a
+    /// description of the last termination.
+    fn new_restarts(&self, pods: Vec<Pod>) -> Vec<(String, String, String)> {
+        let mut reported = self
+            .reported
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut restarted = Vec::new();
+        for pod in pods {
+            if pod.metadata.deletion_timestamp.is_some() {
+                continue;
+            }
+            let Some(name) = pod.metadata.name.clone() else {
+                continue;
+            };
+            let label = pod
+                .metadata
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(NODE_INDEX_LABEL))
+                .map_or_else(|| name.clone(), |index| format!("node-{index}"));
+            let statuses = pod
+                .status
+                .and_then(|status| status.container_statuses)
+                .unwrap_or_default();
+
+            let restarts = statuses.iter().map(|status| status.restart_count).sum();
+            let seen = reported.entry(name.clone()).or_insert(0);
+            if restarts <= *seen {
+                continue;
+            }
+            let count = restarts - *seen;
+            *seen = restarts;
+            restarted.push((name, label, describe_restart(&statuses, count)));
+        }
+        restarted
+    }
+}
+
+#[async_trait]
+impl NodeSupervisor for K8sSupervisor {
+    async fn poll_exits(&self) -> Result<Vec<NodeExit>, DynError> {
+        let pods: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+        let selector = format!(
+            "app.kubernetes.io/instance={},nomos/logical-role=node",
+            self.release
+        );
+        let list = pods.list(&ListParams::default().labels(&selector)).await?;
+
+        let mut exits = Vec::new();
+        for (pod_name, label, status) in self.new_restarts(list.items) {
+            let params = LogParams {
+                previous: true,
+                tail_lines: Some(EXIT_LOG_TAIL_LINES),
+                ..Default::default()
+            };
+            let log_tail = pods
+                .logs(&pod_name, &params)
+                .await
+                .unwrap_or_else(|err| format!("<failed to collect logs: {err}>"));
+            exits.push(NodeExit {
+                label,
+                status: format!("pod {pod_name} {status}"),
+                log_tail,
+            });
+        }
+        Ok(exits)
+    }
+}
+
+fn describe_restart(statuses: &[ContainerStatus], count: i32) -> String {
+    let termination = statuses
+        .iter()
+        .filter_map(|status| status.last_state.as_ref()?.terminated.as_ref())
+        .map(|terminated| {
+            let reason = terminated.reason.as_deref().unwrap_or("unknown reason");
+            format!("exit code {} ({reason})", terminated.exit_code)
+        })
+        .collect::<Vec<_>>();
+    if termination.is_empty() {
+        format!("restarted {count} time(s)")
+    } else {
+        format!("restarted {count} time(s), last {}", termination.join(", "))
+    }
+}
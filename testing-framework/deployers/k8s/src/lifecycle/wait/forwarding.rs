@@ -35,6 +35,9 @@ impl Drop for PortForwardHandle {
     }
 }
 
+/// Number of forwards `port_forward_group` opens per node (api, testing).
+pub const FORWARDS_PER_NODE: usize = 2;
+
 pub struct PortForwardSpawn {
     pub local_port: u16,
     pub handle: PortForwardHandle,
@@ -91,6 +94,17 @@ pub fn port_forward_service(
         source,
     })?;
 
+    port_forward_service_on(namespace, service, local_port, remote_port)
+}
+
+/// Forward `remote_port` of `service` to a fixed local port, e.g. to restore a
+/// forward after its pod was replaced.
+pub fn port_forward_service_on(
+    namespace: &str,
+    service: &str,
+    local_port: u16,
+    remote_port: u16,
+) -> Result<PortForwardSpawn, ClusterWaitError> {
     let mut child = StdCommand::new("kubectl")
         .arg("port-forward")
         .arg("-n")
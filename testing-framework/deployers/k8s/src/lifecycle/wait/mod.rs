@@ -10,6 +10,8 @@ mod orchestrator;
 mod ports;
 
 pub use forwarding::PortForwardHandle;
+pub(crate) use forwarding::{FORWARDS_PER_NODE, PortForwardSpawn, port_forward_service_on};
+pub(crate) use http_probe::wait_for_node_http_port_forward;
 pub use orchestrator::wait_for_cluster_ready;
 use testing_framework_config::constants::{
     DEFAULT_HTTP_POLL_INTERVAL, DEFAULT_K8S_DEPLOYMENT_TIMEOUT, DEFAULT_NODE_HTTP_PROBE_TIMEOUT,
@@ -89,6 +91,20 @@ pub enum ClusterWaitError {
         #[source]
         source: anyhow::Error,
     },
+    #[error("failed to list pods for {selector}: {source}")]
+    PodList {
+        selector: String,
+        #[source]
+        source: KubeError,
+    },
+    #[error("failed to delete pod {name}: {source}")]
+    PodDelete {
+        name: String,
+        #[source]
+        source: KubeError,
+    },
+    #[error("no replacement pod for {selector} became ready within {timeout:?}")]
+    PodReplacementTimeout { selector: String, timeout: Duration },
     #[error("port-forward task failed: {source}")]
     PortForwardTask {
         #[source]
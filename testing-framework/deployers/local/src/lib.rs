@@ -1,5 +1,9 @@
+mod logs;
 mod manual;
 mod node_control;
+mod nodes;
+mod relay;
+mod resources;
 mod runner;
 
 pub use manual::{LocalManualCluster, ManualClusterError};
@@ -0,0 +1,57 @@
+use std::{fs, process::ExitStatus};
+
+use testing_framework_core::{
+    nodes::common::node::NodeLogFiles,
+    scenario::{DynError, NodeExit, NodeLogs},
+};
+use tracing::debug;
+
+/// Log lines attached to an unexpected exit.
+const EXIT_LOG_TAIL_LINES: usize = 50;
+
+/// Read every log file of the given nodes off the async runtime. Files that
+/// cannot be read are skipped.
+pub(crate) async fn read_node_logs(
+    nodes: Vec<(String, NodeLogFiles)>,
+) -> Result<Vec<NodeLogs>, DynError> {
+    let logs = tokio::task::spawn_blocking(move || {
+        nodes
+            .into_iter()
+            .map(|(label, files)| {
+                let mut contents = String::new();
+                for path in files.paths() {
+                    match fs::read(&path) {
+                        Ok(bytes) => contents.push_str(&String::from_utf8_lossy(&bytes)),
+                        Err(err) => {
+                            debug!(node = %label, path = %path.display(), error = ?err, "failed to read node log file");
+                        }
+                    }
+                }
+                NodeLogs { label, contents }
+            })
+            .collect()
+    })
+    .await?;
+    Ok(logs)
+}
+
+/// Attach the tail of each node's logs to its exit status.
+pub(crate) async fn describe_exits(
+    exits: Vec<(String, ExitStatus, NodeLogFiles)>,
+) -> Result<Vec<NodeExit>, DynError> {
+    if exits.is_empty() {
+        return Ok(Vec::new());
+    }
+    let exits = tokio::task::spawn_blocking(move || {
+        exits
+            .into_iter()
+            .map(|(label, status, files)| NodeExit {
+                label,
+                status: status.to_string(),
+                log_tail: files.tail(EXIT_LOG_TAIL_LINES),
+            })
+            .collect()
+    })
+    .await?;
+    Ok(exits)
+}
@@ -43,7 +43,7 @@ pub(super) fn build_general_config_for(
         return Ok((config, node.network_port()));
     }
 
-    let id = random_node_id();
+    let id = node_id(descriptors, index);
     let network_port = allocate_udp_port("network port")?;
     let blend_port = allocate_udp_port("Blend port")?;
     let topology = descriptors.config();
@@ -106,9 +106,14 @@ fn resolve_initial_peers(
     }
 }
 
-fn random_node_id() -> [u8; 32] {
+/// Id for a node started after the initial topology, derived from the
+/// scenario seed so replays start the same nodes.
+fn node_id(descriptors: &GeneratedTopology, index: usize) -> [u8; 32] {
     let mut id = [0u8; 32];
-    rand::thread_rng().fill(&mut id);
+    descriptors
+        .seed()
+        .rng(&format!("node-{index}"))
+        .fill(&mut id);
     id
 }
 
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
-    sync::Mutex,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
 };
 
 use nomos_node::config::RunConfig as NodeConfig;
@@ -8,15 +9,21 @@ use testing_framework_config::topology::configs::{consensus, time};
 use testing_framework_core::{
     nodes::{
         ApiClient,
+        common::node::wait_for_node_readiness,
         node::{Node, create_node_config},
     },
-    scenario::{DynError, NodeControlHandle, StartNodeOptions, StartedNode},
+    scenario::{
+        DynError, LinkShaping, NodeControlHandle, NodeExit, NodeLogSource, NodeLogs,
+        NodeResourceUsage, NodeSupervisor, ResourceSampler, StartNodeOptions, StartedNode,
+    },
     topology::{
         generation::{GeneratedTopology, find_expected_peer_counts},
         utils::multiaddr_port,
     },
 };
 use thiserror::Error;
+use tokio::time::sleep;
+use tracing::info;
 
 mod config;
 mod state;
@@ -25,6 +32,16 @@ use config::build_general_config_for;
 use state::LocalDynamicState;
 use testing_framework_core::scenario::NodeClients;
 
+use crate::{
+    logs::{describe_exits, read_node_logs},
+    relay::LinkRelays,
+    resources::{ProcSampler, SampleTarget},
+};
+
+/// How often the shaping guard re-checks for unrelayed connections while
+/// shaping is active.
+const SHAPING_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Error)]
 pub enum LocalDynamicError {
     #[error("failed to generate node config: {source}")]
@@ -50,6 +67,8 @@ pub struct LocalDynamicNodes {
     node_clients: NodeClients,
     seed: LocalDynamicSeed,
     state: Mutex<LocalDynamicState>,
+    relays: OnceLock<LinkRelays>,
+    resources: ProcSampler,
 }
 
 #[derive(Clone, Default)]
@@ -112,6 +131,7 @@ impl LocalDynamicNodes {
             peer_ports_by_name: seed.peer_ports_by_name.clone(),
             clients_by_name: HashMap::new(),
             nodes: Vec::new(),
+            node_names: Vec::new(),
         };
 
         Self {
@@ -121,7 +141,134 @@ impl LocalDynamicNodes {
             node_clients,
             seed,
             state: Mutex::new(state),
+            relays: OnceLock::new(),
+            resources: ProcSampler::default(),
+        }
+    }
+
+    /// Take ownership of the initial topology processes so they can be
+    /// restarted. Nodes are registered as `node-{index}` in topology order.
+    pub fn adopt_nodes(&self, nodes: Vec<Node>) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        for (index, node) in nodes.into_iter().enumerate() {
+            state.adopt_node(&format!("node-{index}"), node);
+        }
+    }
+
+    /// Route partition requests through the relays sitting on the initial
+    /// topology's swarm links.
+    pub(crate) fn attach_relays(&self, relays: LinkRelays) {
+        if self.relays.set(relays).is_err() {
+            tracing::warn!("link relays already attached; ignoring");
+        }
+    }
+
+    fn relays(&self) -> Result<&LinkRelays, LocalDynamicError> {
+        self.relays
+            .get()
+            .ok_or_else(|| LocalDynamicError::InvalidArgument {
+                message: "link relays are not enabled for this cluster".to_owned(),
+            })
+    }
+
+    /// Relays only sit on the initial topology's links, so refuse to
+    /// partition while any traffic bypasses them.
+    async fn ensure_relayed_links_only(&self) -> Result<(), LocalDynamicError> {
+        match self.relay_bypass().await {
+            Some(reason) => Err(LocalDynamicError::InvalidArgument {
+                message: format!(
+                    "cannot partition: {reason}; local partitions need a chain or star layout \
+                     without peer discovery"
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Why some traffic would not pass the link relays, if it would not:
+    /// nodes started after deployment, or peers dialled from discovered
+    /// addresses, are connected outside them.
+    async fn relay_bypass(&self) -> Option<String> {
+        let relayed = self.descriptors.expected_peer_counts();
+        let clients = {
+            let state = self
+                .state
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            state
+                .nodes
+                .iter()
+                .map(|node| node.api().clone())
+                .collect::<Vec<_>>()
+        };
+        if clients.len() > relayed.len() {
+            return Some(format!(
+                "{} nodes started after deployment are not behind link relays",
+                clients.len() - relayed.len()
+            ));
         }
+
+        for (index, (client, relayed)) in clients.iter().zip(relayed).enumerate() {
+            // A stopped node holds no connections to check.
+            let Ok(info) = client.network_info().await else {
+                continue;
+            };
+            if info.n_peers > relayed {
+                return Some(format!(
+                    "node-{index} has {} peers but only {relayed} relayed links",
+                    info.n_peers
+                ));
+            }
+        }
+        None
+    }
+
+    /// Warn while link shaping is active and some traffic bypasses the
+    /// relays, since that traffic is not shaped. Re-checked on every shaping
+    /// change and periodically while shaping is on, as discovered peers are
+    /// dialled over time.
+    pub(crate) fn spawn_shaping_guard(self: &Arc<Self>, shaping: &LinkShaping) {
+        let nodes = Arc::downgrade(self);
+        let mut config = shaping.subscribe();
+        tokio::spawn(async move {
+            let mut warned = None;
+            loop {
+                let active = !config.borrow_and_update().is_empty();
+                let bypass = if active {
+                    let Some(nodes) = nodes.upgrade() else {
+                        break;
+                    };
+                    nodes.relay_bypass().await
+                } else {
+                    None
+                };
+                if bypass.is_some() && bypass != warned {
+                    tracing::warn!(
+                        reason = bypass.as_deref(),
+                        "link shaping is active but some traffic bypasses the relays and is not shaped"
+                    );
+                }
+                warned = bypass;
+
+                tokio::select! {
+                    changed = config.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        // Report again for the new shaping config.
+                        warned = None;
+                    }
+                    () = sleep(SHAPING_CHECK_INTERVAL), if active => {}
+                }
+                if nodes.strong_count() == 0 {
+                    break;
+                }
+            }
+        });
     }
 
     #[must_use]
@@ -141,6 +288,7 @@ impl LocalDynamicNodes {
             .unwrap_or_else(|poisoned| poisoned.into_inner());
 
         state.nodes.clear();
+        state.node_names.clear();
         state.peer_ports.clone_from(&self.seed.peer_ports);
         state
             .peer_ports_by_name
@@ -202,6 +350,73 @@ impl LocalDynamicNodes {
             .collect::<Vec<_>>()
     }
 
+    /// Kill the node at `index` and relaunch it with the same config, ports
+    /// and data directory. The node's API client stays valid across the
+    /// restart.
+    pub async fn restart_node(&self, index: usize) -> Result<(), LocalDynamicError> {
+        info!(index, "restarting local node");
+        let api = self.with_node(index, |node| {
+            node.relaunch()
+                .map_err(|source| LocalDynamicError::Spawn { source })
+        })?;
+
+        wait_for_node_readiness(&api)
+            .await
+            .map_err(|source| LocalDynamicError::Spawn { source })?;
+
+        info!(index, "local node restarted");
+        Ok(())
+    }
+
+    /// Kill the node at `index`, keeping its data directory so it can be
+    /// resumed later.
+    pub fn stop_node(&self, index: usize) -> Result<(), LocalDynamicError> {
+        info!(index, "stopping local node");
+        self.with_node(index, |node| {
+            node.stop();
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Start a node previously stopped with [`LocalDynamicNodes::stop_node`]
+    /// and wait until it is ready.
+    pub async fn resume_node(&self, index: usize) -> Result<(), LocalDynamicError> {
+        info!(index, "resuming local node");
+        let api = self.with_node(index, |node| {
+            node.start()
+                .map_err(|source| LocalDynamicError::Spawn { source })
+        })?;
+
+        wait_for_node_readiness(&api)
+            .await
+            .map_err(|source| LocalDynamicError::Spawn { source })?;
+
+        info!(index, "local node resumed");
+        Ok(())
+    }
+
+    fn with_node(
+        &self,
+        index: usize,
+        action: impl FnOnce(&mut Node) -> Result<(), LocalDynamicError>,
+    ) -> Result<ApiClient, LocalDynamicError> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let nodes = state.nodes.len();
+        let Some(node) = state.nodes.get_mut(index) else {
+            return Err(LocalDynamicError::InvalidArgument {
+                message: format!("node index {index} out of range (nodes={nodes})"),
+            });
+        };
+
+        action(node)?;
+        Ok(node.api().clone())
+    }
+
     async fn start_node(
         &self,
         name: &str,
@@ -281,8 +496,28 @@ impl LocalDynamicNodes {
 
 #[async_trait::async_trait]
 impl NodeControlHandle for LocalDynamicNodes {
-    async fn restart_node(&self, _index: usize) -> Result<(), DynError> {
-        Err("local deployer does not support restart_node".into())
+    async fn restart_node(&self, index: usize) -> Result<(), DynError> {
+        self.restart_node(index).await.map_err(|err| err.into())
+    }
+
+    async fn stop_node(&self, index: usize) -> Result<(), DynError> {
+        self.stop_node(index).map_err(|err| err.into())
+    }
+
+    async fn resume_node(&self, index: usize) -> Result<(), DynError> {
+        self.resume_node(index).await.map_err(|err| err.into())
+    }
+
+    async fn partition(&self, groups: &[Vec<usize>]) -> Result<(), DynError> {
+        let relays = self.relays()?;
+        self.ensure_relayed_links_only().await?;
+        relays.partition(groups);
+        Ok(())
+    }
+
+    async fn heal_partition(&self) -> Result<(), DynError> {
+        self.relays()?.heal();
+        Ok(())
     }
 
     async fn start_node(&self, name: &str) -> Result<StartedNode, DynError> {
@@ -305,3 +540,71 @@ impl NodeControlHandle for LocalDynamicNodes {
         self.node_client(name)
     }
 }
+
+#[async_trait::async_trait]
+impl NodeLogSource for LocalDynamicNodes {
+    async fn collect_logs(&self) -> Result<Vec<NodeLogs>, DynError> {
+        let nodes = {
+            let state = self
+                .state
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            state
+                .node_names
+                .iter()
+                .cloned()
+                .zip(state.nodes.iter().map(|node| node.log_files().clone()))
+                .collect::<Vec<_>>()
+        };
+        read_node_logs(nodes).await
+    }
+}
+
+/// Nodes stopped or restarted through [`NodeControlHandle`] do not count as
+/// exits.
+#[async_trait::async_trait]
+impl NodeSupervisor for LocalDynamicNodes {
+    async fn poll_exits(&self) -> Result<Vec<NodeExit>, DynError> {
+        let exits = {
+            let mut state = self
+                .state
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let LocalDynamicState {
+                nodes, node_names, ..
+            } = &mut *state;
+            node_names
+                .iter()
+                .zip(nodes.iter_mut())
+                .filter_map(|(name, node)| {
+                    let status = node.unexpected_exit()?;
+                    Some((name.clone(), status, node.log_files().clone()))
+                })
+                .collect::<Vec<_>>()
+        };
+        describe_exits(exits).await
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceSampler for LocalDynamicNodes {
+    async fn sample(&self) -> Result<Vec<NodeResourceUsage>, DynError> {
+        let targets = {
+            let state = self
+                .state
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            state
+                .node_names
+                .iter()
+                .zip(state.nodes.iter())
+                .map(|(name, node)| SampleTarget {
+                    label: name.clone(),
+                    pid: node.pid(),
+                    workdir: node.workdir().to_path_buf(),
+                })
+                .collect::<Vec<_>>()
+        };
+        self.resources.sample(targets).await
+    }
+}
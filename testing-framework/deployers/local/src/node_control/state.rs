@@ -8,6 +8,8 @@ pub(crate) struct LocalDynamicState {
     pub(crate) peer_ports_by_name: HashMap<String, u16>,
     pub(crate) clients_by_name: HashMap<String, ApiClient>,
     pub(crate) nodes: Vec<Node>,
+    /// Name of each entry in `nodes`, in the same order.
+    pub(crate) node_names: Vec<String>,
 }
 
 impl LocalDynamicState {
@@ -28,5 +30,15 @@ impl LocalDynamicState {
         self.register_common(node_name, network_port, client);
         self.node_count += 1;
         self.nodes.push(node);
+        self.node_names.push(node_name.to_string());
+    }
+
+    /// Track a node from the initial topology; its ports are already part of
+    /// the seed.
+    pub(super) fn adopt_node(&mut self, node_name: &str, node: Node) {
+        self.clients_by_name
+            .insert(node_name.to_string(), node.api().clone());
+        self.nodes.push(node);
+        self.node_names.push(node_name.to_string());
     }
 }
@@ -0,0 +1,84 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use testing_framework_core::{
+    nodes::node::Node,
+    scenario::{
+        DynError, NodeExit, NodeLogSource, NodeLogs, NodeResourceUsage, NodeSupervisor,
+        ResourceSampler,
+    },
+};
+
+use crate::{
+    logs::{describe_exits, read_node_logs},
+    resources::{ProcSampler, SampleTarget},
+};
+
+/// Owns the node processes of a deployment without node control, so their
+/// logs, exits and resource usage can be watched during the run. Nodes are
+/// labelled `node-{index}` in topology order.
+pub(crate) struct LocalNodes {
+    nodes: Mutex<Vec<Node>>,
+    resources: ProcSampler,
+}
+
+impl LocalNodes {
+    pub(crate) fn new(nodes: Vec<Node>) -> Self {
+        Self {
+            nodes: Mutex::new(nodes),
+            resources: ProcSampler::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl NodeLogSource for LocalNodes {
+    async fn collect_logs(&self) -> Result<Vec<NodeLogs>, DynError> {
+        let nodes = self
+            .nodes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (format!("node-{index}"), node.log_files().clone()))
+            .collect();
+        read_node_logs(nodes).await
+    }
+}
+
+#[async_trait]
+impl NodeSupervisor for LocalNodes {
+    async fn poll_exits(&self) -> Result<Vec<NodeExit>, DynError> {
+        let exits = self
+            .nodes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, node)| {
+                let status = node.unexpected_exit()?;
+                Some((format!("node-{index}"), status, node.log_files().clone()))
+            })
+            .collect();
+        describe_exits(exits).await
+    }
+}
+
+#[async_trait]
+impl ResourceSampler for LocalNodes {
+    async fn sample(&self) -> Result<Vec<NodeResourceUsage>, DynError> {
+        let targets = self
+            .nodes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .enumerate()
+            .map(|(index, node)| SampleTarget {
+                label: format!("node-{index}"),
+                pid: node.pid(),
+                workdir: node.workdir().to_path_buf(),
+            })
+            .collect();
+        self.resources.sample(targets).await
+    }
+}
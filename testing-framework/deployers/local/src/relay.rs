@@ -0,0 +1,320 @@
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
+
+use nomos_libp2p::{Multiaddr, Protocol};
+use rand::{Rng, rngs::StdRng};
+use testing_framework_core::{
+    scenario::{LinkConditions, LinkShaping},
+    topology::{generation::GeneratedTopology, utils::multiaddr_port},
+};
+use tokio::{
+    net::UdpSocket,
+    task::{JoinHandle, JoinSet},
+    time::sleep,
+};
+use tracing::{debug, info};
+
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+/// Seed stream the relays' loss and jitter decisions are drawn from.
+const SHAPING_SEED_STREAM: &str = "link_shaping";
+
+/// In-process UDP relays placed on the swarm links between local nodes, so
+/// traffic between them can be cut or shaped without touching the host
+/// network.
+///
+/// Only links derived from each node's initial peers go through a relay;
+/// connections dialled later from discovered addresses bypass it, so node
+/// control refuses to partition, and warns while shaping is active, once a
+/// node has more peers than relayed links.
+pub(crate) struct LinkRelays {
+    policy: Arc<LinkPolicy>,
+    relay_ports: HashMap<(usize, u16), u16>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+/// Shared view of which directed links currently pass traffic, and how.
+struct LinkPolicy {
+    groups: RwLock<HashMap<usize, usize>>,
+    shaping: LinkShaping,
+    rng: Mutex<StdRng>,
+}
+
+/// What a relay does with a single datagram.
+enum Verdict {
+    Forward,
+    Delay(Duration),
+    Drop,
+}
+
+impl LinkPolicy {
+    fn allows(&self, from: usize, to: usize) -> bool {
+        let groups = self
+            .groups
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match (groups.get(&from), groups.get(&to)) {
+            (Some(from_group), Some(to_group)) => from_group == to_group,
+            _ => true,
+        }
+    }
+
+    fn verdict(&self, from: usize, to: usize) -> Verdict {
+        if !self.allows(from, to) {
+            return Verdict::Drop;
+        }
+        match self.shaping.conditions(from, to) {
+            Some(conditions) => shape(
+                conditions,
+                &mut *self
+                    .rng
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            ),
+            None => Verdict::Forward,
+        }
+    }
+}
+
+fn shape(conditions: LinkConditions, rng: &mut impl Rng) -> Verdict {
+    if conditions.loss > 0.0 && rng.gen_bool(conditions.loss.min(1.0)) {
+        return Verdict::Drop;
+    }
+
+    let jitter = conditions.jitter.as_secs_f64();
+    let offset = if jitter > 0.0 {
+        rng.gen_range(-jitter..=jitter)
+    } else {
+        0.0
+    };
+    let delay = Duration::from_secs_f64((conditions.delay.as_secs_f64() + offset).max(0.0));
+    if delay.is_zero() {
+        Verdict::Forward
+    } else {
+        Verdict::Delay(delay)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Link {
+    from: usize,
+    to: usize,
+    target: SocketAddr,
+}
+
+impl LinkRelays {
+    /// Bind one relay per (node, initial peer) pair in the topology, shaping
+    /// traffic according to `shaping`. Loss and jitter are drawn from the
+    /// topology's scenario seed.
+    pub(crate) async fn spawn(
+        descriptors: &GeneratedTopology,
+        shaping: LinkShaping,
+    ) -> io::Result<Self> {
+        let listen_ports = descriptors
+            .nodes()
+            .iter()
+            .map(|node| node.network_port())
+            .collect::<Vec<_>>();
+
+        let policy = Arc::new(LinkPolicy {
+            groups: RwLock::default(),
+            shaping,
+            rng: Mutex::new(descriptors.seed().rng(SHAPING_SEED_STREAM)),
+        });
+        let mut relay_ports = HashMap::new();
+        let mut tasks = Vec::new();
+
+        for node in descriptors.nodes() {
+            for addr in &node.general.network_config.backend.initial_peers {
+                let Some(port) = multiaddr_port(addr) else {
+                    continue;
+                };
+                let Some(peer) = listen_ports.iter().position(|listen| *listen == port) else {
+                    continue;
+                };
+                if relay_ports.contains_key(&(node.index(), port)) {
+                    continue;
+                }
+
+                let front = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+                let relay_port = front.local_addr()?.port();
+                let link = Link {
+                    from: node.index(),
+                    to: peer,
+                    target: SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+                };
+
+                debug!(
+                    from = link.from,
+                    to = link.to,
+                    relay_port,
+                    "spawning link relay"
+                );
+                tasks.push(tokio::spawn(run_link(front, link, Arc::clone(&policy))));
+                relay_ports.insert((node.index(), port), relay_port);
+            }
+        }
+
+        info!(links = relay_ports.len(), "local link relays ready");
+
+        Ok(Self {
+            policy,
+            relay_ports,
+            tasks,
+        })
+    }
+
+    /// Copy of `descriptors` whose initial peers dial through the relays.
+    pub(crate) fn relayed_topology(&self, descriptors: &GeneratedTopology) -> GeneratedTopology {
+        descriptors.with_initial_peers(|index, addr| {
+            multiaddr_port(addr)
+                .and_then(|port| self.relay_ports.get(&(index, port)))
+                .map_or_else(|| addr.clone(), |relay| replace_udp_port(addr, *relay))
+        })
+    }
+
+    /// Drop traffic between nodes assigned to different groups.
+    pub(crate) fn partition(&self, groups: &[Vec<usize>]) {
+        let mut assignment = self
+            .policy
+            .groups
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        assignment.clear();
+        for (group, nodes) in groups.iter().enumerate() {
+            for node in nodes {
+                assignment.insert(*node, group);
+            }
+        }
+        info!(groups = ?groups, "local link relays partitioned");
+    }
+
+    /// Let traffic flow on every link again.
+    pub(crate) fn heal(&self) {
+        self.policy
+            .groups
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+        info!("local link relays healed");
+    }
+}
+
+impl Drop for LinkRelays {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+async fn run_link(front: UdpSocket, link: Link, policy: Arc<LinkPolicy>) {
+    let front = Arc::new(front);
+    // One upstream socket per dialling address so replies find their way back.
+    let mut upstreams: HashMap<SocketAddr, Arc<UdpSocket>> = HashMap::new();
+    let mut reverse = JoinSet::new();
+    let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+
+    loop {
+        let (len, client) = match front.recv_from(&mut buf).await {
+            Ok(received) => received,
+            Err(err) => {
+                debug!(?link, error = %err, "link relay receive failed");
+                continue;
+            }
+        };
+
+        let verdict = policy.verdict(link.from, link.to);
+        if matches!(verdict, Verdict::Drop) {
+            continue;
+        }
+
+        let upstream = match upstreams.entry(client) {
+            Entry::Occupied(entry) => Arc::clone(entry.get()),
+            Entry::Vacant(entry) => {
+                let upstream = match open_upstream(link.target).await {
+                    Ok(socket) => Arc::new(socket),
+                    Err(err) => {
+                        debug!(?link, error = %err, "failed to open link relay upstream");
+                        continue;
+                    }
+                };
+                reverse.spawn(relay_back(
+                    Arc::clone(&upstream),
+                    Arc::clone(&front),
+                    client,
+                    link,
+                    Arc::clone(&policy),
+                ));
+                Arc::clone(entry.insert(upstream))
+            }
+        };
+
+        if let Verdict::Delay(delay) = verdict {
+            let datagram = buf[..len].to_vec();
+            tokio::spawn(async move {
+                sleep(delay).await;
+                if let Err(err) = upstream.send(&datagram).await {
+                    debug!(?link, error = %err, "link relay forward failed");
+                }
+            });
+        } else if let Err(err) = upstream.send(&buf[..len]).await {
+            debug!(?link, error = %err, "link relay forward failed");
+        }
+    }
+}
+
+async fn relay_back(
+    upstream: Arc<UdpSocket>,
+    front: Arc<UdpSocket>,
+    client: SocketAddr,
+    link: Link,
+    policy: Arc<LinkPolicy>,
+) {
+    let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+    loop {
+        // Errors here are usually ICMP "port unreachable" while the peer is
+        // restarting; keep the link alive.
+        let Ok(len) = upstream.recv(&mut buf).await else {
+            continue;
+        };
+
+        match policy.verdict(link.to, link.from) {
+            Verdict::Drop => {}
+            Verdict::Forward => {
+                if let Err(err) = front.send_to(&buf[..len], client).await {
+                    debug!(?link, error = %err, "link relay reply failed");
+                }
+            }
+            Verdict::Delay(delay) => {
+                let front = Arc::clone(&front);
+                let datagram = buf[..len].to_vec();
+                tokio::spawn(async move {
+                    sleep(delay).await;
+                    if let Err(err) = front.send_to(&datagram, client).await {
+                        debug!(?link, error = %err, "link relay reply failed");
+                    }
+                });
+            }
+        }
+    }
+}
+
+async fn open_upstream(target: SocketAddr) -> io::Result<UdpSocket> {
+    let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+    socket.connect(target).await?;
+    Ok(socket)
+}
+
+fn replace_udp_port(addr: &Multiaddr, port: u16) -> Multiaddr {
+    addr.iter()
+        .map(|protocol| match protocol {
+            Protocol::Udp(_) => Protocol::Udp(port),
+            other => other,
+        })
+        .collect()
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Instant,
+};
+
+use testing_framework_core::scenario::{DynError, NodeResourceUsage, ResourceUsage};
+
+/// Clock ticks per second used for CPU times in `/proc/<pid>/stat`
+/// (`USER_HZ`, 100 on every mainstream Linux build).
+const CLOCK_TICKS_PER_SEC: f64 = 100.0;
+
+/// A node process to sample.
+pub(crate) struct SampleTarget {
+    pub(crate) label: String,
+    pub(crate) pid: u32,
+    pub(crate) workdir: PathBuf,
+}
+
+/// Raw readings for one process; `None` where `/proc` had no answer.
+struct ProcReading {
+    label: String,
+    pid: u32,
+    cpu_ticks: Option<u64>,
+    rss_bytes: Option<u64>,
+    open_fds: Option<u64>,
+    disk_bytes: Option<u64>,
+}
+
+#[derive(Clone, Copy)]
+struct CpuReading {
+    pid: u32,
+    ticks: u64,
+    at: Instant,
+}
+
+/// Samples node processes through `/proc`. CPU usage is the change in process
+/// CPU time between two samples, so a process's first sample has none.
+#[derive(Default)]
+pub(crate) struct ProcSampler {
+    previous: Mutex<HashMap<String, CpuReading>>,
+}
+
+impl ProcSampler {
+    pub(crate) async fn sample(
+        &self,
+        targets: Vec<SampleTarget>,
+    ) -> Result<Vec<NodeResourceUsage>, DynError> {
+        let readings = tokio::task::spawn_blocking(move || {
+            targets.into_iter().map(read_proc).collect::<Vec<_>>()
+        })
+        .await?;
+
+        let now = Instant::now();
+        let mut previous = self
+            .previous
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Ok(readings
+            .into_iter()
+            .map(|reading| {
+                let cpu_percent = reading.cpu_ticks.and_then(|ticks| {
+                    let current = CpuReading {
+                        pid: reading.pid,
+                        ticks,
+                        at: now,
+                    };
+                    let last = previous.insert(reading.label.clone(), current)?;
+                    cpu_percent(last, current)
+                });
+                NodeResourceUsage {
+                    label: reading.label,
+                    usage: ResourceUsage {
+                        cpu_percent,
+                        rss_bytes: reading.rss_bytes,
+                        open_fds: reading.open_fds,
+                        disk_bytes: reading.disk_bytes,
+                    },
+                }
+            })
+            .collect())
+    }
+}
+
+fn cpu_percent(last: CpuReading, current: CpuReading) -> Option<f64> {
+    // A restarted node has a new process with its own CPU counter.
+    if last.pid != current.pid || current.ticks < last.ticks {
+        return None;
+    }
+    let wall = current.at.saturating_duration_since(last.at).as_secs_f64();
+    if wall <= 0.0 {
+        return None;
+    }
+    let cpu = (current.ticks - last.ticks) as f64 / CLOCK_TICKS_PER_SEC;
+    Some(cpu / wall * 100.0)
+}
+
+fn read_proc(target: SampleTarget) -> ProcReading {
+    let proc_dir = PathBuf::from(format!("/proc/{}", target.pid));
+    ProcReading {
+        cpu_ticks: fs::read_to_string(proc_dir.join("stat"))
+            .ok()
+            .and_then(|stat| parse_cpu_ticks(&stat)),
+        rss_bytes: fs::read_to_string(proc_dir.join("status"))
+            .ok()
+            .and_then(|status| parse_rss_bytes(&status)),
+        open_fds: fs::read_dir(proc_dir.join("fd"))
+            .ok()
+            .map(|entries| entries.count() as u64),
+        disk_bytes: Some(dir_size(&target.workdir)),
+        label: target.label,
+        pid: target.pid,
+    }
+}
+
+/// User plus system CPU ticks from `/proc/<pid>/stat`. The command name may
+/// contain spaces, so fields are counted from its closing parenthesis.
+fn parse_cpu_ticks(stat: &str) -> Option<u64> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    let mut fields = rest.split_whitespace().skip(11);
+    let utime = fields.next()?.parse::<u64>().ok()?;
+    let stime = fields.next()?.parse::<u64>().ok()?;
+    Some(utime + stime)
+}
+
+/// `VmRSS` from `/proc/<pid>/status`, which the kernel reports in kB.
+fn parse_rss_bytes(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kb = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kb * 1024)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
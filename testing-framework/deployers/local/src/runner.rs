@@ -4,11 +4,12 @@ use async_trait::async_trait;
 use testing_framework_core::{
     scenario::{
         BlockFeed, BlockFeedTask, Deployer, DynError, Metrics, NodeClients, NodeControlCapability,
-        RunContext, Runner, Scenario, ScenarioError, spawn_block_feed,
+        RunContext, Runner, Scenario, ScenarioError, ScenarioSeed, spawn_block_feed_with_nodes,
     },
     topology::{
         config::TopologyConfig,
         deployment::{SpawnTopologyError, Topology},
+        generation::GeneratedTopology,
         readiness::ReadinessError,
     },
 };
@@ -18,7 +19,12 @@ use tracing::{debug, info};
 use crate::{
     manual::{LocalManualCluster, ManualClusterError},
     node_control::{LocalDynamicNodes, LocalDynamicSeed},
+    nodes::LocalNodes,
+    relay::LinkRelays,
 };
+
+const BLOCK_FEED_SEED_STREAM: &str = "block_feed";
+
 /// Spawns nodes as local processes, reusing the existing
 /// integration harness.
 #[derive(Clone)]
@@ -34,6 +40,11 @@ pub enum LocalDeployerError {
         #[source]
         source: SpawnTopologyError,
     },
+    #[error("failed to start local link relays: {source}")]
+    Relay {
+        #[source]
+        source: std::io::Error,
+    },
     #[error("readiness probe failed: {source}")]
     ReadinessFailed {
         #[source]
@@ -49,6 +60,8 @@ pub enum LocalDeployerError {
         #[source]
         source: DynError,
     },
+    #[error("{message}")]
+    NodeExited { message: String },
 }
 
 impl From<ScenarioError> for LocalDeployerError {
@@ -58,6 +71,15 @@ impl From<ScenarioError> for LocalDeployerError {
             ScenarioError::ExpectationCapture(source) | ScenarioError::Expectations(source) => {
                 Self::ExpectationsFailed { source }
             }
+            ScenarioError::Monitor {
+                expectation,
+                source,
+            } => Self::ExpectationsFailed {
+                source: format!("{expectation}: {source}").into(),
+            },
+            ScenarioError::NodeExit(message) => Self::NodeExited {
+                message: format!("nodes exited unexpectedly:\n{message}"),
+            },
         }
     }
 }
@@ -74,19 +96,25 @@ impl Deployer<()> for LocalDeployer {
         let topology = Self::prepare_topology(scenario, self.membership_check).await?;
         let node_clients = NodeClients::from_topology(scenario.topology(), &topology);
 
-        let (block_feed, block_feed_guard) = spawn_block_feed_with(&node_clients).await?;
+        let (block_feed, block_feed_guard) =
+            spawn_block_feed_with(&node_clients, scenario.topology().seed()).await?;
+        // The processes move out of the topology so they can be supervised.
+        let nodes = Arc::new(LocalNodes::new(topology.into_nodes()));
 
         let context = RunContext::new(
             scenario.topology().clone(),
-            Some(topology),
+            None,
             node_clients,
             scenario.duration(),
             Metrics::empty(),
             block_feed,
             None,
-        );
+        )
+        .with_node_logs(nodes.clone())
+        .with_node_supervisor(nodes.clone())
+        .with_resource_sampler(nodes);
 
-        Ok(Runner::new(context, Some(Box::new(block_feed_guard))))
+        Ok(Runner::new(context, Some(Box::new(block_feed_guard))).with_deployer("local"))
     }
 }
 
@@ -103,27 +131,37 @@ impl Deployer<NodeControlCapability> for LocalDeployer {
             "starting local deployment with node control"
         );
 
-        let topology = Self::prepare_topology(scenario, self.membership_check).await?;
+        let (topology, relays) =
+            Self::prepare_relayed_topology(scenario, self.membership_check).await?;
         let node_clients = NodeClients::from_topology(scenario.topology(), &topology);
         let node_control = Arc::new(LocalDynamicNodes::new_with_seed(
             scenario.topology().clone(),
             node_clients.clone(),
             LocalDynamicSeed::from_topology(scenario.topology()),
         ));
+        // Node control owns the initial processes so it can restart them.
+        node_control.adopt_nodes(topology.into_nodes());
+        node_control.attach_relays(relays);
+        node_control.spawn_shaping_guard(scenario.link_shaping());
 
-        let (block_feed, block_feed_guard) = spawn_block_feed_with(&node_clients).await?;
+        let (block_feed, block_feed_guard) =
+            spawn_block_feed_with(&node_clients, scenario.topology().seed()).await?;
 
         let context = RunContext::new(
             scenario.topology().clone(),
-            Some(topology),
+            None,
             node_clients,
             scenario.duration(),
             Metrics::empty(),
             block_feed,
-            Some(node_control),
-        );
-
-        Ok(Runner::new(context, Some(Box::new(block_feed_guard))))
+            Some(node_control.clone()),
+        )
+        .with_link_shaping(scenario.link_shaping().clone())
+        .with_node_logs(node_control.clone())
+        .with_node_supervisor(node_control.clone())
+        .with_resource_sampler(node_control);
+
+        Ok(Runner::new(context, Some(Box::new(block_feed_guard))).with_deployer("local"))
     }
 }
 
@@ -154,15 +192,7 @@ impl LocalDeployer {
         scenario: &Scenario<Caps>,
         membership_check: bool,
     ) -> Result<Topology, LocalDeployerError> {
-        let descriptors = scenario.topology();
-
-        info!(nodes = descriptors.nodes().len(), "spawning local nodes");
-
-        let topology = descriptors
-            .clone()
-            .spawn_local()
-            .await
-            .map_err(|source| LocalDeployerError::Spawn { source })?;
+        let topology = spawn_topology(scenario.topology()).await?;
 
         if membership_check {
             wait_for_readiness(&topology).await.map_err(|source| {
@@ -177,6 +207,53 @@ impl LocalDeployer {
 
         Ok(topology)
     }
+
+    /// Spawn the topology with every initial peer link routed through a
+    /// [`LinkRelays`] instance, so node control can partition the network and
+    /// the scenario's link shaping is applied.
+    async fn prepare_relayed_topology(
+        scenario: &Scenario<NodeControlCapability>,
+        membership_check: bool,
+    ) -> Result<(Topology, LinkRelays), LocalDeployerError> {
+        let descriptors = scenario.topology();
+        let relays = LinkRelays::spawn(descriptors, scenario.link_shaping().clone())
+            .await
+            .map_err(|source| LocalDeployerError::Relay { source })?;
+
+        let topology = spawn_topology(&relays.relayed_topology(descriptors)).await?;
+
+        if membership_check {
+            // Relayed configs dial relay ports, so expected peers come from the
+            // original descriptors.
+            let endpoints = topology
+                .nodes()
+                .iter()
+                .map(|node| node.url())
+                .collect::<Vec<_>>();
+            descriptors
+                .wait_remote_readiness(&endpoints)
+                .await
+                .map_err(|source| {
+                    debug!(error = ?source, "local readiness failed");
+                    LocalDeployerError::ReadinessFailed { source }
+                })?;
+
+            info!("local nodes are ready");
+        } else {
+            info!("skipping local membership readiness checks");
+        }
+
+        Ok((topology, relays))
+    }
+}
+
+async fn spawn_topology(descriptors: &GeneratedTopology) -> Result<Topology, LocalDeployerError> {
+    info!(nodes = descriptors.nodes().len(), "spawning local nodes");
+
+    descriptors
+        .spawn_local()
+        .await
+        .map_err(|source| LocalDeployerError::Spawn { source })
 }
 
 impl Default for LocalDeployer {
@@ -196,13 +273,16 @@ async fn wait_for_readiness(topology: &Topology) -> Result<(), ReadinessError> {
 
 async fn spawn_block_feed_with(
     node_clients: &NodeClients,
+    seed: ScenarioSeed,
 ) -> Result<(BlockFeed, BlockFeedTask), LocalDeployerError> {
     debug!(
         nodes = node_clients.node_clients().len(),
         "selecting node client for local block feed"
     );
 
-    let Some(block_source_client) = node_clients.random_node() else {
+    let Some(block_source_client) =
+        node_clients.random_node_with(&mut seed.rng(BLOCK_FEED_SEED_STREAM))
+    else {
         return Err(LocalDeployerError::WorkloadFailed {
             source: "block feed requires at least one node".into(),
         });
@@ -210,7 +290,7 @@ async fn spawn_block_feed_with(
 
     info!("starting block feed");
 
-    spawn_block_feed(block_source_client)
+    spawn_block_feed_with_nodes(block_source_client, node_clients.clone())
         .await
         .map_err(workload_error)
 }
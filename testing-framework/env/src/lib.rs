@@ -42,6 +42,13 @@ pub fn nomos_tests_keep_logs() -> bool {
     env::var("LOGOS_BLOCKCHAIN_TESTS_KEEP_LOGS").is_ok()
 }
 
+#[must_use]
+pub fn nomos_tests_seed() -> Option<u64> {
+    env::var("LOGOS_BLOCKCHAIN_TESTS_SEED")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+}
+
 #[must_use]
 pub fn nomos_testnet_image() -> Option<String> {
     env::var("LOGOS_BLOCKCHAIN_TESTNET_IMAGE").ok()
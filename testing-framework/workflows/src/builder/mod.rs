@@ -4,19 +4,28 @@ use std::{
 };
 
 use testing_framework_core::{
-    scenario::{Builder as CoreScenarioBuilder, NodeControlCapability, ObservabilityCapability},
+    scenario::{
+        Builder as CoreScenarioBuilder, LinkConditions, NodeControlCapability,
+        ObservabilityCapability,
+    },
     topology::configs::wallet::WalletConfig,
 };
 
 use crate::{
-    expectations::ConsensusLiveness,
-    workloads::{chaos::RandomRestartWorkload, transaction},
+    expectations::{
+        ConsensusLiveness, ConsensusSafety, FinalityLag, LeaderFairness, LogScanExpectation,
+        MaxReorgDepth, PeerConnectivity, ResourceUsageExpectation, SlotOccupancy,
+        SupplyConservation,
+    },
+    workloads::{channel, chaos::RandomRestartWorkload, double_spend, invalid_tx, transaction},
 };
 
 #[derive(Debug, thiserror::Error)]
 pub enum BuilderInputError {
     #[error("{field} must be non-zero")]
     ZeroValue { field: &'static str },
+    #[error("{field} must be a positive number, got {value}")]
+    NotPositive { field: &'static str, value: f64 },
     #[error("invalid url for {field}: '{value}': {message}")]
     InvalidUrl {
         field: &'static str,
@@ -35,10 +44,70 @@ pub trait ScenarioBuilderExt<Caps>: Sized {
         self,
         f: impl FnOnce(TransactionFlowBuilder<Caps>) -> TransactionFlowBuilder<Caps>,
     ) -> CoreScenarioBuilder<Caps>;
+
+    #[must_use]
+    /// Write chains of `messages_per_channel` inscriptions to `channels`
+    /// channels and expect them to land in parent order (ignores zero).
+    fn channel_inscriptions(self, channels: usize, messages_per_channel: usize) -> Self;
+
+    #[must_use]
+    /// Submit conflicting spends of up to `pairs` wallets' genesis UTXOs to
+    /// different nodes and expect exactly one of each pair to be included.
+    fn double_spends(self, pairs: usize) -> Self;
+
+    #[must_use]
+    /// Submit every kind of invalid transaction `rounds` times and expect
+    /// none to be included while nodes keep producing blocks (ignores zero).
+    fn invalid_transactions(self, rounds: usize) -> Self;
+
     #[must_use]
     /// Attach a consensus liveness expectation.
     fn expect_consensus_liveness(self) -> Self;
 
+    #[must_use]
+    /// Attach a consensus safety expectation (nodes agree on the finalized
+    /// prefix).
+    fn expect_consensus_safety(self) -> Self;
+
+    #[must_use]
+    /// Fail the run as soon as any node reorgs deeper than `max_depth`
+    /// blocks.
+    fn expect_max_reorg_depth(self, max_depth: u64) -> Self;
+
+    #[must_use]
+    /// Replay the ledger from the genesis notes and expect the token supply
+    /// to be conserved.
+    fn expect_supply_conservation(self) -> Self;
+
+    #[must_use]
+    /// Expect the fraction of occupied slots to match `active_slot_coeff`
+    /// within `tolerance` (relative, e.g. `0.25` for ±25%).
+    fn expect_slot_occupancy(self, tolerance: f64) -> Self;
+
+    #[must_use]
+    /// Expect block production to be spread across nodes in proportion to
+    /// their leader stake.
+    fn expect_leader_fairness(self) -> Self;
+
+    #[must_use]
+    /// Sample every node's tip-to-LIB gap during the run and fail on gaps
+    /// beyond twice `security_param` or on LIB stalls.
+    fn expect_finality_lag(self) -> Self;
+
+    #[must_use]
+    /// Sample peer counts during the run and expect every node to keep (or
+    /// regain) the peers its layout implies.
+    fn expect_peer_connectivity(self) -> Self;
+
+    #[must_use]
+    /// Scan every node's logs after the run and expect no panics.
+    fn expect_clean_logs(self) -> Self;
+
+    #[must_use]
+    /// Sample node resource usage and fail if any node's resident memory
+    /// grows faster than `mb_per_hour` MiB per hour after warmup.
+    fn expect_bounded_memory_growth(self, mb_per_hour: f64) -> Self;
+
     #[must_use]
     /// Seed deterministic wallets with total funds split across `users`.
     fn initialize_wallet(self, total_funds: u64, users: usize) -> Self;
@@ -56,10 +125,82 @@ impl<Caps> ScenarioBuilderExt<Caps> for CoreScenarioBuilder<Caps> {
         f(self.transactions()).apply()
     }
 
+    fn channel_inscriptions(self, channels: usize, messages_per_channel: usize) -> Self {
+        let (Some(channels), Some(messages)) = (
+            NonZeroUsize::new(channels),
+            NonZeroUsize::new(messages_per_channel),
+        ) else {
+            tracing::warn!(
+                channels,
+                messages_per_channel,
+                "channel counts must be non-zero; ignoring channel_inscriptions"
+            );
+            return self;
+        };
+        self.with_workload(channel::Workload::new(channels).with_messages_per_channel(messages))
+    }
+
+    fn double_spends(self, pairs: usize) -> Self {
+        let Some(pairs) = NonZeroUsize::new(pairs) else {
+            tracing::warn!(pairs, "double spend pair count must be non-zero; ignoring");
+            return self;
+        };
+        self.with_workload(double_spend::Workload::new().with_pair_limit(Some(pairs)))
+    }
+
+    fn invalid_transactions(self, rounds: usize) -> Self {
+        let Some(rounds) = NonZeroUsize::new(rounds) else {
+            tracing::warn!(
+                rounds,
+                "invalid transaction rounds must be non-zero; ignoring"
+            );
+            return self;
+        };
+        self.with_workload(invalid_tx::Workload::new().with_rounds(rounds))
+    }
+
     fn expect_consensus_liveness(self) -> Self {
         self.with_expectation(ConsensusLiveness::default())
     }
 
+    fn expect_consensus_safety(self) -> Self {
+        self.with_expectation(ConsensusSafety::default())
+    }
+
+    fn expect_max_reorg_depth(self, max_depth: u64) -> Self {
+        self.with_expectation(MaxReorgDepth::new(max_depth))
+    }
+
+    fn expect_supply_conservation(self) -> Self {
+        self.with_expectation(SupplyConservation::new())
+    }
+
+    fn expect_slot_occupancy(self, tolerance: f64) -> Self {
+        self.with_expectation(SlotOccupancy::new().with_tolerance(tolerance))
+    }
+
+    fn expect_leader_fairness(self) -> Self {
+        self.with_expectation(LeaderFairness::new())
+    }
+
+    fn expect_finality_lag(self) -> Self {
+        self.with_expectation(FinalityLag::new())
+    }
+
+    fn expect_peer_connectivity(self) -> Self {
+        self.with_expectation(PeerConnectivity::new())
+    }
+
+    fn expect_clean_logs(self) -> Self {
+        self.with_expectation(LogScanExpectation::new())
+    }
+
+    fn expect_bounded_memory_growth(self, mb_per_hour: f64) -> Self {
+        self.with_expectation(
+            ResourceUsageExpectation::new().with_max_rss_growth_mb_per_hour(mb_per_hour),
+        )
+    }
+
     fn initialize_wallet(self, total_funds: u64, users: usize) -> Self {
         let Some(user_count) = NonZeroUsize::new(users) else {
             tracing::warn!(
@@ -393,6 +534,13 @@ pub struct TransactionFlowBuilder<Caps> {
     builder: CoreScenarioBuilder<Caps>,
     rate: NonZeroU64,
     users: Option<NonZeroUsize>,
+    sustained: bool,
+    tps: Option<f64>,
+    arrivals: transaction::Arrivals,
+    load_duration: Option<Duration>,
+    max_in_flight: Option<NonZeroUsize>,
+    inclusion_latency: Vec<(transaction::Percentile, transaction::LatencyLimit)>,
+    finality_latency: Vec<(transaction::Percentile, transaction::LatencyLimit)>,
 }
 
 impl<Caps> TransactionFlowBuilder<Caps> {
@@ -405,6 +553,13 @@ impl<Caps> TransactionFlowBuilder<Caps> {
             builder,
             rate: Self::default_rate(),
             users: None,
+            sustained: false,
+            tps: None,
+            arrivals: transaction::Arrivals::Constant,
+            load_duration: None,
+            max_in_flight: None,
+            inclusion_latency: Vec::new(),
+            finality_latency: Vec::new(),
         }
     }
 
@@ -462,18 +617,143 @@ impl<Caps> TransactionFlowBuilder<Caps> {
         Ok(self)
     }
 
+    #[must_use]
+    /// Keep submitting for the whole run by re-spending each wallet's change
+    /// outputs instead of spending every genesis UTXO once.
+    pub const fn sustained(mut self) -> Self {
+        self.sustained = true;
+        self
+    }
+
+    #[must_use]
+    /// Switch to an open-loop generator submitting `tps` transactions per
+    /// second regardless of inclusion (ignores non-positive rates). Overrides
+    /// `rate`, `users` and `sustained`.
+    pub fn tps(mut self, tps: f64) -> Self {
+        if tps.is_finite() && tps > 0.0 {
+            self.tps = Some(tps);
+        } else {
+            tracing::warn!(
+                tps,
+                "transaction tps must be positive; keeping previous setting"
+            );
+        }
+        self
+    }
+
+    /// Like `tps`, but returns an error instead of ignoring the rate.
+    pub fn try_tps(mut self, tps: f64) -> Result<Self, BuilderInputError> {
+        if !(tps.is_finite() && tps > 0.0) {
+            return Err(BuilderInputError::NotPositive {
+                field: "transaction_tps",
+                value: tps,
+            });
+        }
+        self.tps = Some(tps);
+        Ok(self)
+    }
+
+    #[must_use]
+    /// Arrival pattern for the open-loop generator.
+    pub const fn arrivals(mut self, arrivals: transaction::Arrivals) -> Self {
+        self.arrivals = arrivals;
+        self
+    }
+
+    #[must_use]
+    /// Stop the open-loop generator after `duration` instead of at the end of
+    /// the run.
+    pub const fn load_duration(mut self, duration: Duration) -> Self {
+        self.load_duration = Some(duration);
+        self
+    }
+
+    #[must_use]
+    /// Limit how many open-loop submissions may be outstanding at once
+    /// (ignores zero).
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        match NonZeroUsize::new(max_in_flight) {
+            Some(value) => self.max_in_flight = Some(value),
+            None => tracing::warn!(
+                max_in_flight,
+                "open-loop in-flight limit must be non-zero; keeping previous setting"
+            ),
+        }
+        self
+    }
+
+    #[must_use]
+    /// Require submit-to-inclusion latency at `percentile` to stay below
+    /// `limit`. Only checked by the default per-block workload.
+    pub fn max_inclusion_latency(
+        mut self,
+        percentile: transaction::Percentile,
+        limit: transaction::LatencyLimit,
+    ) -> Self {
+        self.inclusion_latency.push((percentile, limit));
+        self
+    }
+
+    #[must_use]
+    /// Require submit-to-finality latency at `percentile` to stay below
+    /// `limit`. Only checked by the default per-block workload.
+    pub fn max_finality_latency(
+        mut self,
+        percentile: transaction::Percentile,
+        limit: transaction::LatencyLimit,
+    ) -> Self {
+        self.finality_latency.push((percentile, limit));
+        self
+    }
+
     #[must_use]
     /// Attach the transaction workload to the scenario.
     pub fn apply(mut self) -> CoreScenarioBuilder<Caps> {
-        let workload = transaction::Workload::new(self.rate).with_user_limit(self.users);
+        if let Some(workload) = self.tps.and_then(transaction::OpenLoopWorkload::new) {
+            tracing::info!(
+                tps = workload.tps(),
+                arrivals = ?self.arrivals,
+                duration_secs = self.load_duration.map(|d| d.as_secs_f64()),
+                max_in_flight = self.max_in_flight.map(NonZeroUsize::get),
+                "attaching open-loop transaction workload"
+            );
+            let mut workload = workload.with_arrivals(self.arrivals);
+            if let Some(duration) = self.load_duration {
+                workload = workload.with_duration(duration);
+            }
+            if let Some(max_in_flight) = self.max_in_flight {
+                workload = workload.with_max_in_flight(max_in_flight);
+            }
+            return self.builder.with_workload(workload);
+        }
 
         tracing::info!(
             rate = self.rate.get(),
             users = self.users.map(|u| u.get()),
+            sustained = self.sustained,
             "attaching transaction workload"
         );
 
-        self.builder = self.builder.with_workload(workload);
+        self.builder = if self.sustained {
+            self.builder.with_workload(
+                transaction::SustainedWorkload::new(self.rate).with_user_limit(self.users),
+            )
+        } else {
+            let workload = transaction::Workload::new(self.rate).with_user_limit(self.users);
+            let workload =
+                self.inclusion_latency
+                    .iter()
+                    .fold(workload, |workload, (percentile, limit)| {
+                        workload.with_inclusion_latency(*percentile, *limit)
+                    });
+            let workload = self
+                .finality_latency
+                .iter()
+                .fold(workload, |workload, (percentile, limit)| {
+                    workload.with_finality_latency(*percentile, *limit)
+                });
+            self.builder.with_workload(workload)
+        };
         self.builder
     }
 }
@@ -506,7 +786,7 @@ impl ChaosBuilderExt for CoreScenarioBuilder<NodeControlCapability> {
 /// Chaos workload builder root.
 ///
 /// Start with `chaos()` on a scenario builder, then select a workload variant
-/// such as `restart()`.
+/// such as `restart()`, `partition()` or `link_shaping()`.
 pub struct ChaosBuilder {
     builder: CoreScenarioBuilder<NodeControlCapability>,
 }
@@ -533,6 +813,31 @@ impl ChaosBuilder {
             include_nodes: true,
         }
     }
+
+    /// Configure a network partition chaos workload.
+    #[must_use]
+    pub fn partition(self) -> ChaosPartitionBuilder {
+        const DEFAULT_PARTITION_GROUPS: usize = 2;
+        const DEFAULT_PARTITION_DELAY: Duration = Duration::from_secs(30);
+        const DEFAULT_PARTITION_WINDOW: Duration = Duration::from_secs(60);
+
+        ChaosPartitionBuilder {
+            builder: self.builder,
+            groups: DEFAULT_PARTITION_GROUPS,
+            delay: DEFAULT_PARTITION_DELAY,
+            window: DEFAULT_PARTITION_WINDOW,
+        }
+    }
+
+    /// Configure latency and packet loss between nodes.
+    #[must_use]
+    pub fn link_shaping(self) -> ChaosLinkShapingBuilder {
+        ChaosLinkShapingBuilder {
+            builder: self.builder,
+            default: None,
+            links: Vec::new(),
+        }
+    }
 }
 
 pub struct ChaosRestartBuilder {
@@ -620,3 +925,113 @@ impl ChaosRestartBuilder {
         self.builder
     }
 }
+
+pub struct ChaosPartitionBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    groups: usize,
+    delay: Duration,
+    window: Duration,
+}
+
+impl ChaosPartitionBuilder {
+    #[must_use]
+    /// Number of isolated groups to split the nodes into.
+    pub fn groups(mut self, groups: usize) -> Self {
+        if groups < 2 {
+            tracing::warn!(
+                groups,
+                "chaos partition needs at least two groups; keeping previous value"
+            );
+        } else {
+            self.groups = groups;
+        }
+        self
+    }
+
+    #[must_use]
+    /// Time to wait after the run starts before splitting the network.
+    pub const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    #[must_use]
+    /// How long the partition lasts before it is healed.
+    pub fn window(mut self, window: Duration) -> Self {
+        if window.is_zero() {
+            tracing::warn!("chaos partition window must be non-zero; keeping previous value");
+        } else {
+            self.window = window;
+        }
+        self
+    }
+
+    #[must_use]
+    /// Finalize the partition workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        let workload = NetworkPartitionWorkload::new(self.groups, self.delay, self.window);
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
+
+pub struct ChaosLinkShapingBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    default: Option<LinkConditions>,
+    links: Vec<(usize, usize, LinkConditions)>,
+}
+
+impl ChaosLinkShapingBuilder {
+    #[must_use]
+    /// Conditions applied to every node-to-node link without an override.
+    pub fn default_conditions(mut self, conditions: LinkConditions) -> Self {
+        self.default = Some(sanitize_conditions(conditions));
+        self
+    }
+
+    #[must_use]
+    /// Conditions for traffic sent from node `from` to node `to`.
+    pub fn link(mut self, from: usize, to: usize, conditions: LinkConditions) -> Self {
+        if from == to {
+            tracing::warn!(node = from, "link shaping ignores self links");
+        } else {
+            self.links.push((from, to, sanitize_conditions(conditions)));
+        }
+        self
+    }
+
+    #[must_use]
+    /// Store the conditions on the scenario; they can still be changed
+    /// mid-run through `RunContext::link_shaping`.
+    pub fn apply(self) -> CoreScenarioBuilder<NodeControlCapability> {
+        let Self {
+            builder,
+            default,
+            links,
+        } = self;
+        builder.link_shaping().update(|config| {
+            if default.is_some() {
+                config.default = default;
+            }
+            for (from, to, conditions) in links {
+                config.links.insert((from, to), conditions);
+            }
+        });
+        builder
+    }
+}
+
+fn sanitize_conditions(mut conditions: LinkConditions) -> LinkConditions {
+    if !(0.0..=1.0).contains(&conditions.loss) {
+        tracing::warn!(
+            loss = conditions.loss,
+            "link shaping loss must be within 0.0..=1.0; clamping"
+        );
+        conditions.loss = if conditions.loss.is_nan() {
+            0.0
+        } else {
+            conditions.loss.clamp(0.0, 1.0)
+        };
+    }
+    conditions
+}
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use nomos_core::header::HeaderId;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, Expectation, RunContext},
+};
+use thiserror::Error;
+use tokio::time::sleep;
+
+const REQUEST_RETRIES: usize = 5;
+const REQUEST_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Checks that no two nodes have finalized conflicting blocks: every node's
+/// LIB-to-genesis chain must be a prefix of (or extend) every other node's.
+pub struct ConsensusSafety {
+    genesis: Option<HeaderId>,
+}
+
+#[derive(Debug, Error)]
+enum ConsensusSafetyIssue {
+    #[error(
+        "node-{left} and node-{right} finalized different blocks at height {height} \
+         ({left_header:?} vs {right_header:?}); last common block: {common:?}"
+    )]
+    Conflict {
+        left: usize,
+        right: usize,
+        height: usize,
+        left_header: HeaderId,
+        right_header: HeaderId,
+        common: Option<HeaderId>,
+    },
+    #[error("node-{node} finalized chain request failed: {source}")]
+    RequestFailed {
+        node: usize,
+        #[source]
+        source: DynError,
+    },
+}
+
+#[derive(Debug, Error)]
+enum ConsensusSafetyError {
+    #[error("consensus safety requires at least one node")]
+    MissingParticipants,
+    #[error("could not locate the genesis block")]
+    MissingGenesis,
+    #[error("consensus safety violated:\n{message}")]
+    Violations { message: String },
+}
+
+#[async_trait]
+impl Expectation for ConsensusSafety {
+    fn name(&self) -> &'static str {
+        "consensus_safety"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        // The chain is still short here, so walking back to genesis is cheap.
+        if let Some(client) = ctx.random_node_client(&mut ctx.rng(self.name())) {
+            match find_genesis(&client).await {
+                Ok(genesis) => self.genesis = Some(genesis),
+                Err(err) => tracing::debug!(%err, "consensus safety: genesis lookup deferred"),
+            }
+        }
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let clients = ctx.node_clients().all_clients();
+        if clients.is_empty() {
+            return Err(Box::new(ConsensusSafetyError::MissingParticipants));
+        }
+
+        let genesis = match self.genesis {
+            Some(genesis) => genesis,
+            None => {
+                let genesis = find_genesis(&clients[0])
+                    .await
+                    .map_err(|_| ConsensusSafetyError::MissingGenesis)?;
+                self.genesis = Some(genesis);
+                genesis
+            }
+        };
+
+        let mut chains = Vec::with_capacity(clients.len());
+        let mut issues = Vec::new();
+        for (node, client) in clients.iter().enumerate() {
+            match fetch_finalized_chain(client, genesis).await {
+                Ok(chain) => {
+                    tracing::debug!(node, finalized = chain.len(), "finalized chain collected");
+                    chains.push((node, chain));
+                }
+                Err(source) => issues.push(ConsensusSafetyIssue::RequestFailed { node, source }),
+            }
+        }
+
+        issues.extend(find_conflicts(&chains));
+
+        if issues.is_empty() {
+            let finalized = chains
+                .iter()
+                .map(|(_, chain)| chain.len())
+                .collect::<Vec<_>>();
+            tracing::info!(
+                nodes = chains.len(),
+                finalized = ?finalized,
+                "consensus safety expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        for issue in &issues {
+            tracing::warn!(?issue, "consensus safety issue");
+        }
+        let message = issues
+            .iter()
+            .map(|issue| format!("- {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(Box::new(ConsensusSafetyError::Violations { message }))
+    }
+}
+
+/// Finalized headers of one node, ordered from genesis to its LIB.
+async fn fetch_finalized_chain(
+    client: &ApiClient,
+    genesis: HeaderId,
+) -> Result<Vec<HeaderId>, DynError> {
+    let mut last_err: Option<DynError> = None;
+    for attempt in 0..REQUEST_RETRIES {
+        let result = async {
+            let info = client.consensus_info().await?;
+            client
+                .consensus_headers(Some(info.lib), Some(genesis))
+                .await
+        }
+        .await;
+
+        match result {
+            Ok(mut headers) => {
+                headers.reverse();
+                return Ok(headers);
+            }
+            Err(err) => {
+                tracing::debug!(attempt, %err, "finalized chain request failed");
+                last_err = Some(err.into());
+                sleep(REQUEST_RETRY_DELAY).await;
+            }
+        }
+    }
+    Err(last_err.unwrap_or_else(|| "finalized chain request failed".into()))
+}
+
+/// Walk parents from the node's tip until reaching the block built on the
+/// zero header.
+pub(crate) async fn find_genesis(client: &ApiClient) -> Result<HeaderId, DynError> {
+    let genesis_parent = HeaderId::from([0; 32]);
+    let mut cursor = client.consensus_info().await?.tip;
+    loop {
+        let block = client
+            .storage_block(&cursor)
+            .await?
+            .ok_or("missing block while looking for genesis")?;
+        let parent = block.header().parent();
+        if parent == genesis_parent || parent == cursor {
+            return Ok(cursor);
+        }
+        cursor = parent;
+    }
+}
+
+/// Pairwise comparison of finalized chains; reports the first height at which
+/// each pair disagrees.
+fn find_conflicts(chains: &[(usize, Vec<HeaderId>)]) -> Vec<ConsensusSafetyIssue> {
+    let mut issues = Vec::new();
+    for (position, (left, left_chain)) in chains.iter().enumerate() {
+        for (right, right_chain) in &chains[position + 1..] {
+            let divergence = left_chain
+                .iter()
+                .zip(right_chain)
+                .position(|(left_header, right_header)| left_header != right_header);
+
+            if let Some(height) = divergence {
+                issues.push(ConsensusSafetyIssue::Conflict {
+                    left: *left,
+                    right: *right,
+                    height,
+                    left_header: left_chain[height],
+                    right_header: right_chain[height],
+                    common: height.checked_sub(1).map(|common| left_chain[common]),
+                });
+            }
+        }
+    }
+    issues
+}
@@ -0,0 +1,227 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use nomos_core::header::HeaderId;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::time::Instant;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+/// Default gap bound, as a multiple of `security_param`.
+const GAP_FACTOR: u64 = 2;
+/// Default LIB stall bound, as a multiple of the slots expected to produce
+/// `security_param` blocks.
+const STALL_FACTOR: f64 = 3.0;
+
+#[derive(Clone, Debug, Default)]
+/// Samples every node's tip-to-LIB gap throughout the run and fails as soon
+/// as a gap exceeds its bound or a node's LIB stops advancing for too long.
+pub struct FinalityLag {
+    max_gap: Option<u64>,
+    max_stall_slots: Option<u64>,
+    limits: Option<Limits>,
+    nodes: Vec<NodeLag>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Limits {
+    max_gap: u64,
+    max_stall_slots: u64,
+    max_stall: Duration,
+}
+
+#[derive(Clone, Debug, Default)]
+struct NodeLag {
+    samples: usize,
+    last_gap: u64,
+    max_gap: u64,
+    /// Current LIB and when it was first seen. Cleared when a sample fails,
+    /// so time a node spent unreachable (e.g. stopped by node control) is not
+    /// counted as a stall.
+    lib: Option<(HeaderId, Instant)>,
+    longest_stall: Duration,
+}
+
+#[derive(Debug, Error)]
+enum FinalityLagError {
+    #[error("finality lag expectation not captured")]
+    NotCaptured,
+    #[error("finality lag needs a slot duration to bound LIB stalls")]
+    UnknownSlotDuration,
+    #[error("node-{node} tip is {gap} blocks ahead of its LIB (max {max_gap})")]
+    GapExceeded { node: usize, gap: u64, max_gap: u64 },
+    #[error(
+        "node-{node} LIB {lib:?} has not advanced for {stalled:?} (max {max_stall_slots} slots, {max_stall:?})"
+    )]
+    LibStalled {
+        node: usize,
+        lib: HeaderId,
+        stalled: Duration,
+        max_stall_slots: u64,
+        max_stall: Duration,
+    },
+    #[error("no finality lag samples were collected")]
+    NoSamples,
+}
+
+impl FinalityLag {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Largest allowed tip-to-LIB gap in blocks; defaults to twice the
+    /// `security_param`.
+    pub const fn with_max_gap(mut self, blocks: u64) -> Self {
+        self.max_gap = Some(blocks);
+        self
+    }
+
+    #[must_use]
+    /// Longest a node's LIB may stay unchanged, in slots; defaults to three
+    /// times the slots expected to produce `security_param` blocks.
+    pub const fn with_max_lib_stall(mut self, slots: u64) -> Self {
+        self.max_stall_slots = Some(slots);
+        self
+    }
+
+    async fn sample(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let limits = self.limits.ok_or(FinalityLagError::NotCaptured)?;
+        let clients = ctx.node_clients().all_clients();
+        if self.nodes.len() < clients.len() {
+            self.nodes.resize_with(clients.len(), NodeLag::default);
+        }
+
+        for (node, client) in clients.iter().enumerate() {
+            let info = match client.consensus_info().await {
+                Ok(info) => info,
+                Err(err) => {
+                    tracing::debug!(node, %err, "finality lag consensus info failed");
+                    self.nodes[node].lib = None;
+                    continue;
+                }
+            };
+            // Headers run from the tip back to the LIB, both included.
+            let gap = match client
+                .consensus_headers(Some(info.tip), Some(info.lib))
+                .await
+            {
+                Ok(headers) => headers.len().saturating_sub(1) as u64,
+                Err(err) => {
+                    tracing::debug!(node, %err, "finality lag headers request failed");
+                    self.nodes[node].lib = None;
+                    continue;
+                }
+            };
+
+            let now = Instant::now();
+            let lag = &mut self.nodes[node];
+            lag.samples += 1;
+            lag.last_gap = gap;
+            lag.max_gap = lag.max_gap.max(gap);
+            let since = match lag.lib {
+                Some((lib, since)) if lib == info.lib => since,
+                _ => {
+                    lag.lib = Some((info.lib, now));
+                    now
+                }
+            };
+            let stalled = now.saturating_duration_since(since);
+            lag.longest_stall = lag.longest_stall.max(stalled);
+
+            if gap > limits.max_gap {
+                return Err(Box::new(FinalityLagError::GapExceeded {
+                    node,
+                    gap,
+                    max_gap: limits.max_gap,
+                }));
+            }
+            if stalled > limits.max_stall {
+                return Err(Box::new(FinalityLagError::LibStalled {
+                    node,
+                    lib: info.lib,
+                    stalled,
+                    max_stall_slots: limits.max_stall_slots,
+                    max_stall: limits.max_stall,
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Expectation for FinalityLag {
+    fn name(&self) -> &'static str {
+        "finality_lag"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let params = &ctx.descriptors().config().consensus_params;
+        let security_param = u64::from(params.security_param.get());
+        let slot_duration = ctx
+            .descriptors()
+            .slot_duration()
+            .ok_or(FinalityLagError::UnknownSlotDuration)?;
+
+        let max_stall_slots = self.max_stall_slots.unwrap_or_else(|| {
+            let coeff = params.active_slot_coeff.clamp(f64::EPSILON, 1.0);
+            (STALL_FACTOR * security_param as f64 / coeff).ceil() as u64
+        });
+        let limits = Limits {
+            max_gap: self.max_gap.unwrap_or(GAP_FACTOR * security_param),
+            max_stall_slots,
+            max_stall: slot_duration
+                .saturating_mul(u32::try_from(max_stall_slots).unwrap_or(u32::MAX)),
+        };
+        tracing::info!(?limits, "finality lag expectation starting capture");
+        self.limits = Some(limits);
+        self.nodes.clear();
+        Ok(())
+    }
+
+    fn monitor_interval(&self) -> Option<Duration> {
+        Some(SAMPLE_INTERVAL)
+    }
+
+    async fn monitor(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.sample(ctx).await
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.sample(ctx).await?;
+        if self.nodes.iter().all(|lag| lag.samples == 0) {
+            return Err(Box::new(FinalityLagError::NoSamples));
+        }
+        tracing::info!(
+            max_gap = self.nodes.iter().map(|lag| lag.max_gap).max(),
+            longest_stall = ?self.nodes.iter().map(|lag| lag.longest_stall).max(),
+            "finality lag expectation satisfied"
+        );
+        Ok(())
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        let limits = self.limits?;
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(node, lag)| {
+                serde_json::json!({
+                    "node": node,
+                    "samples": lag.samples,
+                    "last_gap": lag.last_gap,
+                    "max_gap": lag.max_gap,
+                    "longest_lib_stall_secs": lag.longest_stall.as_secs_f64(),
+                })
+            })
+            .collect::<Vec<_>>();
+        Some(serde_json::json!({
+            "max_gap": limits.max_gap,
+            "max_lib_stall_slots": limits.max_stall_slots,
+            "nodes": nodes,
+        }))
+    }
+}
@@ -0,0 +1,459 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, Weak},
+};
+
+use async_trait::async_trait;
+use key_management_system_service::keys::ZkPublicKey;
+use nomos_core::header::HeaderId;
+use testing_framework_core::{
+    scenario::{BlockRecord, DynError, Expectation, RunContext},
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+/// One-sided z-score for the goodness-of-fit and per-node checks (p ≈ 0.001).
+const DEFAULT_Z: f64 = 3.09;
+/// Below this many expected blocks a node producing none is not suspicious.
+const MIN_EXPECTED_BLOCKS: f64 = 5.0;
+
+#[derive(Clone, Debug)]
+/// Checks that block production is spread across nodes in proportion to
+/// their leader stake.
+///
+/// Each block is attributed to the node whose leader key signed its proof of
+/// leadership. Blocks whose key matches no node stay unattributed.
+pub struct LeaderFairness {
+    z: f64,
+    attribution: Option<Arc<Mutex<Attribution>>>,
+    result: Option<FairnessResult>,
+}
+
+#[derive(Debug, Default)]
+struct Attribution {
+    /// Node whose leader key is in each header's proof.
+    producers: HashMap<HeaderId, usize>,
+    /// Headers broadcast by the block feed.
+    observed: HashSet<HeaderId>,
+}
+
+#[derive(Clone, Debug)]
+struct FairnessResult {
+    nodes: Vec<NodeShare>,
+    attributed: usize,
+    unattributed: usize,
+    chi_square: f64,
+    critical: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct NodeShare {
+    stake: u64,
+    blocks: usize,
+    expected: f64,
+}
+
+#[derive(Debug, Error)]
+enum FairnessIssue {
+    #[error("node-{node} produced no blocks (expected {expected:.1})")]
+    Silent { node: usize, expected: f64 },
+    #[error("node-{node} produced {blocks} blocks (expected {expected:.1}, max {max:.1})")]
+    Excess {
+        node: usize,
+        blocks: usize,
+        expected: f64,
+        max: f64,
+    },
+    #[error("chi-square {chi_square:.2} exceeds critical value {critical:.2}")]
+    GoodnessOfFit { chi_square: f64, critical: f64 },
+}
+
+#[derive(Debug, Error)]
+enum LeaderFairnessError {
+    #[error("leader fairness expectation not captured")]
+    NotCaptured,
+    #[error("leader fairness needs at least two nodes with leader stake")]
+    NotEnoughLeaders,
+    #[error("no block could be attributed to a producer")]
+    NoAttributedBlocks,
+    #[error("leader distribution is unfair:\n{message}")]
+    Violations { message: String },
+}
+
+impl LeaderFairness {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            z: DEFAULT_Z,
+            attribution: None,
+            result: None,
+        }
+    }
+
+    #[must_use]
+    /// z-score used for the per-node and chi-square bounds; higher is more
+    /// lenient.
+    pub const fn with_z(mut self, z: f64) -> Self {
+        self.z = z;
+        self
+    }
+
+    fn fairness(&self, stakes: &[u64], attribution: &Attribution) -> FairnessResult {
+        let mut blocks = vec![0usize; stakes.len()];
+        let mut unattributed = 0;
+        for header in &attribution.observed {
+            match attribution.producers.get(header) {
+                Some(node) if *node < blocks.len() => blocks[*node] += 1,
+                _ => unattributed += 1,
+            }
+        }
+
+        let attributed = blocks.iter().sum::<usize>();
+        let total_stake = stakes.iter().sum::<u64>().max(1) as f64;
+        let nodes = stakes
+            .iter()
+            .zip(&blocks)
+            .map(|(stake, blocks)| NodeShare {
+                stake: *stake,
+                blocks: *blocks,
+                expected: attributed as f64 * *stake as f64 / total_stake,
+            })
+            .collect::<Vec<_>>();
+
+        let chi_square = nodes
+            .iter()
+            .filter(|node| node.expected > 0.0)
+            .map(|node| (node.blocks as f64 - node.expected).powi(2) / node.expected)
+            .sum();
+        let dof = nodes
+            .iter()
+            .filter(|node| node.stake > 0)
+            .count()
+            .saturating_sub(1);
+
+        FairnessResult {
+            nodes,
+            attributed,
+            unattributed,
+            chi_square,
+            critical: chi_square_critical(dof, self.z),
+        }
+    }
+
+    fn issues(&self, result: &FairnessResult) -> Vec<FairnessIssue> {
+        let mut issues = Vec::new();
+        let total = result.attributed as f64;
+        for (node, share) in result.nodes.iter().enumerate() {
+            if share.stake == 0 {
+                continue;
+            }
+            if share.blocks == 0 && share.expected >= MIN_EXPECTED_BLOCKS {
+                issues.push(FairnessIssue::Silent {
+                    node,
+                    expected: share.expected,
+                });
+            }
+            let p = share.expected / total;
+            let max = share.expected + self.z * (total * p * (1.0 - p)).sqrt();
+            if share.blocks as f64 > max.max(1.0) {
+                issues.push(FairnessIssue::Excess {
+                    node,
+                    blocks: share.blocks,
+                    expected: share.expected,
+                    max,
+                });
+            }
+        }
+        if result.chi_square > result.critical {
+            issues.push(FairnessIssue::GoodnessOfFit {
+                chi_square: result.chi_square,
+                critical: result.critical,
+            });
+        }
+        issues
+    }
+}
+
+impl Default for LeaderFairness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Expectation for LeaderFairness {
+    fn name(&self) -> &'static str {
+        "leader_fairness"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.attribution.is_some() {
+            return Ok(());
+        }
+
+        let attribution = Arc::new(Mutex::new(Attribution::default()));
+        spawn_feed_capture(
+            ctx.block_feed().subscribe(),
+            leader_keys(ctx.descriptors()),
+            Arc::downgrade(&attribution),
+        );
+        self.attribution = Some(attribution);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let Some(attribution) = self.attribution.as_ref() else {
+            return Err(Box::new(LeaderFairnessError::NotCaptured));
+        };
+        let stakes = leader_stakes(ctx.descriptors());
+        if stakes.iter().filter(|stake| **stake > 0).count() < 2 {
+            return Err(Box::new(LeaderFairnessError::NotEnoughLeaders));
+        }
+
+        let result = {
+            let attribution = attribution
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            self.fairness(&stakes, &attribution)
+        };
+        self.result = Some(result.clone());
+        if result.attributed == 0 {
+            return Err(Box::new(LeaderFairnessError::NoAttributedBlocks));
+        }
+
+        let issues = self.issues(&result);
+        if issues.is_empty() {
+            tracing::info!(
+                attributed = result.attributed,
+                unattributed = result.unattributed,
+                chi_square = result.chi_square,
+                critical = result.critical,
+                "leader fairness expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        for issue in &issues {
+            tracing::warn!(?issue, "leader fairness issue");
+        }
+        let message = issues
+            .iter()
+            .map(|issue| format!("- {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(Box::new(LeaderFairnessError::Violations { message }))
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        let result = self.result.as_ref()?;
+        let nodes = result
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(node, share)| {
+                serde_json::json!({
+                    "node": node,
+                    "stake": share.stake,
+                    "blocks": share.blocks,
+                    "expected": share.expected,
+                })
+            })
+            .collect::<Vec<_>>();
+        Some(serde_json::json!({
+            "nodes": nodes,
+            "attributed": result.attributed,
+            "unattributed": result.unattributed,
+            "chi_square": result.chi_square,
+            "critical": result.critical,
+        }))
+    }
+}
+
+/// Leader note value of every node, in topology order.
+fn leader_stakes(descriptors: &GeneratedTopology) -> Vec<u64> {
+    descriptors
+        .nodes()
+        .iter()
+        .map(|node| {
+            let consensus = &node.general.consensus_config;
+            consensus
+                .utxos
+                .iter()
+                .filter(|utxo| utxo.note.pk == consensus.leader_config.pk)
+                .map(|utxo| utxo.note.value)
+                .sum()
+        })
+        .collect()
+}
+
+/// Leader key of every node, in topology order.
+fn leader_keys(descriptors: &GeneratedTopology) -> Vec<ZkPublicKey> {
+    descriptors
+        .nodes()
+        .iter()
+        .map(|node| node.general.consensus_config.leader_config.pk)
+        .collect()
+}
+
+/// Index of the node whose leader key produced the block's proof of
+/// leadership.
+fn producer(keys: &[ZkPublicKey], record: &BlockRecord) -> Option<usize> {
+    let leader = record.block.header().leader_proof().leader_key();
+    keys.iter().position(|key| key == leader)
+}
+
+/// Wilson–Hilferty approximation of the chi-square quantile with `dof`
+/// degrees of freedom at standard normal quantile `z`.
+fn chi_square_critical(dof: usize, z: f64) -> f64 {
+    if dof == 0 {
+        return f64::INFINITY;
+    }
+    let k = dof as f64;
+    let term = 2.0 / (9.0 * k);
+    k * (1.0 - term + z * term.sqrt()).powi(3)
+}
+
+fn spawn_feed_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    keys: Vec<ZkPublicKey>,
+    attribution: Weak<Mutex<Attribution>>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    let Some(attribution) = attribution.upgrade() else {
+                        break;
+                    };
+                    let producer = producer(&keys, &record);
+                    if producer.is_none() {
+                        tracing::debug!(
+                            header = ?record.header,
+                            "leader fairness found no node for block leader key"
+                        );
+                    }
+                    let mut attribution = attribution
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    attribution.observed.insert(record.header);
+                    if let Some(node) = producer {
+                        attribution.producers.insert(record.header, node);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "leader fairness capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::debug!("leader fairness capture feed closed");
+                    break;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(byte: u8) -> HeaderId {
+        HeaderId::from([byte; 32])
+    }
+
+    /// Attribution where node `i` produced `blocks[i]` distinct headers.
+    fn produced(blocks: &[usize]) -> Attribution {
+        let mut attribution = Attribution::default();
+        let mut next = 0u8;
+        for (node, count) in blocks.iter().enumerate() {
+            for _ in 0..*count {
+                let id = header(next);
+                next += 1;
+                attribution.producers.insert(id, node);
+                attribution.observed.insert(id);
+            }
+        }
+        attribution
+    }
+
+    #[test]
+    fn fairness_splits_expected_blocks_by_stake() {
+        let mut attribution = produced(&[30, 10]);
+        attribution.observed.insert(header(200));
+
+        let result = LeaderFairness::new().fairness(&[300, 100], &attribution);
+
+        assert_eq!(result.attributed, 40);
+        assert_eq!(result.unattributed, 1);
+        assert!((result.nodes[0].expected - 30.0).abs() < 1e-9);
+        assert!((result.nodes[1].expected - 10.0).abs() < 1e-9);
+        assert!(result.chi_square.abs() < 1e-9);
+        assert!((result.critical - chi_square_critical(1, DEFAULT_Z)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fairness_ignores_unstaked_nodes_for_degrees_of_freedom() {
+        let result = LeaderFairness::new().fairness(&[100, 100, 0], &produced(&[5, 5, 0]));
+
+        assert!(result.nodes[2].expected.abs() < 1e-9);
+        assert!((result.critical - chi_square_critical(1, DEFAULT_Z)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn issues_empty_for_proportional_production() {
+        let expectation = LeaderFairness::new();
+        let result = expectation.fairness(&[100, 100, 100, 100], &produced(&[24, 26, 25, 25]));
+
+        assert!(expectation.issues(&result).is_empty());
+    }
+
+    #[test]
+    fn issues_flag_silent_and_excess_nodes() {
+        let expectation = LeaderFairness::new();
+        let result = expectation.fairness(&[100, 100, 100, 100], &produced(&[0, 60, 20, 20]));
+
+        let issues = expectation.issues(&result);
+
+        assert!(
+            issues
+                .iter()
+                .any(|issue| matches!(issue, FairnessIssue::Silent { node: 0, .. }))
+        );
+        assert!(
+            issues
+                .iter()
+                .any(|issue| matches!(issue, FairnessIssue::Excess { node: 1, .. }))
+        );
+        assert!(
+            issues
+                .iter()
+                .any(|issue| matches!(issue, FairnessIssue::GoodnessOfFit { .. }))
+        );
+    }
+
+    #[test]
+    fn issues_tolerate_silence_below_minimum_expectation() {
+        let expectation = LeaderFairness::new();
+        let result = expectation.fairness(&[100, 100], &produced(&[0, 4]));
+
+        assert!(
+            !expectation
+                .issues(&result)
+                .iter()
+                .any(|issue| matches!(issue, FairnessIssue::Silent { .. }))
+        );
+    }
+
+    #[test]
+    fn chi_square_critical_approximates_table_values() {
+        // Upper 0.1% quantiles of the chi-square distribution.
+        for (dof, exact) in [(2, 13.816), (4, 18.467), (10, 29.588), (30, 59.703)] {
+            let approx = chi_square_critical(dof, DEFAULT_Z);
+            assert!(
+                (approx - exact).abs() / exact < 0.03,
+                "dof {dof}: {approx} vs {exact}"
+            );
+        }
+        assert!(chi_square_critical(0, DEFAULT_Z).is_infinite());
+    }
+}
@@ -0,0 +1,265 @@
+use async_trait::async_trait;
+use regex::Regex;
+use testing_framework_core::scenario::{DynError, Expectation, NodeLogs, RunContext};
+use thiserror::Error;
+
+/// Patterns denied by default: Rust panic messages in any form.
+const DEFAULT_PATTERNS: &[&str] = &[r"thread '[^']*' panicked", r"\bpanic\b"];
+/// Matching lines kept per node; further matches are only counted.
+const MAX_MATCHES_PER_NODE: usize = 20;
+/// Matching lines listed per node in the failure message.
+const MAX_LISTED_MATCHES: usize = 3;
+/// Longest line excerpt kept for a match.
+const MAX_LINE_LEN: usize = 400;
+
+#[derive(Clone, Debug)]
+/// Collects every node's logs at the end of the run and fails if any line
+/// matches a deny pattern. Panics are denied by default; further regexes can
+/// be added with [`LogScanExpectation::with_pattern`].
+pub struct LogScanExpectation {
+    patterns: Vec<String>,
+    compiled: Option<Vec<Regex>>,
+    nodes: Vec<NodeScan>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct NodeScan {
+    label: String,
+    lines: usize,
+    total_matches: usize,
+    matches: Vec<LogMatch>,
+}
+
+#[derive(Clone, Debug)]
+struct LogMatch {
+    line_number: usize,
+    pattern: String,
+    line: String,
+}
+
+#[derive(Debug, Error)]
+enum LogScanError {
+    #[error("invalid log deny pattern '{pattern}': {source}")]
+    InvalidPattern {
+        pattern: String,
+        #[source]
+        source: regex::Error,
+    },
+    #[error("deployer does not expose node logs")]
+    NoLogSource,
+    #[error("log scan expectation not captured")]
+    NotCaptured,
+    #[error("node logs matched deny patterns:\n{message}")]
+    Violations { message: String },
+}
+
+impl LogScanExpectation {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            patterns: DEFAULT_PATTERNS
+                .iter()
+                .map(|pattern| (*pattern).to_owned())
+                .collect(),
+            compiled: None,
+            nodes: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Also deny lines matching `pattern` (regex syntax).
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.patterns.push(pattern.into());
+        self
+    }
+
+    #[must_use]
+    /// Drop the default panic patterns, keeping only those added with
+    /// [`LogScanExpectation::with_pattern`].
+    pub fn without_default_patterns(mut self) -> Self {
+        self.patterns
+            .retain(|pattern| !DEFAULT_PATTERNS.contains(&pattern.as_str()));
+        self
+    }
+
+    fn compile(&self) -> Result<Vec<Regex>, LogScanError> {
+        self.patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|source| LogScanError::InvalidPattern {
+                    pattern: pattern.clone(),
+                    source,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for LogScanExpectation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Expectation for LogScanExpectation {
+    fn name(&self) -> &'static str {
+        "log_scan"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        // Fail before the run rather than after it when nothing can be scanned.
+        if ctx.node_logs().is_none() {
+            return Err(Box::new(LogScanError::NoLogSource));
+        }
+        self.compiled = Some(self.compile()?);
+        tracing::info!(
+            patterns = ?self.patterns,
+            "log scan expectation starting capture"
+        );
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let patterns = self.compiled.as_ref().ok_or(LogScanError::NotCaptured)?;
+        let source = ctx.node_logs().ok_or(LogScanError::NoLogSource)?;
+        let nodes = source
+            .collect_logs()
+            .await?
+            .iter()
+            .map(|logs| scan_node(patterns, logs))
+            .collect();
+        self.nodes = nodes;
+
+        let offending = self
+            .nodes
+            .iter()
+            .filter(|node| node.total_matches > 0)
+            .collect::<Vec<_>>();
+        if offending.is_empty() {
+            tracing::info!(
+                nodes = self.nodes.len(),
+                lines = self.nodes.iter().map(|node| node.lines).sum::<usize>(),
+                "log scan expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        let mut message = Vec::new();
+        for node in offending {
+            tracing::warn!(
+                node = %node.label,
+                matches = node.total_matches,
+                "node logs matched deny patterns"
+            );
+            message.push(format!(
+                "- {}: {} matching lines",
+                node.label, node.total_matches
+            ));
+            for found in node.matches.iter().take(MAX_LISTED_MATCHES) {
+                message.push(format!(
+                    "    {}:{}: {}",
+                    node.label, found.line_number, found.line
+                ));
+            }
+        }
+        Err(Box::new(LogScanError::Violations {
+            message: message.join("\n"),
+        }))
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let matches = node
+                    .matches
+                    .iter()
+                    .map(|found| {
+                        serde_json::json!({
+                            "line_number": found.line_number,
+                            "pattern": found.pattern,
+                            "line": found.line,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                serde_json::json!({
+                    "node": node.label,
+                    "lines": node.lines,
+                    "total_matches": node.total_matches,
+                    "matches": matches,
+                })
+            })
+            .collect::<Vec<_>>();
+        Some(serde_json::json!({
+            "patterns": self.patterns,
+            "nodes": nodes,
+        }))
+    }
+}
+
+fn scan_node(patterns: &[Regex], logs: &NodeLogs) -> NodeScan {
+    let mut scan = NodeScan {
+        label: logs.label.clone(),
+        ..NodeScan::default()
+    };
+    for (idx, line) in logs.contents.lines().enumerate() {
+        scan.lines += 1;
+        let Some(pattern) = patterns.iter().find(|pattern| pattern.is_match(line)) else {
+            continue;
+        };
+        scan.total_matches += 1;
+        if scan.matches.len() < MAX_MATCHES_PER_NODE {
+            scan.matches.push(LogMatch {
+                line_number: idx + 1,
+                pattern: pattern.as_str().to_owned(),
+                line: excerpt(line),
+            });
+        }
+    }
+    scan
+}
+
+fn excerpt(line: &str) -> String {
+    let line = line.trim_end();
+    match line.char_indices().nth(MAX_LINE_LEN) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_node_reports_matching_lines_with_numbers() {
+        let expectation = LogScanExpectation::new().with_pattern("ERROR .*db");
+        let patterns = expectation.compile().expect("patterns compile");
+        let logs = NodeLogs {
+            label: "node-1".to_owned(),
+            contents: "INFO started\n\
+                       thread 'tokio-runtime-worker' panicked at src/lib.rs:1:1:\n\
+                       WARN panicking is fine in a word\n\
+                       ERROR failed to open db\n"
+                .to_owned(),
+        };
+
+        let scan = scan_node(&patterns, &logs);
+
+        assert_eq!(scan.lines, 4);
+        assert_eq!(scan.total_matches, 2);
+        assert_eq!(
+            scan.matches
+                .iter()
+                .map(|found| found.line_number)
+                .collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert_eq!(scan.matches[1].pattern, "ERROR .*db");
+    }
+}
@@ -1,3 +1,22 @@
 mod consensus_liveness;
+mod consensus_safety;
+mod finality_lag;
+mod leader_fairness;
+mod log_scan;
+mod peer_connectivity;
+mod reorg_depth;
+mod resource_usage;
+mod slot_occupancy;
+mod supply_conservation;
 
 pub use consensus_liveness::ConsensusLiveness;
+pub use consensus_safety::ConsensusSafety;
+pub(crate) use consensus_safety::find_genesis;
+pub use finality_lag::FinalityLag;
+pub use leader_fairness::LeaderFairness;
+pub use log_scan::LogScanExpectation;
+pub use peer_connectivity::PeerConnectivity;
+pub use reorg_depth::MaxReorgDepth;
+pub use resource_usage::ResourceUsageExpectation;
+pub use slot_occupancy::SlotOccupancy;
+pub use supply_conservation::SupplyConservation;
@@ -0,0 +1,277 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::time::{Instant, sleep};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_MAX_DEGRADED: Duration = Duration::from_secs(60);
+const RECOVERY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_RECOVERY_WAIT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug)]
+/// Samples every node's peer count during the run. Each node must end the run
+/// with at least the peers its layout implies, and may not stay below that
+/// for longer than `max_degraded` at a time. Periods in which a node had no
+/// peers at all are recorded in the report.
+pub struct PeerConnectivity {
+    max_degraded: Duration,
+    started: Option<Instant>,
+    expected: Vec<usize>,
+    nodes: Vec<NodePeers>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct NodePeers {
+    samples: usize,
+    failed_samples: usize,
+    last: Option<usize>,
+    min: Option<usize>,
+    max: usize,
+    /// Start of the current run of samples below the expected count.
+    degraded_since: Option<Instant>,
+    longest_degraded: Duration,
+    /// Start of the current run of samples with no peers.
+    isolated_since: Option<Instant>,
+    isolations: Vec<(Instant, Instant)>,
+}
+
+impl NodePeers {
+    fn record(&mut self, peers: usize, expected: Option<usize>, now: Instant) {
+        self.samples += 1;
+        self.last = Some(peers);
+        self.min = Some(self.min.map_or(peers, |min| min.min(peers)));
+        self.max = self.max.max(peers);
+
+        if expected.is_some_and(|expected| peers < expected) {
+            let since = *self.degraded_since.get_or_insert(now);
+            self.longest_degraded = self
+                .longest_degraded
+                .max(now.saturating_duration_since(since));
+        } else {
+            self.degraded_since = None;
+        }
+
+        if peers == 0 {
+            self.isolated_since.get_or_insert(now);
+        } else if let Some(since) = self.isolated_since.take() {
+            self.isolations.push((since, now));
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+enum PeerConnectivityIssue {
+    #[error("node-{node} ended with {peers} peers, expected at least {expected}")]
+    BelowMinimum {
+        node: usize,
+        peers: usize,
+        expected: usize,
+    },
+    #[error("node-{node} network info never answered")]
+    NoSamples { node: usize },
+    #[error("node-{node} stayed below {expected} peers for {degraded:?} (max {max:?})")]
+    Degraded {
+        node: usize,
+        expected: usize,
+        degraded: Duration,
+        max: Duration,
+    },
+}
+
+#[derive(Debug, Error)]
+enum PeerConnectivityError {
+    #[error("peer connectivity expectation not captured")]
+    NotCaptured,
+    #[error("peer connectivity violated:\n{message}")]
+    Violations { message: String },
+}
+
+impl PeerConnectivity {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_degraded: DEFAULT_MAX_DEGRADED,
+            started: None,
+            expected: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Longest a node may stay below its expected peer count at a time, e.g.
+    /// while re-dialling after a restart.
+    pub const fn with_max_degraded(mut self, max_degraded: Duration) -> Self {
+        self.max_degraded = max_degraded;
+        self
+    }
+
+    async fn sample(&mut self, ctx: &RunContext) {
+        let clients = ctx.node_clients().all_clients();
+        if self.nodes.len() < clients.len() {
+            self.nodes.resize_with(clients.len(), NodePeers::default);
+        }
+        for (node, client) in clients.iter().enumerate() {
+            match client.network_info().await {
+                Ok(info) => {
+                    let expected = self.expected.get(node).copied();
+                    self.nodes[node].record(info.n_peers, expected, Instant::now());
+                }
+                Err(err) => {
+                    tracing::debug!(node, %err, "peer connectivity network info failed");
+                    self.nodes[node].failed_samples += 1;
+                }
+            }
+        }
+    }
+
+    fn issues(&self) -> Vec<PeerConnectivityIssue> {
+        let mut issues = Vec::new();
+        for (node, peers) in self.nodes.iter().enumerate() {
+            let Some(last) = peers.last else {
+                issues.push(PeerConnectivityIssue::NoSamples { node });
+                continue;
+            };
+            let Some(expected) = self.expected.get(node).copied() else {
+                continue;
+            };
+            if last < expected {
+                issues.push(PeerConnectivityIssue::BelowMinimum {
+                    node,
+                    peers: last,
+                    expected,
+                });
+            }
+            if peers.longest_degraded > self.max_degraded {
+                issues.push(PeerConnectivityIssue::Degraded {
+                    node,
+                    expected,
+                    degraded: peers.longest_degraded,
+                    max: self.max_degraded,
+                });
+            }
+        }
+        issues
+    }
+
+    /// Whether every node is back to its expected peer count.
+    fn recovered(&self) -> bool {
+        self.nodes.iter().enumerate().all(|(node, peers)| {
+            match (peers.last, self.expected.get(node)) {
+                (Some(last), Some(expected)) => last >= *expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            }
+        })
+    }
+}
+
+impl Default for PeerConnectivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Expectation for PeerConnectivity {
+    fn name(&self) -> &'static str {
+        "peer_connectivity"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.expected = ctx.descriptors().expected_peer_counts();
+        self.started = Some(Instant::now());
+        self.nodes.clear();
+        tracing::info!(
+            expected = ?self.expected,
+            "peer connectivity expectation starting capture"
+        );
+        Ok(())
+    }
+
+    fn monitor_interval(&self) -> Option<Duration> {
+        Some(SAMPLE_INTERVAL)
+    }
+
+    async fn monitor(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.sample(ctx).await;
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.started.is_none() {
+            return Err(Box::new(PeerConnectivityError::NotCaptured));
+        }
+
+        // A node restarted near the end of the run may still be re-dialling.
+        let mut remaining = MAX_RECOVERY_WAIT;
+        loop {
+            self.sample(ctx).await;
+            if self.recovered() || remaining.is_zero() {
+                break;
+            }
+            sleep(RECOVERY_POLL_INTERVAL).await;
+            remaining = remaining.saturating_sub(RECOVERY_POLL_INTERVAL);
+        }
+
+        let issues = self.issues();
+        if issues.is_empty() {
+            tracing::info!(
+                nodes = self.nodes.len(),
+                isolations = self
+                    .nodes
+                    .iter()
+                    .map(|peers| peers.isolations.len())
+                    .sum::<usize>(),
+                "peer connectivity expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        for issue in &issues {
+            tracing::warn!(?issue, "peer connectivity issue");
+        }
+        let message = issues
+            .iter()
+            .map(|issue| format!("- {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(Box::new(PeerConnectivityError::Violations { message }))
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        let started = self.started?;
+        let offset = |instant: Instant| instant.saturating_duration_since(started).as_secs_f64();
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(node, peers)| {
+                let mut isolations = peers
+                    .isolations
+                    .iter()
+                    .map(|(from, to)| serde_json::json!({ "from_secs": offset(*from), "to_secs": offset(*to) }))
+                    .collect::<Vec<_>>();
+                if let Some(since) = peers.isolated_since {
+                    isolations.push(serde_json::json!({ "from_secs": offset(since), "to_secs": null }));
+                }
+                serde_json::json!({
+                    "node": node,
+                    "expected": self.expected.get(node),
+                    "samples": peers.samples,
+                    "failed_samples": peers.failed_samples,
+                    "last": peers.last,
+                    "min": peers.min,
+                    "max": peers.max,
+                    "longest_degraded_secs": peers.longest_degraded.as_secs_f64(),
+                    "isolations": isolations,
+                })
+            })
+            .collect::<Vec<_>>();
+        Some(serde_json::json!({
+            "max_degraded_secs": self.max_degraded.as_secs_f64(),
+            "nodes": nodes,
+        }))
+    }
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use nomos_core::header::HeaderId;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+
+const MONITOR_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug)]
+/// Checks that no node reorgs deeper than `max_depth` blocks, using the fork
+/// tracking of the run's block feed.
+pub struct MaxReorgDepth {
+    max_depth: u64,
+    observed_depth: u64,
+    reorgs: usize,
+}
+
+#[derive(Debug, Error)]
+enum MaxReorgDepthError {
+    #[error("node-{node} reorged {depth} blocks (max {max_depth}): {old_tip:?} -> {new_tip:?}")]
+    Exceeded {
+        node: usize,
+        depth: u64,
+        max_depth: u64,
+        old_tip: HeaderId,
+        new_tip: HeaderId,
+    },
+}
+
+impl MaxReorgDepth {
+    #[must_use]
+    pub const fn new(max_depth: u64) -> Self {
+        Self {
+            max_depth,
+            observed_depth: 0,
+            reorgs: 0,
+        }
+    }
+
+    fn check(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let reorgs = ctx.block_feed().chain().reorgs();
+        self.reorgs = reorgs.len();
+
+        let Some(deepest) = reorgs.into_iter().max_by_key(|reorg| reorg.depth) else {
+            return Ok(());
+        };
+        self.observed_depth = deepest.depth;
+
+        if deepest.depth > self.max_depth {
+            return Err(Box::new(MaxReorgDepthError::Exceeded {
+                node: deepest.node,
+                depth: deepest.depth,
+                max_depth: self.max_depth,
+                old_tip: deepest.old_tip,
+                new_tip: deepest.new_tip,
+            }));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Expectation for MaxReorgDepth {
+    fn name(&self) -> &'static str {
+        "max_reorg_depth"
+    }
+
+    fn monitor_interval(&self) -> Option<Duration> {
+        Some(MONITOR_INTERVAL)
+    }
+
+    async fn monitor(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.check(ctx)
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.check(ctx)?;
+        tracing::info!(
+            max_depth = self.max_depth,
+            observed_depth = self.observed_depth,
+            reorgs = self.reorgs,
+            "max reorg depth expectation satisfied"
+        );
+        Ok(())
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "max_depth": self.max_depth,
+            "observed_depth": self.observed_depth,
+            "reorgs": self.reorgs,
+        }))
+    }
+}
@@ -0,0 +1,428 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use async_trait::async_trait;
+use testing_framework_core::scenario::{
+    DynError, Expectation, ResourceSample, ResourceUsage, RunContext,
+};
+use thiserror::Error;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// Samples ignored for growth slopes while nodes start up and fill caches.
+const DEFAULT_WARMUP: Duration = Duration::from_secs(60);
+/// Fewest post-warmup samples a growth slope is computed from.
+const MIN_GROWTH_SAMPLES: usize = 6;
+const MIB: f64 = 1024.0 * 1024.0;
+const SECS_PER_HOUR: f64 = 3600.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Metric {
+    Cpu,
+    Rss,
+    OpenFds,
+    Disk,
+}
+
+impl Metric {
+    const ALL: [Self; 4] = [Self::Cpu, Self::Rss, Self::OpenFds, Self::Disk];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Rss => "rss",
+            Self::OpenFds => "open_fds",
+            Self::Disk => "disk",
+        }
+    }
+
+    fn value(self, usage: &ResourceUsage) -> Option<f64> {
+        match self {
+            Self::Cpu => usage.cpu_percent,
+            Self::Rss => usage.rss_bytes.map(|bytes| bytes as f64),
+            Self::OpenFds => usage.open_fds.map(|fds| fds as f64),
+            Self::Disk => usage.disk_bytes.map(|bytes| bytes as f64),
+        }
+    }
+
+    fn format(self, value: f64) -> String {
+        match self {
+            Self::Cpu => format!("{value:.1}%"),
+            Self::Rss | Self::Disk => format!("{:.1} MiB", value / MIB),
+            Self::OpenFds => format!("{value:.0}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Checks every node's sampled resource usage against absolute limits and
+/// growth slopes, e.g. to catch memory leaks during soak runs. Limits are
+/// checked throughout the run; slopes are fitted over the samples taken after
+/// a warmup and checked at the end.
+pub struct ResourceUsageExpectation {
+    max: BTreeMap<Metric, f64>,
+    max_growth_per_hour: BTreeMap<Metric, f64>,
+    warmup: Duration,
+    captured: bool,
+    nodes: Vec<NodeUsage>,
+}
+
+#[derive(Clone, Debug)]
+struct NodeUsage {
+    label: String,
+    samples: usize,
+    metrics: BTreeMap<Metric, MetricSummary>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct MetricSummary {
+    last: f64,
+    peak: f64,
+    growth_per_hour: Option<f64>,
+}
+
+#[derive(Debug, Error)]
+enum ResourceUsageError {
+    #[error("deployer does not sample node resource usage")]
+    NoSampler,
+    #[error("resource usage expectation not captured")]
+    NotCaptured,
+    #[error("no resource usage samples were collected")]
+    NoSamples,
+    #[error("resource usage limits exceeded:\n{message}")]
+    Violations { message: String },
+}
+
+impl ResourceUsageExpectation {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max: BTreeMap::new(),
+            max_growth_per_hour: BTreeMap::new(),
+            warmup: DEFAULT_WARMUP,
+            captured: false,
+            nodes: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Highest CPU use per node, in percent of one core.
+    pub fn with_max_cpu_percent(mut self, percent: f64) -> Self {
+        self.max.insert(Metric::Cpu, percent);
+        self
+    }
+
+    #[must_use]
+    /// Highest resident memory per node, in MiB.
+    pub fn with_max_rss_mb(mut self, mb: u64) -> Self {
+        self.max.insert(Metric::Rss, mb as f64 * MIB);
+        self
+    }
+
+    #[must_use]
+    /// Most open file descriptors per node.
+    pub fn with_max_open_fds(mut self, fds: u64) -> Self {
+        self.max.insert(Metric::OpenFds, fds as f64);
+        self
+    }
+
+    #[must_use]
+    /// Most disk space written per node, in MiB.
+    pub fn with_max_disk_mb(mut self, mb: u64) -> Self {
+        self.max.insert(Metric::Disk, mb as f64 * MIB);
+        self
+    }
+
+    #[must_use]
+    /// Fastest resident memory growth per node, in MiB per hour.
+    pub fn with_max_rss_growth_mb_per_hour(mut self, mb: f64) -> Self {
+        self.max_growth_per_hour.insert(Metric::Rss, mb * MIB);
+        self
+    }
+
+    #[must_use]
+    /// Fastest growth in open file descriptors per node, per hour.
+    pub fn with_max_open_fds_growth_per_hour(mut self, fds: f64) -> Self {
+        self.max_growth_per_hour.insert(Metric::OpenFds, fds);
+        self
+    }
+
+    #[must_use]
+    /// Fastest disk usage growth per node, in MiB per hour.
+    pub fn with_max_disk_growth_mb_per_hour(mut self, mb: f64) -> Self {
+        self.max_growth_per_hour.insert(Metric::Disk, mb * MIB);
+        self
+    }
+
+    #[must_use]
+    /// Samples to leave out of growth slopes at the start of the run;
+    /// defaults to one minute.
+    pub const fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    fn limit_violations(&self, nodes: &[NodeUsage]) -> Vec<String> {
+        let mut violations = Vec::new();
+        for node in nodes {
+            for (metric, summary) in &node.metrics {
+                if let Some(max) = self.max.get(metric)
+                    && summary.peak > *max
+                {
+                    violations.push(format!(
+                        "{} {} peaked at {} (max {})",
+                        node.label,
+                        metric.name(),
+                        metric.format(summary.peak),
+                        metric.format(*max)
+                    ));
+                }
+            }
+        }
+        violations
+    }
+
+    fn growth_violations(&self, nodes: &[NodeUsage]) -> Vec<String> {
+        let mut violations = Vec::new();
+        for node in nodes {
+            for (metric, max) in &self.max_growth_per_hour {
+                let Some(growth) = node
+                    .metrics
+                    .get(metric)
+                    .and_then(|summary| summary.growth_per_hour)
+                else {
+                    tracing::warn!(
+                        node = %node.label,
+                        metric = metric.name(),
+                        "too few resource samples after warmup to check growth"
+                    );
+                    continue;
+                };
+                if growth > *max {
+                    violations.push(format!(
+                        "{} {} grew {}/hour (max {}/hour)",
+                        node.label,
+                        metric.name(),
+                        metric.format(growth),
+                        metric.format(*max)
+                    ));
+                }
+            }
+        }
+        violations
+    }
+}
+
+impl Default for ResourceUsageExpectation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Expectation for ResourceUsageExpectation {
+    fn name(&self) -> &'static str {
+        "resource_usage"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if ctx.resource_usage().is_none() {
+            return Err(Box::new(ResourceUsageError::NoSampler));
+        }
+        self.captured = true;
+        self.nodes.clear();
+        tracing::info!(
+            max = ?self.max,
+            max_growth_per_hour = ?self.max_growth_per_hour,
+            warmup = ?self.warmup,
+            "resource usage expectation starting capture"
+        );
+        Ok(())
+    }
+
+    fn monitor_interval(&self) -> Option<Duration> {
+        (!self.max.is_empty()).then_some(CHECK_INTERVAL)
+    }
+
+    async fn monitor(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let series = ctx.resource_usage().ok_or(ResourceUsageError::NoSampler)?;
+        self.nodes = summarize(&series.snapshot(), self.warmup);
+        let violations = self.limit_violations(&self.nodes);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(Box::new(ResourceUsageError::Violations {
+            message: format_violations(&violations),
+        }))
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if !self.captured {
+            return Err(Box::new(ResourceUsageError::NotCaptured));
+        }
+        let series = ctx.resource_usage().ok_or(ResourceUsageError::NoSampler)?;
+        self.nodes = summarize(&series.snapshot(), self.warmup);
+        if self.nodes.iter().all(|node| node.samples == 0) {
+            return Err(Box::new(ResourceUsageError::NoSamples));
+        }
+
+        let mut violations = self.limit_violations(&self.nodes);
+        violations.extend(self.growth_violations(&self.nodes));
+        if violations.is_empty() {
+            tracing::info!(
+                nodes = self.nodes.len(),
+                samples = self.nodes.iter().map(|node| node.samples).sum::<usize>(),
+                "resource usage expectation satisfied"
+            );
+            return Ok(());
+        }
+        for violation in &violations {
+            tracing::warn!(%violation, "resource usage limit exceeded");
+        }
+        Err(Box::new(ResourceUsageError::Violations {
+            message: format_violations(&violations),
+        }))
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let limits = |limits: &BTreeMap<Metric, f64>| {
+            limits
+                .iter()
+                .map(|(metric, value)| (metric.name().to_owned(), serde_json::json!(value)))
+                .collect::<serde_json::Map<_, _>>()
+        };
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let metrics = node
+                    .metrics
+                    .iter()
+                    .map(|(metric, summary)| {
+                        (
+                            metric.name().to_owned(),
+                            serde_json::json!({
+                                "last": summary.last,
+                                "peak": summary.peak,
+                                "growth_per_hour": summary.growth_per_hour,
+                            }),
+                        )
+                    })
+                    .collect::<serde_json::Map<_, _>>();
+                serde_json::json!({
+                    "node": node.label,
+                    "samples": node.samples,
+                    "metrics": metrics,
+                })
+            })
+            .collect::<Vec<_>>();
+        Some(serde_json::json!({
+            "max": limits(&self.max),
+            "max_growth_per_hour": limits(&self.max_growth_per_hour),
+            "warmup_secs": self.warmup.as_secs_f64(),
+            "nodes": nodes,
+        }))
+    }
+}
+
+fn summarize(series: &BTreeMap<String, Vec<ResourceSample>>, warmup: Duration) -> Vec<NodeUsage> {
+    series
+        .iter()
+        .map(|(label, samples)| NodeUsage {
+            label: label.clone(),
+            samples: samples.len(),
+            metrics: Metric::ALL
+                .into_iter()
+                .filter_map(|metric| Some((metric, summarize_metric(samples, metric, warmup)?)))
+                .collect(),
+        })
+        .collect()
+}
+
+fn summarize_metric(
+    samples: &[ResourceSample],
+    metric: Metric,
+    warmup: Duration,
+) -> Option<MetricSummary> {
+    let values = samples
+        .iter()
+        .filter_map(|sample| Some((sample.elapsed, metric.value(&sample.usage)?)))
+        .collect::<Vec<_>>();
+    let (_, last) = *values.last()?;
+    let peak = values.iter().map(|(_, value)| *value).fold(last, f64::max);
+    let after_warmup = values
+        .iter()
+        .filter(|(elapsed, _)| *elapsed >= warmup)
+        .copied()
+        .collect::<Vec<_>>();
+    Some(MetricSummary {
+        last,
+        peak,
+        growth_per_hour: growth_per_hour(&after_warmup),
+    })
+}
+
+/// Least-squares slope of the values over time, per hour.
+fn growth_per_hour(points: &[(Duration, f64)]) -> Option<f64> {
+    if points.len() < MIN_GROWTH_SAMPLES {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_t = points.iter().map(|(t, _)| t.as_secs_f64()).sum::<f64>() / n;
+    let mean_v = points.iter().map(|(_, v)| *v).sum::<f64>() / n;
+    let (covariance, variance) =
+        points
+            .iter()
+            .fold((0.0, 0.0), |(covariance, variance), (t, v)| {
+                let dt = t.as_secs_f64() - mean_t;
+                (covariance + dt * (v - mean_v), variance + dt * dt)
+            });
+    (variance > 0.0).then(|| covariance / variance * SECS_PER_HOUR)
+}
+
+fn format_violations(violations: &[String]) -> String {
+    violations
+        .iter()
+        .map(|violation| format!("- {violation}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rss_samples(mib_per_minute: f64, minutes: u64) -> Vec<ResourceSample> {
+        (0..=minutes)
+            .map(|minute| ResourceSample {
+                elapsed: Duration::from_secs(minute * 60),
+                usage: ResourceUsage {
+                    rss_bytes: Some((100.0 * MIB + minute as f64 * mib_per_minute * MIB) as u64),
+                    ..ResourceUsage::default()
+                },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn growth_slope_is_fitted_after_warmup() {
+        let mut series = BTreeMap::new();
+        series.insert("node-0".to_owned(), rss_samples(1.0, 10));
+        series.insert("node-1".to_owned(), rss_samples(0.0, 10));
+        let expectation = ResourceUsageExpectation::new()
+            .with_max_rss_growth_mb_per_hour(30.0)
+            .with_max_rss_mb(105);
+
+        let nodes = summarize(&series, expectation.warmup);
+
+        let growth = nodes[0].metrics[&Metric::Rss]
+            .growth_per_hour
+            .expect("enough samples");
+        assert!((growth / MIB - 60.0).abs() < 1e-3);
+        assert_eq!(nodes[1].metrics[&Metric::Rss].growth_per_hour, Some(0.0));
+        assert_eq!(expectation.growth_violations(&nodes).len(), 1);
+        assert_eq!(expectation.limit_violations(&nodes).len(), 1);
+        assert!(!nodes[0].metrics.contains_key(&Metric::Cpu));
+    }
+}
@@ -0,0 +1,312 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use nomos_core::header::HeaderId;
+use testing_framework_core::scenario::{BlockRecord, DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+const DEFAULT_TOLERANCE: f64 = 0.25;
+const MIN_BLOCKS: usize = 10;
+/// Chance of wrongly flagging an honest run as having an empty-slot run that
+/// is too long.
+const EMPTY_RUN_FALSE_POSITIVE: f64 = 1e-3;
+
+/// Parent and slot of every block observed by the feed, by header.
+type ObservedSlots = Arc<Mutex<HashMap<HeaderId, (HeaderId, u64)>>>;
+
+#[derive(Clone, Debug)]
+/// Compares the slot occupancy of the observed chain with the configured
+/// `active_slot_coeff`: the fraction of occupied slots must be within
+/// `tolerance` (relative) of the coefficient, and no run of empty slots may be
+/// implausibly long.
+pub struct SlotOccupancy {
+    tolerance: f64,
+    observed: Option<ObservedSlots>,
+    stats: Option<SlotStats>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct SlotStats {
+    active_slot_coeff: f64,
+    blocks: usize,
+    slots: u64,
+    occupancy: f64,
+    mean_interval: f64,
+    /// Block intervals in slots, with their counts.
+    intervals: BTreeMap<u64, usize>,
+    /// Runs of consecutive empty slots, by length, with their counts.
+    empty_runs: BTreeMap<u64, usize>,
+    longest_empty_run: u64,
+    max_empty_run: Option<u64>,
+}
+
+#[derive(Debug, Error)]
+enum SlotOccupancyIssue {
+    #[error("occupied slot fraction {observed:.3} is outside {expected:.3} ± {tolerance:.0}%")]
+    Occupancy {
+        observed: f64,
+        expected: f64,
+        tolerance: f64,
+    },
+    #[error("longest empty-slot run {observed} exceeds plausible maximum {max}")]
+    EmptyRun { observed: u64, max: u64 },
+}
+
+#[derive(Debug, Error)]
+enum SlotOccupancyError {
+    #[error("slot occupancy expectation not captured")]
+    NotCaptured,
+    #[error("active_slot_coeff {coeff} must be in (0, 1]")]
+    InvalidCoefficient { coeff: f64 },
+    #[error("observed {blocks} chained blocks, need at least {MIN_BLOCKS}")]
+    InsufficientBlocks { blocks: usize },
+    #[error("slot occupancy deviates from consensus params:\n{message}")]
+    Violations { message: String },
+}
+
+impl SlotOccupancy {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            tolerance: DEFAULT_TOLERANCE,
+            observed: None,
+            stats: None,
+        }
+    }
+
+    #[must_use]
+    /// Relative deviation allowed from the predicted occupancy (e.g. `0.25`
+    /// for ±25%).
+    pub const fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    fn check(&self, stats: &SlotStats) -> Vec<SlotOccupancyIssue> {
+        let mut issues = Vec::new();
+        let expected = stats.active_slot_coeff;
+        if (stats.occupancy - expected).abs() > expected * self.tolerance {
+            issues.push(SlotOccupancyIssue::Occupancy {
+                observed: stats.occupancy,
+                expected,
+                tolerance: self.tolerance * 100.0,
+            });
+        }
+        match stats.max_empty_run {
+            Some(max) if stats.longest_empty_run > max => {
+                issues.push(SlotOccupancyIssue::EmptyRun {
+                    observed: stats.longest_empty_run,
+                    max,
+                });
+            }
+            _ => {}
+        }
+        issues
+    }
+}
+
+impl Default for SlotOccupancy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Expectation for SlotOccupancy {
+    fn name(&self) -> &'static str {
+        "slot_occupancy"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.observed.is_some() {
+            return Ok(());
+        }
+        let observed = ObservedSlots::default();
+        spawn_slot_capture(ctx.block_feed().subscribe(), Arc::clone(&observed));
+        self.observed = Some(observed);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let observed = self
+            .observed
+            .as_ref()
+            .ok_or(SlotOccupancyError::NotCaptured)?;
+        let coeff = ctx
+            .descriptors()
+            .config()
+            .consensus_params
+            .active_slot_coeff;
+        if !(coeff > 0.0 && coeff <= 1.0) {
+            return Err(Box::new(SlotOccupancyError::InvalidCoefficient { coeff }));
+        }
+
+        let slots = {
+            let observed = observed
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            longest_chain_slots(&observed)
+        };
+        if slots.len() < MIN_BLOCKS {
+            return Err(Box::new(SlotOccupancyError::InsufficientBlocks {
+                blocks: slots.len(),
+            }));
+        }
+
+        let stats = slot_stats(&slots, coeff);
+        let issues = self.check(&stats);
+        self.stats = Some(stats.clone());
+
+        if issues.is_empty() {
+            tracing::info!(
+                occupancy = stats.occupancy,
+                expected = coeff,
+                mean_interval = stats.mean_interval,
+                longest_empty_run = stats.longest_empty_run,
+                blocks = stats.blocks,
+                "slot occupancy expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        for issue in &issues {
+            tracing::warn!(?issue, "slot occupancy issue");
+        }
+        let message = issues
+            .iter()
+            .map(|issue| format!("- {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(Box::new(SlotOccupancyError::Violations { message }))
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        let stats = self.stats.as_ref()?;
+        let histogram = |counts: &BTreeMap<u64, usize>| {
+            counts
+                .iter()
+                .map(|(length, count)| (length.to_string(), serde_json::Value::from(*count)))
+                .collect::<serde_json::Map<_, _>>()
+        };
+        Some(serde_json::json!({
+            "active_slot_coeff": stats.active_slot_coeff,
+            "tolerance": self.tolerance,
+            "blocks": stats.blocks,
+            "slots": stats.slots,
+            "occupancy": stats.occupancy,
+            "mean_interval_slots": stats.mean_interval,
+            "interval_histogram": histogram(&stats.intervals),
+            "empty_run_histogram": histogram(&stats.empty_runs),
+            "longest_empty_run": stats.longest_empty_run,
+            "max_plausible_empty_run": stats.max_empty_run,
+        }))
+    }
+}
+
+/// Slots of the chain ending at the latest observed block, oldest first.
+/// Blocks on other forks are ignored so their slots do not count twice.
+fn longest_chain_slots(observed: &HashMap<HeaderId, (HeaderId, u64)>) -> Vec<u64> {
+    let Some(mut cursor) = observed
+        .iter()
+        .max_by_key(|(_, (_, slot))| *slot)
+        .map(|(header, _)| *header)
+    else {
+        return Vec::new();
+    };
+
+    let mut slots = Vec::new();
+    while let Some((parent, slot)) = observed.get(&cursor) {
+        slots.push(*slot);
+        if *parent == cursor {
+            break;
+        }
+        cursor = *parent;
+    }
+    slots.reverse();
+    slots
+}
+
+fn slot_stats(slots: &[u64], active_slot_coeff: f64) -> SlotStats {
+    let mut intervals = BTreeMap::new();
+    let mut empty_runs = BTreeMap::new();
+    for pair in slots.windows(2) {
+        let interval = pair[1].saturating_sub(pair[0]);
+        *intervals.entry(interval).or_default() += 1;
+        if interval > 1 {
+            *empty_runs.entry(interval - 1).or_default() += 1;
+        }
+    }
+
+    let first = slots.first().copied().unwrap_or_default();
+    let last = slots.last().copied().unwrap_or_default();
+    // The first block only anchors the window; occupancy counts the rest.
+    let span = last.saturating_sub(first).max(1);
+    let gaps = slots.len().saturating_sub(1);
+
+    // Empty runs are geometric: a run of `k` empty slots after a block has
+    // probability (1 - f)^k, so over `gaps` intervals the longest plausible
+    // one is ln(gaps / p) / -ln(1 - f).
+    let max_empty_run = (active_slot_coeff < 1.0).then(|| {
+        let bound = (gaps as f64 / EMPTY_RUN_FALSE_POSITIVE).ln() / -(1.0 - active_slot_coeff).ln();
+        bound.ceil() as u64
+    });
+
+    SlotStats {
+        active_slot_coeff,
+        blocks: slots.len(),
+        slots: span,
+        occupancy: gaps as f64 / span as f64,
+        mean_interval: span as f64 / gaps.max(1) as f64,
+        longest_empty_run: empty_runs.keys().next_back().copied().unwrap_or(0),
+        intervals,
+        empty_runs,
+        max_empty_run,
+    }
+}
+
+fn spawn_slot_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    observed: ObservedSlots,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    let header = record.block.header();
+                    observed
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .insert(record.header, (header.parent(), u64::from(header.slot())));
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "slot occupancy capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::debug!("slot occupancy capture feed closed");
+                    break;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_stats_counts_intervals_and_empty_runs() {
+        let stats = slot_stats(&[10, 11, 13, 14, 18], 0.5);
+
+        assert_eq!(stats.slots, 8);
+        assert!((stats.occupancy - 0.5).abs() < f64::EPSILON);
+        assert_eq!(stats.intervals, BTreeMap::from([(1, 2), (2, 1), (4, 1)]));
+        assert_eq!(stats.empty_runs, BTreeMap::from([(1, 1), (3, 1)]));
+        assert_eq!(stats.longest_empty_run, 3);
+        assert_eq!(slot_stats(&[1, 2, 3], 1.0).max_empty_run, None);
+    }
+}
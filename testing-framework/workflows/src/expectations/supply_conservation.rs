@@ -0,0 +1,311 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use nomos_core::{
+    block::Block,
+    header::HeaderId,
+    mantle::{GenesisTx as _, SignedMantleTx, Transaction as _, TxHash, Utxo, ledger::NoteId},
+};
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{BlockRecord, DynError, Expectation, RunContext},
+};
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+use super::find_genesis;
+
+/// Blocks observed by the block feed since capture started, by header.
+type CapturedBlocks = Arc<Mutex<HashMap<HeaderId, Arc<Block<SignedMantleTx>>>>>;
+
+#[derive(Default)]
+/// Replays every ledger transaction on the canonical chain from the genesis
+/// notes and checks that no value is created, no UTXO is spent twice and the
+/// final UTXO set still adds up to the genesis funding.
+pub struct SupplyConservation {
+    genesis_utxos: Vec<Utxo>,
+    blocks: Option<CapturedBlocks>,
+    replay: Option<ReplaySummary>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct ReplaySummary {
+    genesis_supply: u64,
+    final_supply: u64,
+    fees: u64,
+    blocks: usize,
+    transactions: usize,
+    violations: usize,
+}
+
+#[derive(Debug, Error)]
+enum SupplyIssue {
+    #[error("tx {tx:?} spends note {note:?} already spent by {spender:?}")]
+    DoubleSpend {
+        tx: TxHash,
+        note: NoteId,
+        spender: TxHash,
+    },
+    #[error("tx {tx:?} spends unknown note {note:?}")]
+    UnknownInput { tx: TxHash, note: NoteId },
+    #[error("tx {tx:?} outputs {outputs} exceed inputs {inputs}")]
+    Inflation {
+        tx: TxHash,
+        inputs: u64,
+        outputs: u64,
+    },
+    #[error("final supply {final_supply} plus fees {fees} differs from genesis funding {genesis}")]
+    Mismatch {
+        genesis: u64,
+        final_supply: u64,
+        fees: u64,
+    },
+}
+
+#[derive(Debug, Error)]
+enum SupplyConservationError {
+    #[error("supply conservation expectation not captured")]
+    NotCaptured,
+    #[error("supply conservation requires at least one node")]
+    MissingParticipants,
+    #[error("could not locate the genesis block")]
+    MissingGenesis,
+    #[error("block {header:?} on the canonical chain could not be fetched")]
+    MissingBlock { header: HeaderId },
+    #[error("supply conservation violated:\n{message}")]
+    Violations { message: String },
+}
+
+/// UTXO set rebuilt by replaying ledger transactions in chain order.
+#[derive(Default)]
+struct Ledger {
+    unspent: HashMap<NoteId, u64>,
+    spent: HashMap<NoteId, TxHash>,
+    fees: u64,
+    transactions: usize,
+}
+
+impl Ledger {
+    fn seed(utxos: &[Utxo]) -> Self {
+        Self {
+            unspent: utxos
+                .iter()
+                .map(|utxo| (utxo.id(), utxo.note.value))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    fn supply(&self) -> u64 {
+        self.unspent.values().sum()
+    }
+
+    fn apply(&mut self, tx: &SignedMantleTx, issues: &mut Vec<SupplyIssue>) {
+        let ledger_tx = &tx.mantle_tx.ledger_tx;
+        if ledger_tx.inputs.is_empty() && ledger_tx.outputs.is_empty() {
+            return;
+        }
+        let tx_hash = tx.hash();
+        let ledger_hash = ledger_tx.hash();
+        self.transactions += 1;
+
+        let mut inputs = 0u64;
+        for note in &ledger_tx.inputs {
+            if let Some(value) = self.unspent.remove(note) {
+                inputs = inputs.saturating_add(value);
+                self.spent.insert(*note, tx_hash);
+            } else if let Some(spender) = self.spent.get(note) {
+                issues.push(SupplyIssue::DoubleSpend {
+                    tx: tx_hash,
+                    note: *note,
+                    spender: *spender,
+                });
+            } else {
+                issues.push(SupplyIssue::UnknownInput {
+                    tx: tx_hash,
+                    note: *note,
+                });
+            }
+        }
+
+        let mut outputs = 0u64;
+        for (idx, note) in ledger_tx.outputs.iter().enumerate() {
+            outputs = outputs.saturating_add(note.value);
+            self.unspent
+                .insert(Utxo::new(ledger_hash, idx, *note).id(), note.value);
+        }
+
+        // Whatever the outputs leave unclaimed is paid as fees.
+        match inputs.checked_sub(outputs) {
+            Some(fee) => self.fees = self.fees.saturating_add(fee),
+            None => issues.push(SupplyIssue::Inflation {
+                tx: tx_hash,
+                inputs,
+                outputs,
+            }),
+        }
+    }
+}
+
+impl SupplyConservation {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn replay(
+        &self,
+        client: &ApiClient,
+        blocks: &CapturedBlocks,
+    ) -> Result<(ReplaySummary, Vec<SupplyIssue>), DynError> {
+        let genesis = find_genesis(client)
+            .await
+            .map_err(|_| SupplyConservationError::MissingGenesis)?;
+        let tip = client.consensus_info().await?.tip;
+        let mut headers = client.consensus_headers(Some(tip), Some(genesis)).await?;
+        headers.reverse();
+
+        let mut ledger = Ledger::seed(&self.genesis_utxos);
+        let genesis_supply = ledger.supply();
+        let mut issues = Vec::new();
+        let mut replayed = 0;
+        for header in headers.into_iter().filter(|header| *header != genesis) {
+            let captured = blocks
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .get(&header)
+                .cloned();
+            // Blocks produced before capture started are fetched on demand.
+            let block = match captured {
+                Some(block) => block,
+                None => Arc::new(
+                    client
+                        .storage_block(&header)
+                        .await?
+                        .ok_or(SupplyConservationError::MissingBlock { header })?,
+                ),
+            };
+            for tx in block.transactions() {
+                ledger.apply(tx, &mut issues);
+            }
+            replayed += 1;
+        }
+
+        let final_supply = ledger.supply();
+        if final_supply.saturating_add(ledger.fees) != genesis_supply {
+            issues.push(SupplyIssue::Mismatch {
+                genesis: genesis_supply,
+                final_supply,
+                fees: ledger.fees,
+            });
+        }
+
+        let summary = ReplaySummary {
+            genesis_supply,
+            final_supply,
+            fees: ledger.fees,
+            blocks: replayed,
+            transactions: ledger.transactions,
+            violations: issues.len(),
+        };
+        Ok((summary, issues))
+    }
+}
+
+#[async_trait]
+impl Expectation for SupplyConservation {
+    fn name(&self) -> &'static str {
+        "supply_conservation"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.blocks.is_some() {
+            return Ok(());
+        }
+
+        if let Some(node) = ctx.descriptors().nodes().first() {
+            let genesis_tx = &node.general.consensus_config.genesis_tx;
+            let ledger_tx = &genesis_tx.mantle_tx().ledger_tx;
+            let tx_hash = ledger_tx.hash();
+            self.genesis_utxos = ledger_tx
+                .outputs
+                .iter()
+                .enumerate()
+                .map(|(idx, note)| Utxo::new(tx_hash, idx, *note))
+                .collect();
+        }
+
+        let blocks = CapturedBlocks::default();
+        spawn_block_capture(ctx.block_feed().subscribe(), Arc::clone(&blocks));
+        self.blocks = Some(blocks);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let blocks = self
+            .blocks
+            .clone()
+            .ok_or(SupplyConservationError::NotCaptured)?;
+        let client = ctx
+            .random_node_client(&mut ctx.rng(self.name()))
+            .ok_or(SupplyConservationError::MissingParticipants)?;
+
+        let (summary, issues) = self.replay(&client, &blocks).await?;
+        self.replay = Some(summary);
+
+        if issues.is_empty() {
+            tracing::info!(?summary, "supply conservation expectation satisfied");
+            return Ok(());
+        }
+
+        for issue in &issues {
+            tracing::warn!(?issue, "supply conservation issue");
+        }
+        let message = issues
+            .iter()
+            .map(|issue| format!("- {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(Box::new(SupplyConservationError::Violations { message }))
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        let summary = self.replay?;
+        Some(serde_json::json!({
+            "genesis_supply": summary.genesis_supply,
+            "final_supply": summary.final_supply,
+            "fees": summary.fees,
+            "blocks": summary.blocks,
+            "transactions": summary.transactions,
+            "violations": summary.violations,
+        }))
+    }
+}
+
+fn spawn_block_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    blocks: CapturedBlocks,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    blocks
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .insert(record.header, Arc::clone(&record.block));
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "supply conservation capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::debug!("supply conservation capture feed closed");
+                    break;
+                }
+            }
+        }
+    });
+}
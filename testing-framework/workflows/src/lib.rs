@@ -1,8 +1,10 @@
 pub mod builder;
 pub mod expectations;
+pub mod spec;
 pub mod util;
 pub mod workloads;
 
 pub use builder::{ChaosBuilderExt, ObservabilityBuilderExt, ScenarioBuilderExt};
-pub use expectations::ConsensusLiveness;
+pub use expectations::{ConsensusLiveness, ConsensusSafety, MaxReorgDepth};
+pub use spec::register_spec_components;
 pub use workloads::transaction::TxInclusionExpectation;
@@ -0,0 +1,407 @@
+//! Spec file support: registers this crate's workloads and expectations with
+//! a [`SpecRegistry`] so they can be listed in scenario files.
+
+use std::{num::NonZeroUsize, time::Duration};
+
+use serde::Deserialize;
+use testing_framework_core::scenario::{
+    ComponentSpec, DynError, LinkConditions, SpecBuilder, SpecRegistry,
+};
+
+use crate::{
+    builder::{BuilderInputError, ChaosBuilderExt as _, ScenarioBuilderExt as _},
+    expectations::{
+        FinalityLag, LeaderFairness, LogScanExpectation, PeerConnectivity,
+        ResourceUsageExpectation, SlotOccupancy,
+    },
+    workloads::transaction::Arrivals,
+};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransactionsParams {
+    rate: Option<u64>,
+    users: Option<usize>,
+    #[serde(default)]
+    sustained: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OpenLoopParams {
+    tps: f64,
+    #[serde(default)]
+    arrivals: ArrivalsParam,
+    /// Burst size for `arrivals = "bursty"`.
+    burst_size: Option<usize>,
+    duration_secs: Option<u64>,
+    max_in_flight: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ArrivalsParam {
+    #[default]
+    Constant,
+    Poisson,
+    Bursty,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChaosRestartParams {
+    min_delay_secs: Option<u64>,
+    max_delay_secs: Option<u64>,
+    target_cooldown_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChaosPartitionParams {
+    groups: Option<usize>,
+    delay_secs: Option<u64>,
+    window_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LinkShapingParams {
+    #[serde(default)]
+    delay_ms: u64,
+    #[serde(default)]
+    jitter_ms: u64,
+    #[serde(default)]
+    loss: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChannelInscriptionsParams {
+    channels: usize,
+    messages_per_channel: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DoubleSpendsParams {
+    pairs: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InvalidTransactionsParams {
+    rounds: usize,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaxReorgDepthParams {
+    max_depth: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SlotOccupancyParams {
+    tolerance: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FinalityLagParams {
+    max_gap: Option<u64>,
+    max_lib_stall_slots: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PeerConnectivityParams {
+    max_degraded_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CleanLogsParams {
+    #[serde(default)]
+    patterns: Vec<String>,
+    #[serde(default = "default_true")]
+    default_patterns: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ResourceUsageParams {
+    max_cpu_percent: Option<f64>,
+    max_rss_mb: Option<u64>,
+    max_open_fds: Option<u64>,
+    max_disk_mb: Option<u64>,
+    max_rss_growth_mb_per_hour: Option<f64>,
+    max_open_fds_growth_per_hour: Option<f64>,
+    max_disk_growth_mb_per_hour: Option<f64>,
+    warmup_secs: Option<u64>,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+/// Register every workload and expectation of this crate:
+///
+/// - workloads: `transactions`, `open_loop`, `chaos_restart`,
+///   `chaos_partition`, `link_shaping`, `channel_inscriptions`,
+///   `double_spends`, `invalid_transactions`
+/// - expectations: `consensus_liveness`, `consensus_safety`, `max_reorg_depth`,
+///   `supply_conservation`, `slot_occupancy`, `leader_fairness`,
+///   `finality_lag`, `peer_connectivity`, `clean_logs`, `resource_usage`
+pub fn register_spec_components(registry: &mut SpecRegistry) {
+    registry
+        .register_workload("transactions", apply_transactions)
+        .register_workload("open_loop", apply_open_loop)
+        .register_workload("chaos_restart", |builder, spec| {
+            let params = spec.params::<ChaosRestartParams>()?;
+            Ok(builder.chaos_with(|chaos| {
+                let mut restart = chaos.restart();
+                if let Some(secs) = params.min_delay_secs {
+                    restart = restart.min_delay(Duration::from_secs(secs));
+                }
+                if let Some(secs) = params.max_delay_secs {
+                    restart = restart.max_delay(Duration::from_secs(secs));
+                }
+                if let Some(secs) = params.target_cooldown_secs {
+                    restart = restart.target_cooldown(Duration::from_secs(secs));
+                }
+                restart.apply()
+            }))
+        })
+        .register_workload("chaos_partition", |builder, spec| {
+            let params = spec.params::<ChaosPartitionParams>()?;
+            Ok(builder.chaos_with(|chaos| {
+                let mut partition = chaos.partition();
+                if let Some(groups) = params.groups {
+                    partition = partition.groups(groups);
+                }
+                if let Some(secs) = params.delay_secs {
+                    partition = partition.delay(Duration::from_secs(secs));
+                }
+                if let Some(secs) = params.window_secs {
+                    partition = partition.window(Duration::from_secs(secs));
+                }
+                partition.apply()
+            }))
+        })
+        .register_workload("link_shaping", |builder, spec| {
+            let params = spec.params::<LinkShapingParams>()?;
+            let conditions = LinkConditions {
+                delay: Duration::from_millis(params.delay_ms),
+                jitter: Duration::from_millis(params.jitter_ms),
+                loss: params.loss,
+            };
+            Ok(builder
+                .chaos_with(|chaos| chaos.link_shaping().default_conditions(conditions).apply()))
+        })
+        .register_workload("channel_inscriptions", |builder, spec| {
+            let params = spec.params::<ChannelInscriptionsParams>()?;
+            Ok(builder.channel_inscriptions(params.channels, params.messages_per_channel))
+        })
+        .register_workload("double_spends", |builder, spec| {
+            let params = spec.params::<DoubleSpendsParams>()?;
+            Ok(builder.double_spends(params.pairs))
+        })
+        .register_workload("invalid_transactions", |builder, spec| {
+            let params = spec.params::<InvalidTransactionsParams>()?;
+            Ok(builder.invalid_transactions(params.rounds))
+        });
+
+    registry
+        .register_expectation("consensus_liveness", |builder, _| {
+            Ok(builder.expect_consensus_liveness())
+        })
+        .register_expectation("consensus_safety", |builder, _| {
+            Ok(builder.expect_consensus_safety())
+        })
+        .register_expectation("max_reorg_depth", |builder, spec| {
+            let params = spec.params::<MaxReorgDepthParams>()?;
+            Ok(builder.expect_max_reorg_depth(params.max_depth))
+        })
+        .register_expectation("supply_conservation", |builder, _| {
+            Ok(builder.expect_supply_conservation())
+        })
+        .register_expectation("slot_occupancy", |builder, spec| {
+            let params = spec.params::<SlotOccupancyParams>()?;
+            let expectation = params
+                .tolerance
+                .map_or_else(SlotOccupancy::new, |tolerance| {
+                    SlotOccupancy::new().with_tolerance(tolerance)
+                });
+            Ok(builder.with_expectation(expectation))
+        })
+        .register_expectation("leader_fairness", |builder, _| {
+            Ok(builder.with_expectation(LeaderFairness::new()))
+        })
+        .register_expectation("finality_lag", |builder, spec| {
+            let params = spec.params::<FinalityLagParams>()?;
+            let mut expectation = FinalityLag::new();
+            if let Some(blocks) = params.max_gap {
+                expectation = expectation.with_max_gap(blocks);
+            }
+            if let Some(slots) = params.max_lib_stall_slots {
+                expectation = expectation.with_max_lib_stall(slots);
+            }
+            Ok(builder.with_expectation(expectation))
+        })
+        .register_expectation("peer_connectivity", |builder, spec| {
+            let params = spec.params::<PeerConnectivityParams>()?;
+            let mut expectation = PeerConnectivity::new();
+            if let Some(secs) = params.max_degraded_secs {
+                expectation = expectation.with_max_degraded(Duration::from_secs(secs));
+            }
+            Ok(builder.with_expectation(expectation))
+        })
+        .register_expectation("clean_logs", |builder, spec| {
+            let params = spec.params::<CleanLogsParams>()?;
+            let mut expectation = LogScanExpectation::new();
+            if !params.default_patterns {
+                expectation = expectation.without_default_patterns();
+            }
+            for pattern in params.patterns {
+                expectation = expectation.with_pattern(pattern);
+            }
+            Ok(builder.with_expectation(expectation))
+        })
+        .register_expectation("resource_usage", apply_resource_usage);
+}
+
+fn apply_transactions(builder: SpecBuilder, spec: &ComponentSpec) -> Result<SpecBuilder, DynError> {
+    let params = spec.params::<TransactionsParams>()?;
+    let mut flow = builder.transactions();
+    if let Some(rate) = params.rate {
+        flow = flow.try_rate(rate)?;
+    }
+    if let Some(users) = params.users {
+        flow = flow.try_users(users)?;
+    }
+    if params.sustained {
+        flow = flow.sustained();
+    }
+    Ok(flow.apply())
+}
+
+fn apply_open_loop(builder: SpecBuilder, spec: &ComponentSpec) -> Result<SpecBuilder, DynError> {
+    let params = spec.params::<OpenLoopParams>()?;
+    let arrivals = match params.arrivals {
+        ArrivalsParam::Constant => Arrivals::Constant,
+        ArrivalsParam::Poisson => Arrivals::Poisson,
+        ArrivalsParam::Bursty => {
+            let size = params.burst_size.and_then(NonZeroUsize::new).ok_or(
+                BuilderInputError::ZeroValue {
+                    field: "burst_size",
+                },
+            )?;
+            Arrivals::Bursty { size }
+        }
+    };
+
+    let mut flow = builder
+        .transactions()
+        .try_tps(params.tps)?
+        .arrivals(arrivals);
+    if let Some(secs) = params.duration_secs {
+        flow = flow.load_duration(Duration::from_secs(secs));
+    }
+    if let Some(max_in_flight) = params.max_in_flight {
+        flow = flow.max_in_flight(max_in_flight);
+    }
+    Ok(flow.apply())
+}
+
+fn apply_resource_usage(
+    builder: SpecBuilder,
+    spec: &ComponentSpec,
+) -> Result<SpecBuilder, DynError> {
+    let params = spec.params::<ResourceUsageParams>()?;
+    let mut expectation = ResourceUsageExpectation::new();
+    if let Some(percent) = params.max_cpu_percent {
+        expectation = expectation.with_max_cpu_percent(percent);
+    }
+    if let Some(mb) = params.max_rss_mb {
+        expectation = expectation.with_max_rss_mb(mb);
+    }
+    if let Some(fds) = params.max_open_fds {
+        expectation = expectation.with_max_open_fds(fds);
+    }
+    if let Some(mb) = params.max_disk_mb {
+        expectation = expectation.with_max_disk_mb(mb);
+    }
+    if let Some(mb) = params.max_rss_growth_mb_per_hour {
+        expectation = expectation.with_max_rss_growth_mb_per_hour(mb);
+    }
+    if let Some(fds) = params.max_open_fds_growth_per_hour {
+        expectation = expectation.with_max_open_fds_growth_per_hour(fds);
+    }
+    if let Some(mb) = params.max_disk_growth_mb_per_hour {
+        expectation = expectation.with_max_disk_growth_mb_per_hour(mb);
+    }
+    if let Some(secs) = params.warmup_secs {
+        expectation = expectation.with_warmup(Duration::from_secs(secs));
+    }
+    Ok(builder.with_expectation(expectation))
+}
+
+#[cfg(test)]
+mod tests {
+    use testing_framework_core::scenario::{DeployerSpec, ScenarioSpec, SpecError};
+
+    use super::*;
+
+    const SPEC: &str = r#"
+duration_secs = 120
+wallets = 20
+deployer = "compose"
+
+[topology]
+nodes = 3
+network = "chain"
+
+[[workloads]]
+kind = "transactions"
+rate = 2
+users = 10
+
+[[workloads]]
+kind = "chaos_restart"
+min_delay_secs = 20
+
+[[expectations]]
+kind = "consensus_liveness"
+
+[[expectations]]
+kind = "clean_logs"
+patterns = ["ERROR .*storage"]
+"#;
+
+    #[test]
+    fn spec_builds_registered_components_and_rejects_unknown_kinds() {
+        let mut registry = SpecRegistry::new();
+        register_spec_components(&mut registry);
+
+        let spec = ScenarioSpec::from_toml_str(SPEC).expect("spec parses");
+        assert_eq!(spec.deployer, DeployerSpec::Compose);
+        assert_eq!(spec.workloads[1].kind, "chaos_restart");
+        assert!(registry.builder(&spec).is_ok());
+
+        let mut unknown = spec.clone();
+        unknown.expectations[0].kind = "consensus_vibes".to_owned();
+        assert!(matches!(
+            registry.builder(&unknown),
+            Err(SpecError::UnknownExpectation { .. })
+        ));
+
+        let mut invalid = spec;
+        invalid.workloads[0]
+            .params
+            .insert("rate".to_owned(), serde_json::json!(0));
+        assert!(matches!(
+            registry.builder(&invalid),
+            Err(SpecError::Component { .. })
+        ));
+    }
+}
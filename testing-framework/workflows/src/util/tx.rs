@@ -9,15 +9,32 @@ use testing_framework_core::scenario::DynError;
 /// Builds a signed inscription transaction with deterministic payload for
 /// testing.
 pub fn create_inscription_transaction_with_id(id: ChannelId) -> Result<SignedMantleTx, DynError> {
-    let signing_key = Ed25519Key::from_bytes(&[0u8; 32]);
-    let signer = signing_key.public_key();
-
-    let inscription_op = InscriptionOp {
-        channel_id: id,
-        inscription: format!("Test channel inscription {id:?}").into_bytes(),
-        parent: MsgId::root(),
-        signer,
-    };
+    let inscription_op = inscription_op(
+        id,
+        MsgId::root(),
+        format!("Test channel inscription {id:?}").into_bytes(),
+    );
+    create_inscription_transaction(inscription_op)
+}
+
+/// Builds an inscription for `channel_id` chained after `parent`, signed by
+/// the test signing key.
+#[must_use]
+pub fn inscription_op(channel_id: ChannelId, parent: MsgId, inscription: Vec<u8>) -> InscriptionOp {
+    InscriptionOp {
+        channel_id,
+        inscription,
+        parent,
+        signer: test_signing_key().public_key(),
+    }
+}
+
+/// Wraps an inscription built by [`inscription_op`] in a signed transaction.
+pub fn create_inscription_transaction(
+    inscription_op: InscriptionOp,
+) -> Result<SignedMantleTx, DynError> {
+    let signing_key = test_signing_key();
+    let id = inscription_op.channel_id;
 
     let mantle_tx = MantleTx {
         ops: vec![Op::ChannelInscribe(inscription_op)],
@@ -41,3 +58,7 @@ pub fn create_inscription_transaction_with_id(id: ChannelId) -> Result<SignedMan
     )
     .map_err(|err| format!("failed to build signed mantle transaction: {err}").into())
 }
+
+fn test_signing_key() -> Ed25519Key {
+    Ed25519Key::from_bytes(&[0u8; 32])
+}
@@ -0,0 +1,197 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use nomos_core::mantle::ops::channel::{ChannelId, MsgId};
+use testing_framework_core::scenario::{BlockRecord, DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::{sync::broadcast, time::sleep};
+
+use super::workload::{channel_messages, message_chain, scenario_channels};
+
+const CATCHUP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_CATCHUP_WAIT: Duration = Duration::from_secs(60);
+
+/// Inscriptions observed per channel, in block feed order, as
+/// `(message, parent)`.
+type Observed = Arc<Mutex<Vec<Vec<(MsgId, MsgId)>>>>;
+
+/// Checks that every message of the channel workload landed and that each
+/// channel's messages were included in parent order.
+#[derive(Clone)]
+pub struct ChannelOrderExpectation {
+    channel_count: NonZeroUsize,
+    channels: Vec<ChannelId>,
+    messages_per_channel: NonZeroUsize,
+    observed: Option<Observed>,
+}
+
+#[derive(Debug, Error)]
+enum ChannelExpectationError {
+    #[error("channel order expectation not captured")]
+    NotCaptured,
+    #[error(
+        "channel {channel:?}: message {message:?} at position {position} has parent {parent:?}, expected {expected:?}"
+    )]
+    OutOfOrder {
+        channel: ChannelId,
+        position: usize,
+        message: MsgId,
+        parent: MsgId,
+        expected: MsgId,
+    },
+    #[error("channel {channel:?}: {landed}/{expected} messages landed")]
+    Missing {
+        channel: ChannelId,
+        landed: usize,
+        expected: usize,
+    },
+}
+
+impl ChannelOrderExpectation {
+    pub const NAME: &'static str = "channel_order_expectation";
+
+    /// Constructs an expectation for the given channel count and chain length.
+    #[must_use]
+    pub const fn new(channels: NonZeroUsize, messages_per_channel: NonZeroUsize) -> Self {
+        Self {
+            channel_count: channels,
+            channels: Vec::new(),
+            messages_per_channel,
+            observed: None,
+        }
+    }
+
+    fn landed(&self, observed: &Observed) -> usize {
+        lock(observed).iter().map(Vec::len).sum()
+    }
+
+    fn check(&self, observed: &Observed) -> Result<(), ChannelExpectationError> {
+        let observed = lock(observed);
+        for (channel, landed) in self.channels.iter().zip(observed.iter()) {
+            let mut expected_parent = MsgId::root();
+            for (position, (message, parent)) in landed.iter().enumerate() {
+                if *parent != expected_parent {
+                    return Err(ChannelExpectationError::OutOfOrder {
+                        channel: *channel,
+                        position,
+                        message: *message,
+                        parent: *parent,
+                        expected: expected_parent,
+                    });
+                }
+                expected_parent = *message;
+            }
+
+            let expected = message_chain(*channel, self.messages_per_channel);
+            let matched = expected
+                .iter()
+                .zip(landed.iter())
+                .take_while(|(op, (message, _))| op.id() == *message)
+                .count();
+            if matched < expected.len() {
+                return Err(ChannelExpectationError::Missing {
+                    channel: *channel,
+                    landed: matched,
+                    expected: expected.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Expectation for ChannelOrderExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.observed.is_some() {
+            return Ok(());
+        }
+
+        self.channels = scenario_channels(ctx, self.channel_count);
+        tracing::info!(
+            channels = self.channels.len(),
+            messages_per_channel = self.messages_per_channel.get(),
+            "channel order expectation starting capture"
+        );
+
+        let observed: Observed = Arc::new(Mutex::new(vec![Vec::new(); self.channels.len()]));
+        spawn_channel_capture(
+            ctx.block_feed().subscribe(),
+            self.channels.clone(),
+            Arc::clone(&observed),
+        );
+        self.observed = Some(observed);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+        let observed = self
+            .observed
+            .clone()
+            .ok_or(ChannelExpectationError::NotCaptured)?;
+
+        let expected = self.channels.len() * self.messages_per_channel.get();
+        let mut remaining = MAX_CATCHUP_WAIT;
+        while self.landed(&observed) < expected && remaining > Duration::ZERO {
+            sleep(CATCHUP_POLL_INTERVAL).await;
+            remaining = remaining.saturating_sub(CATCHUP_POLL_INTERVAL);
+        }
+
+        if let Err(err) = self.check(&observed) {
+            tracing::warn!(error = %err, "channel order expectation failed");
+            return Err(err.into());
+        }
+
+        tracing::info!(
+            channels = self.channels.len(),
+            messages = expected,
+            "channel order expectation satisfied"
+        );
+        Ok(())
+    }
+}
+
+fn spawn_channel_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    channels: Vec<ChannelId>,
+    observed: Observed,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    for (channel, message, parent) in channel_messages(&record.block) {
+                        let Some(index) = channels.iter().position(|id| *id == channel) else {
+                            continue;
+                        };
+                        let mut observed = lock(&observed);
+                        if !observed[index].iter().any(|(seen, _)| *seen == message) {
+                            observed[index].push((message, parent));
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "channel capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::debug!("channel capture feed closed");
+                    break;
+                }
+            }
+        }
+    });
+}
+
+fn lock(observed: &Observed) -> std::sync::MutexGuard<'_, Vec<Vec<(MsgId, MsgId)>>> {
+    observed
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
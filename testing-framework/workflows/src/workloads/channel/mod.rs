@@ -0,0 +1,5 @@
+mod expectation;
+mod workload;
+
+pub use expectation::ChannelOrderExpectation;
+pub use workload::Workload;
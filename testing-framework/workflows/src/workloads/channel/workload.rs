@@ -0,0 +1,296 @@
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use nomos_core::{
+    block::Block,
+    header::HeaderId,
+    mantle::{
+        AuthenticatedMantleTx as _, SignedMantleTx,
+        ops::{
+            Op,
+            channel::{ChannelId, MsgId, inscribe::InscriptionOp},
+        },
+    },
+};
+use rand::{Rng as _, rngs::StdRng};
+use testing_framework_core::scenario::{
+    ChainTree, DynError, Expectation, RunContext, Workload as ScenarioWorkload,
+};
+use tokio::{
+    sync::broadcast,
+    time::{Instant, MissedTickBehavior, interval},
+};
+
+use super::expectation::ChannelOrderExpectation;
+use crate::{
+    util::tx::{create_inscription_transaction, inscription_op},
+    workloads::util::submit_transaction_via_cluster,
+};
+
+const DEFAULT_MESSAGES_PER_CHANNEL: usize = 5;
+const RESUBMIT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const FALLBACK_BLOCK_INTERVAL: Duration = Duration::from_secs(2);
+/// Blocks to wait for an inscription before submitting it again.
+const RESUBMIT_AFTER_BLOCKS: u32 = 10;
+/// Seed stream the channel ids are drawn from.
+const CHANNEL_SEED_STREAM: &str = "channel";
+
+/// Writes chains of inscriptions to one or more channels, each message
+/// linking to the previous one as its parent.
+///
+/// A message is only submitted once its parent has been observed in a block,
+/// so every chain is valid regardless of how transactions are batched. If
+/// every node reorgs away from the block a message landed in, the chain falls
+/// back to that message and submits it again.
+#[derive(Clone)]
+pub struct Workload {
+    channels: NonZeroUsize,
+    messages_per_channel: NonZeroUsize,
+}
+
+impl Workload {
+    /// Creates a workload writing to `channels` channels, chosen from the
+    /// scenario seed when the run starts.
+    #[must_use]
+    pub fn new(channels: NonZeroUsize) -> Self {
+        Self {
+            channels,
+            messages_per_channel: NonZeroUsize::new(DEFAULT_MESSAGES_PER_CHANNEL)
+                .expect("default message count is non-zero"),
+        }
+    }
+
+    /// Number of messages written to each channel.
+    #[must_use]
+    pub const fn with_messages_per_channel(mut self, messages: NonZeroUsize) -> Self {
+        self.messages_per_channel = messages;
+        self
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for Workload {
+    fn name(&self) -> &'static str {
+        "channel_workload"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(ChannelOrderExpectation::new(
+            self.channels,
+            self.messages_per_channel,
+        ))]
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let channels = scenario_channels(ctx, self.channels);
+        let resubmit_after = ctx
+            .run_metrics()
+            .block_interval_hint()
+            .unwrap_or(FALLBACK_BLOCK_INTERVAL)
+            * RESUBMIT_AFTER_BLOCKS;
+
+        tracing::info!(
+            channels = channels.len(),
+            messages_per_channel = self.messages_per_channel.get(),
+            "starting channel inscription workload"
+        );
+
+        let mut rng = ctx.rng(self.name());
+        let mut blocks = ctx.block_feed().subscribe();
+        let mut chains = channels
+            .iter()
+            .map(|channel| ChainProgress::new(message_chain(*channel, self.messages_per_channel)))
+            .collect::<Vec<_>>();
+
+        for chain in &mut chains {
+            chain.submit_next(ctx, &mut rng).await;
+        }
+
+        let mut resubmit = interval(RESUBMIT_CHECK_INTERVAL);
+        resubmit.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        while chains.iter().any(|chain| !chain.is_done()) {
+            tokio::select! {
+                record = blocks.recv() => match record {
+                    Ok(record) => {
+                        let landed = channel_messages(&record.block);
+                        for chain in &mut chains {
+                            if chain.observe(record.header, &landed) {
+                                chain.submit_next(ctx, &mut rng).await;
+                            }
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "channel workload lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err("channel workload: block feed closed".into());
+                    }
+                },
+                _ = resubmit.tick() => {
+                    let tree = ctx.block_feed().chain();
+                    for chain in &mut chains {
+                        if chain.fall_back(&tree) {
+                            tracing::warn!(
+                                channel = ?chain.channel(),
+                                position = chain.position(),
+                                "channel inscription reorged out; resubmitting"
+                            );
+                            chain.submit_next(ctx, &mut rng).await;
+                        } else if chain.needs_submit() || chain.is_stale(resubmit_after) {
+                            tracing::debug!(
+                                channel = ?chain.channel(),
+                                position = chain.position(),
+                                "resubmitting channel inscription"
+                            );
+                            chain.submit_next(ctx, &mut rng).await;
+                        }
+                    }
+                }
+            }
+        }
+
+        tracing::info!("channel inscription workload finished");
+        Ok(())
+    }
+}
+
+/// The `count` channels written by this run. Drawn from a dedicated seed
+/// stream, so the workload and its expectation agree on them without sharing
+/// state.
+pub(super) fn scenario_channels(ctx: &RunContext, count: NonZeroUsize) -> Vec<ChannelId> {
+    let mut rng = ctx.rng(CHANNEL_SEED_STREAM);
+    (0..count.get())
+        .map(|_| ChannelId::from(rng.r#gen::<[u8; 32]>()))
+        .collect()
+}
+
+/// Deterministic chain of inscriptions for `channel`, each parented on the
+/// previous message.
+pub(super) fn message_chain(channel: ChannelId, messages: NonZeroUsize) -> Vec<InscriptionOp> {
+    let mut parent = MsgId::root();
+    (0..messages.get())
+        .map(|index| {
+            let op = inscription_op(
+                channel,
+                parent,
+                format!("channel {channel:?} message {index}").into_bytes(),
+            );
+            parent = op.id();
+            op
+        })
+        .collect()
+}
+
+/// Every channel inscription in `block`, as `(channel, message, parent)`.
+pub(super) fn channel_messages(block: &Block<SignedMantleTx>) -> Vec<(ChannelId, MsgId, MsgId)> {
+    let mut landed = Vec::new();
+    for tx in block.transactions() {
+        for op in &tx.mantle_tx().ops {
+            if let Op::ChannelInscribe(inscription) = op {
+                landed.push((inscription.channel_id, inscription.id(), inscription.parent));
+            }
+        }
+    }
+    landed
+}
+
+struct ChainProgress {
+    messages: Vec<InscriptionOp>,
+    /// Block each message observed on chain landed in, in order; its length
+    /// is the index of the pending message.
+    landed_in: Vec<HeaderId>,
+    /// When the pending message was last submitted; `None` until a
+    /// submission succeeds.
+    submitted_at: Option<Instant>,
+}
+
+impl ChainProgress {
+    const fn new(messages: Vec<InscriptionOp>) -> Self {
+        Self {
+            messages,
+            landed_in: Vec::new(),
+            submitted_at: None,
+        }
+    }
+
+    fn channel(&self) -> ChannelId {
+        self.messages[0].channel_id
+    }
+
+    fn position(&self) -> usize {
+        self.landed_in.len()
+    }
+
+    fn is_done(&self) -> bool {
+        self.position() >= self.messages.len()
+    }
+
+    fn needs_submit(&self) -> bool {
+        !self.is_done() && self.submitted_at.is_none()
+    }
+
+    fn is_stale(&self, timeout: Duration) -> bool {
+        !self.is_done()
+            && self
+                .submitted_at
+                .is_some_and(|submitted| submitted.elapsed() >= timeout)
+    }
+
+    /// Advance past the pending message if it landed in block `header`;
+    /// returns true when it did.
+    fn observe(&mut self, header: HeaderId, landed: &[(ChannelId, MsgId, MsgId)]) -> bool {
+        let Some(pending) = self.messages.get(self.position()) else {
+            return false;
+        };
+        let pending_id = pending.id();
+        if landed
+            .iter()
+            .any(|(channel, id, _)| *channel == pending.channel_id && *id == pending_id)
+        {
+            self.landed_in.push(header);
+            self.submitted_at = None;
+            return true;
+        }
+        false
+    }
+
+    /// Step back past messages whose block every node has reorged away from;
+    /// returns true when it did.
+    fn fall_back(&mut self, tree: &ChainTree) -> bool {
+        let position = self.position();
+        while self
+            .landed_in
+            .last()
+            .is_some_and(|header| tree.is_orphaned(*header))
+        {
+            self.landed_in.pop();
+        }
+        if self.position() == position {
+            return false;
+        }
+        self.submitted_at = None;
+        true
+    }
+
+    /// Submit the pending message. Failures are logged and retried on the
+    /// next resubmit check.
+    async fn submit_next(&mut self, ctx: &RunContext, rng: &mut StdRng) {
+        let Some(op) = self.messages.get(self.position()) else {
+            return;
+        };
+        let result = match create_inscription_transaction(op.clone()) {
+            Ok(tx) => submit_transaction_via_cluster(ctx, rng, Arc::new(tx)).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(()) => self.submitted_at = Some(Instant::now()),
+            Err(err) => tracing::warn!(
+                channel = ?self.channel(),
+                position = self.position(),
+                error = %err,
+                "channel inscription submission failed; retrying"
+            ),
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use std::{collections::HashMap, time::Duration};
 
 use async_trait::async_trait;
-use rand::{Rng as _, seq::SliceRandom as _, thread_rng};
-use testing_framework_core::scenario::{DynError, RunContext, Workload};
+use rand::{Rng as _, rngs::StdRng, seq::SliceRandom as _};
+use testing_framework_core::{
+    scenario::{DynError, RunContext, Workload},
+    topology::configs::network::Libp2pNetworkLayout,
+};
 use tokio::time::{Instant, sleep};
 use tracing::info;
 
@@ -53,7 +56,7 @@ impl RandomRestartWorkload {
         targets
     }
 
-    fn random_delay(&self) -> Duration {
+    fn random_delay(&self, rng: &mut StdRng) -> Duration {
         if self.max_delay <= self.min_delay {
             return self.min_delay;
         }
@@ -62,7 +65,7 @@ impl RandomRestartWorkload {
             .checked_sub(self.min_delay)
             .unwrap_or(MIN_DELAY_SPREAD_FALLBACK)
             .as_secs_f64();
-        let offset = thread_rng().gen_range(0.0..=spread);
+        let offset = rng.gen_range(0.0..=spread);
         let delay = self
             .min_delay
             .checked_add(Duration::from_secs_f64(offset))
@@ -85,6 +88,7 @@ impl RandomRestartWorkload {
         &self,
         targets: &[Target],
         cooldowns: &HashMap<Target, Instant>,
+        rng: &mut StdRng,
     ) -> Result<Target, DynError> {
         if targets.is_empty() {
             return Err("chaos restart workload has no eligible targets".into());
@@ -115,12 +119,12 @@ impl RandomRestartWorkload {
                 .filter(|target| cooldowns.get(target).is_none_or(|ready| *ready <= now))
                 .collect();
 
-            if let Some(choice) = available.choose(&mut thread_rng()).copied() {
+            if let Some(choice) = available.choose(rng).copied() {
                 tracing::debug!(?choice, "chaos restart picked target");
                 return Ok(choice);
             }
 
-            if let Some(choice) = targets.choose(&mut thread_rng()).copied() {
+            if let Some(choice) = targets.choose(rng).copied() {
                 return Ok(choice);
             }
             return Err("chaos restart workload has no eligible targets".into());
@@ -152,10 +156,11 @@ impl Workload for RandomRestartWorkload {
         );
 
         let mut cooldowns = self.initialize_cooldowns(&targets);
+        let mut rng = ctx.rng(self.name());
 
         loop {
-            sleep(self.random_delay()).await;
-            let target = self.pick_target(&targets, &cooldowns).await?;
+            sleep(self.random_delay(&mut rng)).await;
+            let target = self.pick_target(&targets, &cooldowns, &mut rng).await?;
 
             match target {
                 Target::Node(index) => {
@@ -176,3 +181,164 @@ impl Workload for RandomRestartWorkload {
 enum Target {
     Node(usize),
 }
+
+/// Splits the nodes into isolated groups for a window, then heals the split.
+#[derive(Debug)]
+pub struct NetworkPartitionWorkload {
+    groups: usize,
+    delay: Duration,
+    window: Duration,
+}
+
+impl NetworkPartitionWorkload {
+    /// Creates a partition workload.
+    ///
+    /// After `delay`, nodes are split into `groups` groups that cannot reach
+    /// each other; the partition is healed after `window`. Each group keeps
+    /// the initial-peer links between its members, so it stays connected.
+    /// Star layouts keep the hub with most nodes and cut off single leaves,
+    /// since leaves only link to the hub.
+    #[must_use]
+    pub const fn new(groups: usize, delay: Duration, window: Duration) -> Self {
+        Self {
+            groups,
+            delay,
+            window,
+        }
+    }
+
+    fn partition_groups(&self, layout: &Libp2pNetworkLayout, node_count: usize) -> Vec<Vec<usize>> {
+        let group_count = self.groups.min(node_count);
+        if group_count == 0 {
+            return Vec::new();
+        }
+        match layout {
+            Libp2pNetworkLayout::Star => {
+                let hub_group_len = node_count - (group_count - 1);
+                let mut groups = vec![(0..hub_group_len).collect::<Vec<_>>()];
+                groups.extend((hub_group_len..node_count).map(|leaf| vec![leaf]));
+                groups
+            }
+            // Chain links each node to the previous one and full links every
+            // pair, so contiguous index ranges stay connected.
+            Libp2pNetworkLayout::Chain | Libp2pNetworkLayout::Full => {
+                let base = node_count / group_count;
+                let larger = node_count % group_count;
+                let mut start = 0;
+                (0..group_count)
+                    .map(|group| {
+                        let len = base + usize::from(group < larger);
+                        let range = (start..start + len).collect();
+                        start += len;
+                        range
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Workload for NetworkPartitionWorkload {
+    fn name(&self) -> &'static str {
+        "chaos_partition"
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos partition workload requires node control".to_owned())?;
+
+        let node_count = ctx.descriptors().nodes().len();
+        if node_count < 2 || self.groups < 2 {
+            return Err(format!(
+                "chaos partition needs at least two groups and two nodes (groups={}, nodes={node_count})",
+                self.groups
+            )
+            .into());
+        }
+
+        let layout = &ctx
+            .descriptors()
+            .config()
+            .network_params
+            .libp2p_network_layout;
+        let groups = self.partition_groups(layout, node_count);
+        tracing::info!(config = ?self, ?groups, "starting chaos partition workload");
+
+        sleep(self.delay).await;
+
+        handle
+            .partition(&groups)
+            .await
+            .map_err(|err| format!("network partition failed: {err}"))?;
+        tracing::info!(
+            ?groups,
+            window_secs = self.window.as_secs(),
+            "network partitioned"
+        );
+
+        sleep(self.window).await;
+
+        handle
+            .heal_partition()
+            .await
+            .map_err(|err| format!("partition heal failed: {err}"))?;
+        tracing::info!("network partition healed");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups(layout: &Libp2pNetworkLayout, groups: usize, nodes: usize) -> Vec<Vec<usize>> {
+        NetworkPartitionWorkload::new(groups, Duration::ZERO, Duration::ZERO)
+            .partition_groups(layout, nodes)
+    }
+
+    #[test]
+    fn chain_partition_uses_contiguous_ranges() {
+        assert_eq!(
+            groups(&Libp2pNetworkLayout::Chain, 2, 4),
+            vec![vec![0, 1], vec![2, 3]]
+        );
+        assert_eq!(
+            groups(&Libp2pNetworkLayout::Chain, 3, 7),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5, 6]]
+        );
+    }
+
+    #[test]
+    fn chain_partition_groups_stay_linked() {
+        for group in groups(&Libp2pNetworkLayout::Chain, 3, 10) {
+            assert!(group.windows(2).all(|pair| pair[1] == pair[0] + 1));
+        }
+    }
+
+    #[test]
+    fn star_partition_keeps_leaves_with_the_hub() {
+        assert_eq!(
+            groups(&Libp2pNetworkLayout::Star, 2, 4),
+            vec![vec![0, 1, 2], vec![3]]
+        );
+        assert_eq!(
+            groups(&Libp2pNetworkLayout::Star, 3, 5),
+            vec![vec![0, 1, 2], vec![3], vec![4]]
+        );
+    }
+
+    #[test]
+    fn partition_caps_groups_at_node_count() {
+        assert_eq!(
+            groups(&Libp2pNetworkLayout::Star, 4, 2),
+            vec![vec![0], vec![1]]
+        );
+        assert_eq!(
+            groups(&Libp2pNetworkLayout::Full, 5, 3),
+            vec![vec![0], vec![1], vec![2]]
+        );
+    }
+}
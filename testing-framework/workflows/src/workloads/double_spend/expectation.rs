@@ -0,0 +1,279 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use nomos_core::{
+    header::HeaderId,
+    mantle::{Transaction as _, TxHash},
+};
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{BlockRecord, DynError, Expectation, RunContext},
+};
+use thiserror::Error;
+use tokio::{sync::broadcast, time::sleep};
+
+use super::workload::{ConflictPair, SubmittedPairs};
+use crate::expectations::find_genesis;
+
+const CATCHUP_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_CATCHUP_WAIT: Duration = Duration::from_secs(60);
+
+/// Blocks in which each transaction was observed by the block feed.
+type Inclusions = Arc<Mutex<HashMap<TxHash, Vec<HeaderId>>>>;
+
+/// Checks that exactly one transaction of every conflicting pair was
+/// included, and that every node's chain contains that same one.
+pub struct DoubleSpendExpectation {
+    submitted: SubmittedPairs,
+    inclusions: Option<Inclusions>,
+}
+
+#[derive(Debug, Error)]
+enum DoubleSpendIssue {
+    #[error("wallet {wallet}: neither conflicting transaction was included")]
+    NotIncluded { wallet: usize },
+    #[error("node-{node} wallet {wallet}: {reason}")]
+    InvalidChain {
+        node: usize,
+        wallet: usize,
+        reason: &'static str,
+    },
+    #[error(
+        "wallet {wallet}: node-{node} included {included:?} but node-{other} included {other_included:?}"
+    )]
+    Disagreement {
+        wallet: usize,
+        node: usize,
+        included: TxHash,
+        other: usize,
+        other_included: TxHash,
+    },
+    #[error("node-{node} chain request failed: {source}")]
+    RequestFailed {
+        node: usize,
+        #[source]
+        source: DynError,
+    },
+}
+
+#[derive(Debug, Error)]
+enum DoubleSpendError {
+    #[error("double spend expectation not captured")]
+    NotCaptured,
+    #[error("double spend workload submitted no conflicting pairs")]
+    NothingSubmitted,
+    #[error("could not locate the genesis block")]
+    MissingGenesis,
+    #[error("double spend expectation violated:\n{message}")]
+    Violations { message: String },
+}
+
+impl DoubleSpendExpectation {
+    pub const NAME: &'static str = "double_spend_expectation";
+
+    pub(super) const fn new(submitted: SubmittedPairs) -> Self {
+        Self {
+            submitted,
+            inclusions: None,
+        }
+    }
+
+    async fn check(
+        pairs: &[ConflictPair],
+        inclusions: &Inclusions,
+        clients: &[ApiClient],
+        genesis: HeaderId,
+    ) -> Vec<DoubleSpendIssue> {
+        let mut issues = Vec::new();
+        // Blocks (possibly on several forks) holding each side of a pair.
+        let mut candidates = Vec::new();
+        {
+            let inclusions = inclusions
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            for pair in pairs {
+                let blocks = pair
+                    .txs
+                    .map(|tx| inclusions.get(&tx).cloned().unwrap_or_default());
+                if blocks.iter().all(Vec::is_empty) {
+                    issues.push(DoubleSpendIssue::NotIncluded {
+                        wallet: pair.wallet,
+                    });
+                } else {
+                    candidates.push((pair, blocks));
+                }
+            }
+        }
+        if !issues.is_empty() {
+            return issues;
+        }
+
+        // First node's pick for each pair, as `(node, tx)`.
+        let mut picks: Vec<Option<(usize, TxHash)>> = vec![None; candidates.len()];
+        for (node, client) in clients.iter().enumerate() {
+            let chain = match canonical_chain(client, genesis).await {
+                Ok(chain) => chain,
+                Err(source) => {
+                    issues.push(DoubleSpendIssue::RequestFailed { node, source });
+                    continue;
+                }
+            };
+            for ((pair, blocks), pick) in candidates.iter().zip(picks.iter_mut()) {
+                let on_chain = blocks
+                    .each_ref()
+                    .map(|blocks| blocks.iter().any(|block| chain.contains(block)));
+                let included = match on_chain {
+                    [true, false] => pair.txs[0],
+                    [false, true] => pair.txs[1],
+                    [false, false] => {
+                        issues.push(DoubleSpendIssue::InvalidChain {
+                            node,
+                            wallet: pair.wallet,
+                            reason: "neither conflicting transaction is on its chain",
+                        });
+                        continue;
+                    }
+                    [true, true] => {
+                        issues.push(DoubleSpendIssue::InvalidChain {
+                            node,
+                            wallet: pair.wallet,
+                            reason: "both conflicting transactions are on its chain",
+                        });
+                        continue;
+                    }
+                };
+                match pick {
+                    None => *pick = Some((node, included)),
+                    Some((other, other_included)) if *other_included != included => {
+                        issues.push(DoubleSpendIssue::Disagreement {
+                            wallet: pair.wallet,
+                            node,
+                            included,
+                            other: *other,
+                            other_included: *other_included,
+                        });
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        issues
+    }
+}
+
+#[async_trait]
+impl Expectation for DoubleSpendExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.inclusions.is_some() {
+            return Ok(());
+        }
+        let inclusions = Inclusions::default();
+        spawn_inclusion_capture(ctx.block_feed().subscribe(), Arc::clone(&inclusions));
+        self.inclusions = Some(inclusions);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let inclusions = self
+            .inclusions
+            .clone()
+            .ok_or(DoubleSpendError::NotCaptured)?;
+        let pairs = self
+            .submitted
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        if pairs.is_empty() {
+            return Err(DoubleSpendError::NothingSubmitted.into());
+        }
+
+        let clients = ctx.node_clients().all_clients();
+        let genesis = match clients.first() {
+            Some(client) => find_genesis(client)
+                .await
+                .map_err(|_| DoubleSpendError::MissingGenesis)?,
+            None => return Err(DoubleSpendError::MissingGenesis.into()),
+        };
+
+        // Late blocks may still settle pairs, so retry for a while before
+        // reporting.
+        let mut remaining = MAX_CATCHUP_WAIT;
+        let issues = loop {
+            let issues = Self::check(&pairs, &inclusions, &clients, genesis).await;
+            if issues.is_empty() || remaining.is_zero() {
+                break issues;
+            }
+            sleep(CATCHUP_POLL_INTERVAL).await;
+            remaining = remaining.saturating_sub(CATCHUP_POLL_INTERVAL);
+        };
+
+        if issues.is_empty() {
+            tracing::info!(
+                pairs = pairs.len(),
+                nodes = clients.len(),
+                "double spend expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        for issue in &issues {
+            tracing::warn!(?issue, "double spend issue");
+        }
+        let message = issues
+            .iter()
+            .map(|issue| format!("- {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(DoubleSpendError::Violations { message }.into())
+    }
+}
+
+/// Headers on the node's current chain, from its tip back to genesis.
+async fn canonical_chain(
+    client: &ApiClient,
+    genesis: HeaderId,
+) -> Result<HashSet<HeaderId>, DynError> {
+    let info = client.consensus_info().await?;
+    let headers = client
+        .consensus_headers(Some(info.tip), Some(genesis))
+        .await?;
+    Ok(headers.into_iter().collect())
+}
+
+fn spawn_inclusion_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    inclusions: Inclusions,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    let mut inclusions = inclusions
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    for tx in record.block.transactions() {
+                        let blocks = inclusions.entry(tx.hash()).or_default();
+                        if !blocks.contains(&record.header) {
+                            blocks.push(record.header);
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "double spend capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::debug!("double spend capture feed closed");
+                    break;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,5 @@
+mod expectation;
+mod workload;
+
+pub use expectation::DoubleSpendExpectation;
+pub use workload::Workload;
@@ -0,0 +1,167 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use nomos_core::mantle::{Note, SignedMantleTx, Transaction as _, TxHash};
+use testing_framework_core::{
+    scenario::{DynError, Expectation, RunContext, RunMetrics, Workload as ScenarioWorkload},
+    topology::generation::GeneratedTopology,
+};
+use tokio::time::sleep;
+
+use super::expectation::DoubleSpendExpectation;
+use crate::workloads::transaction::{
+    WalletInput, build_wallet_transaction_with_outputs, wallet_inputs,
+};
+
+const FALLBACK_PAIR_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Two transactions spending the same UTXO.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ConflictPair {
+    pub wallet: usize,
+    pub txs: [TxHash; 2],
+}
+
+/// Conflicting pairs submitted so far, shared with the expectation.
+pub(super) type SubmittedPairs = Arc<Mutex<Vec<ConflictPair>>>;
+
+/// Adversarial workload that spends each wallet's genesis UTXO twice.
+///
+/// For every wallet it signs two conflicting transactions (a plain self
+/// transfer and a split into two notes) and submits them at the same moment
+/// to two different nodes. Do not combine it with another workload spending
+/// the same wallets.
+#[derive(Clone)]
+pub struct Workload {
+    pair_limit: Option<NonZeroUsize>,
+    inputs: Vec<WalletInput>,
+    submitted: SubmittedPairs,
+}
+
+impl Workload {
+    /// Creates a workload that double-spends every seeded wallet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            pair_limit: None,
+            inputs: Vec::new(),
+            submitted: SubmittedPairs::default(),
+        }
+    }
+
+    /// Limits how many wallets are double-spent.
+    #[must_use]
+    pub const fn with_pair_limit(mut self, pairs: Option<NonZeroUsize>) -> Self {
+        self.pair_limit = pairs;
+        self
+    }
+}
+
+impl Default for Workload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for Workload {
+    fn name(&self) -> &'static str {
+        "double_spend_workload"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(DoubleSpendExpectation::new(Arc::clone(
+            &self.submitted,
+        )))]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if descriptors.nodes().len() < 2 {
+            return Err("double spend workload requires at least two nodes".into());
+        }
+        self.inputs = wallet_inputs(descriptors, self.pair_limit)?;
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let clients = ctx.node_clients().node_clients();
+        if clients.len() < 2 {
+            return Err("double spend workload requires at least two node clients".into());
+        }
+        let interval = ctx
+            .run_metrics()
+            .block_interval_hint()
+            .unwrap_or(FALLBACK_PAIR_INTERVAL);
+
+        tracing::info!(
+            pairs = self.inputs.len(),
+            interval_ms = interval.as_millis(),
+            "starting double spend workload"
+        );
+
+        for (wallet, input) in self.inputs.iter().enumerate() {
+            let [first, second] = conflicting_transactions(input)?;
+            let pair = ConflictPair {
+                wallet,
+                txs: [first.hash(), second.hash()],
+            };
+
+            let left = &clients[wallet % clients.len()];
+            let right = &clients[(wallet + 1) % clients.len()];
+            let (left_result, right_result) = tokio::join!(
+                left.submit_transaction(&first),
+                right.submit_transaction(&second)
+            );
+
+            tracing::debug!(
+                wallet,
+                first = ?pair.txs[0],
+                second = ?pair.txs[1],
+                first_accepted = left_result.is_ok(),
+                second_accepted = right_result.is_ok(),
+                "submitted conflicting transactions"
+            );
+
+            if left_result.is_err() && right_result.is_err() {
+                tracing::warn!(wallet, "both conflicting transactions were rejected");
+            } else {
+                self.submitted
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .push(pair);
+            }
+
+            sleep(interval).await;
+        }
+
+        tracing::info!("double spend workload finished");
+        Ok(())
+    }
+}
+
+/// A self transfer and a two-way split of the same input.
+fn conflicting_transactions(input: &WalletInput) -> Result<[SignedMantleTx; 2], DynError> {
+    let owner = input.account.public_key();
+    let value = input.utxo.note.value;
+    if value < 2 {
+        return Err("double spend workload needs wallet inputs worth at least 2".into());
+    }
+
+    let transfer = build_wallet_transaction_with_outputs(input, vec![Note::new(value, owner)])?;
+    let split = build_wallet_transaction_with_outputs(
+        input,
+        vec![
+            Note::new(value / 2, owner),
+            Note::new(value - value / 2, owner),
+        ],
+    )?;
+    Ok([transfer, split])
+}
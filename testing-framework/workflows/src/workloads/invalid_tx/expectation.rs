@@ -0,0 +1,210 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use nomos_core::mantle::{Transaction as _, TxHash};
+use testing_framework_core::scenario::{BlockRecord, DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+use super::workload::{FuzzSubmission, Submissions};
+
+/// Transactions observed in any block since capture started.
+type Included = Arc<Mutex<HashSet<TxHash>>>;
+
+/// Checks that no invalid transaction made it into a block and that every
+/// node kept producing blocks while they were being submitted.
+pub struct InvalidTxExpectation {
+    submissions: Submissions,
+    included: Option<Included>,
+    start_heights: Vec<u64>,
+    summary: BTreeMap<&'static str, MutationSummary>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct MutationSummary {
+    submitted: usize,
+    accepted: usize,
+    included: usize,
+}
+
+#[derive(Debug, Error)]
+enum InvalidTxIssue {
+    #[error("{mutation} transaction {tx:?} was included in a block")]
+    Included { mutation: &'static str, tx: TxHash },
+    #[error("node-{node} did not advance past height {height}")]
+    Stalled { node: usize, height: u64 },
+    #[error("node-{node} consensus info request failed: {source}")]
+    RequestFailed {
+        node: usize,
+        #[source]
+        source: DynError,
+    },
+}
+
+#[derive(Debug, Error)]
+enum InvalidTxError {
+    #[error("invalid tx expectation not captured")]
+    NotCaptured,
+    #[error("invalid tx workload submitted nothing")]
+    NothingSubmitted,
+    #[error("invalid tx expectation violated:\n{message}")]
+    Violations { message: String },
+}
+
+impl InvalidTxExpectation {
+    pub const NAME: &'static str = "invalid_tx_expectation";
+
+    pub(super) const fn new(submissions: Submissions) -> Self {
+        Self {
+            submissions,
+            included: None,
+            start_heights: Vec::new(),
+            summary: BTreeMap::new(),
+        }
+    }
+
+    fn summarize(&mut self, submissions: &[FuzzSubmission], included: &HashSet<TxHash>) {
+        self.summary.clear();
+        for submission in submissions {
+            let entry = self
+                .summary
+                .entry(submission.mutation.as_str())
+                .or_default();
+            entry.submitted += 1;
+            entry.accepted += usize::from(submission.accepted);
+            entry.included += usize::from(included.contains(&submission.tx));
+        }
+    }
+}
+
+#[async_trait]
+impl Expectation for InvalidTxExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.included.is_some() {
+            return Ok(());
+        }
+
+        for client in ctx.node_clients().all_clients() {
+            let height = client.consensus_info().await.map_or(0, |info| info.height);
+            self.start_heights.push(height);
+        }
+
+        let included = Included::default();
+        spawn_inclusion_capture(ctx.block_feed().subscribe(), Arc::clone(&included));
+        self.included = Some(included);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let included = self.included.clone().ok_or(InvalidTxError::NotCaptured)?;
+        let submissions = self
+            .submissions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        if submissions.is_empty() {
+            return Err(InvalidTxError::NothingSubmitted.into());
+        }
+
+        let included = included
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        self.summarize(&submissions, &included);
+
+        let mut issues = submissions
+            .iter()
+            .filter(|submission| included.contains(&submission.tx))
+            .map(|submission| InvalidTxIssue::Included {
+                mutation: submission.mutation.as_str(),
+                tx: submission.tx,
+            })
+            .collect::<Vec<_>>();
+
+        for (node, client) in ctx.node_clients().all_clients().iter().enumerate() {
+            let start = self.start_heights.get(node).copied().unwrap_or(0);
+            match client.consensus_info().await {
+                Ok(info) if info.height > start => {}
+                Ok(info) => issues.push(InvalidTxIssue::Stalled {
+                    node,
+                    height: info.height,
+                }),
+                Err(err) => issues.push(InvalidTxIssue::RequestFailed {
+                    node,
+                    source: err.into(),
+                }),
+            }
+        }
+
+        if issues.is_empty() {
+            tracing::info!(
+                submitted = submissions.len(),
+                summary = ?self.summary,
+                "invalid tx expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        for issue in &issues {
+            tracing::warn!(?issue, "invalid tx issue");
+        }
+        let message = issues
+            .iter()
+            .map(|issue| format!("- {issue}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(InvalidTxError::Violations { message }.into())
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        let summary = self
+            .summary
+            .iter()
+            .map(|(mutation, summary)| {
+                (
+                    (*mutation).to_owned(),
+                    serde_json::json!({
+                        "submitted": summary.submitted,
+                        "accepted": summary.accepted,
+                        "included": summary.included,
+                    }),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        Some(serde_json::Value::Object(summary))
+    }
+}
+
+fn spawn_inclusion_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    included: Included,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    let mut included = included
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                    for tx in record.block.transactions() {
+                        included.insert(tx.hash());
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "invalid tx capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::debug!("invalid tx capture feed closed");
+                    break;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,5 @@
+mod expectation;
+mod workload;
+
+pub use expectation::InvalidTxExpectation;
+pub use workload::{TxMutation, Workload};
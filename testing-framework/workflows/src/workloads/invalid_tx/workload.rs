@@ -0,0 +1,332 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use key_management_system_service::keys::ZkKey;
+use nomos_core::mantle::{
+    Note, SignedMantleTx, Transaction as _, TxHash, Utxo,
+    ops::channel::{ChannelId, MsgId},
+};
+use rand::{Rng as _, rngs::StdRng, seq::SliceRandom as _};
+use testing_framework_core::{
+    scenario::{DynError, Expectation, RunContext, RunMetrics, Workload as ScenarioWorkload},
+    topology::generation::GeneratedTopology,
+};
+use tokio::time::sleep;
+
+use super::expectation::InvalidTxExpectation;
+use crate::{
+    util::tx::{create_inscription_transaction, inscription_op},
+    workloads::transaction::{WalletInput, build_wallet_transaction_with_outputs, wallet_inputs},
+};
+
+const DEFAULT_ROUNDS: usize = 3;
+const SUBMISSION_INTERVAL: Duration = Duration::from_millis(500);
+const OVERSIZED_PAYLOAD_BYTES: usize = 2 * 1024 * 1024;
+
+/// Way in which a submitted transaction is made invalid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TxMutation {
+    /// Ledger proof signed with a key that does not own the input.
+    BadSignature,
+    /// Outputs worth more than the consumed input.
+    OutputsExceedInputs,
+    /// Spends a UTXO of a transaction that never existed.
+    UnknownUtxo,
+    /// Op list shorter than its proof list, as if an op was dropped in
+    /// transit.
+    TruncatedOps,
+    /// Inscription payload far beyond any sane block size.
+    OversizedPayload,
+}
+
+impl TxMutation {
+    pub const ALL: [Self; 5] = [
+        Self::BadSignature,
+        Self::OutputsExceedInputs,
+        Self::UnknownUtxo,
+        Self::TruncatedOps,
+        Self::OversizedPayload,
+    ];
+
+    /// Whether building this mutation needs a seeded wallet.
+    const fn needs_wallet(self) -> bool {
+        matches!(
+            self,
+            Self::BadSignature | Self::OutputsExceedInputs | Self::UnknownUtxo
+        )
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::BadSignature => "bad_signature",
+            Self::OutputsExceedInputs => "outputs_exceed_inputs",
+            Self::UnknownUtxo => "unknown_utxo",
+            Self::TruncatedOps => "truncated_ops",
+            Self::OversizedPayload => "oversized_payload",
+        }
+    }
+}
+
+/// Outcome of submitting one mutated transaction.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct FuzzSubmission {
+    pub mutation: TxMutation,
+    pub tx: TxHash,
+    /// The node accepted the submission instead of rejecting it.
+    pub accepted: bool,
+}
+
+/// Submissions made so far, shared with the expectation.
+pub(super) type Submissions = Arc<Mutex<Vec<FuzzSubmission>>>;
+
+/// Submits deliberately invalid transactions to random nodes to exercise
+/// mempool validation.
+#[derive(Clone)]
+pub struct Workload {
+    mutations: Vec<TxMutation>,
+    rounds: NonZeroUsize,
+    wallet: Option<WalletInput>,
+    submissions: Submissions,
+}
+
+impl Workload {
+    /// Creates a workload submitting every [`TxMutation`] each round.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            mutations: TxMutation::ALL.to_vec(),
+            rounds: NonZeroUsize::new(DEFAULT_ROUNDS).expect("default round count is non-zero"),
+            wallet: None,
+            submissions: Submissions::default(),
+        }
+    }
+
+    /// Restrict the workload to the given mutations.
+    #[must_use]
+    pub fn with_mutations(mut self, mutations: impl IntoIterator<Item = TxMutation>) -> Self {
+        self.mutations = mutations.into_iter().collect();
+        self
+    }
+
+    /// Number of times each mutation is submitted.
+    #[must_use]
+    pub const fn with_rounds(mut self, rounds: NonZeroUsize) -> Self {
+        self.rounds = rounds;
+        self
+    }
+}
+
+impl Default for Workload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for Workload {
+    fn name(&self) -> &'static str {
+        "invalid_tx_workload"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(InvalidTxExpectation::new(Arc::clone(
+            &self.submissions,
+        )))]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if self.mutations.is_empty() {
+            return Err("invalid tx workload has no mutations configured".into());
+        }
+        if self
+            .mutations
+            .iter()
+            .any(|mutation| mutation.needs_wallet())
+        {
+            match wallet_inputs(descriptors, NonZeroUsize::new(1)) {
+                Ok(mut inputs) => self.wallet = inputs.pop(),
+                Err(err) => {
+                    tracing::warn!(%err, "invalid tx workload: skipping wallet-based mutations");
+                    self.mutations.retain(|mutation| !mutation.needs_wallet());
+                }
+            }
+        }
+        if self.mutations.is_empty() {
+            return Err("invalid tx workload: every configured mutation needs a wallet".into());
+        }
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        tracing::info!(
+            mutations = ?self.mutations,
+            rounds = self.rounds.get(),
+            "starting invalid tx workload"
+        );
+
+        let mut rng = ctx.rng(self.name());
+        for round in 0..self.rounds.get() {
+            for mutation in &self.mutations {
+                // Some mutations may already be refused while building.
+                let tx = match self.build(*mutation, round, &mut rng) {
+                    Ok(tx) => tx,
+                    Err(err) => {
+                        tracing::debug!(mutation = mutation.as_str(), %err, "mutation rejected while building");
+                        continue;
+                    }
+                };
+                let client = ctx
+                    .node_clients()
+                    .node_clients()
+                    .choose(&mut rng)
+                    .cloned()
+                    .ok_or("invalid tx workload: no node clients available")?;
+                let result = client.submit_raw_transaction(&tx).await;
+                if result.is_ok() {
+                    tracing::warn!(
+                        mutation = mutation.as_str(),
+                        tx_hash = ?tx.hash(),
+                        url = %client.base_url(),
+                        "node accepted invalid transaction"
+                    );
+                } else {
+                    tracing::debug!(mutation = mutation.as_str(), "invalid transaction rejected");
+                }
+
+                self.submissions
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .push(FuzzSubmission {
+                        mutation: *mutation,
+                        tx: tx.hash(),
+                        accepted: result.is_ok(),
+                    });
+                sleep(SUBMISSION_INTERVAL).await;
+            }
+        }
+
+        tracing::info!("invalid tx workload finished");
+        Ok(())
+    }
+}
+
+impl Workload {
+    fn build(
+        &self,
+        mutation: TxMutation,
+        round: usize,
+        rng: &mut StdRng,
+    ) -> Result<SignedMantleTx, DynError> {
+        let wallet = || {
+            self.wallet
+                .as_ref()
+                .ok_or("invalid tx workload: mutation requires a seeded wallet")
+        };
+
+        match mutation {
+            TxMutation::BadSignature => {
+                let input = wallet()?;
+                // Pay a throwaway key: the proof is not part of the hash, so a
+                // self-transfer would share its hash with the valid transfer
+                // other workloads build for the same wallet.
+                let mut tx = build_wallet_transaction_with_outputs(
+                    input,
+                    vec![Note::new(
+                        input.utxo.note.value,
+                        ZkKey::zero().to_public_key(),
+                    )],
+                )?;
+                tx.ledger_tx_proof =
+                    ZkKey::multi_sign(&[ZkKey::zero()], tx.mantle_tx.hash().as_ref())
+                        .map_err(|err| format!("invalid tx workload signing failed: {err}"))?;
+                Ok(tx)
+            }
+            TxMutation::OutputsExceedInputs => {
+                let input = wallet()?;
+                let inflated = input.utxo.note.value.saturating_add(1 + round as u64);
+                build_wallet_transaction_with_outputs(
+                    input,
+                    vec![Note::new(inflated, input.account.public_key())],
+                )
+            }
+            TxMutation::UnknownUtxo => {
+                let input = wallet()?;
+                // A transaction that is never submitted has no outputs on chain.
+                let phantom = build_wallet_transaction_with_outputs(
+                    input,
+                    vec![Note::new(round as u64 + 1, input.account.public_key())],
+                )?;
+                let unknown = WalletInput {
+                    account: input.account.clone(),
+                    utxo: Utxo::new(phantom.hash(), 0, input.utxo.note),
+                };
+                build_wallet_transaction_with_outputs(
+                    &unknown,
+                    vec![Note::new(input.utxo.note.value, input.account.public_key())],
+                )
+            }
+            TxMutation::TruncatedOps => {
+                let channel = random_channel(rng);
+                let first = inscription_op(channel, MsgId::root(), b"truncated 0".to_vec());
+                let second = inscription_op(channel, first.id(), b"truncated 1".to_vec());
+                let mut tx = create_inscription_transaction(first)?;
+                let extra = create_inscription_transaction(second)?;
+                tx.ops_proofs.extend(extra.ops_proofs);
+                Ok(tx)
+            }
+            TxMutation::OversizedPayload => create_inscription_transaction(inscription_op(
+                random_channel(rng),
+                MsgId::root(),
+                vec![0xAB; OVERSIZED_PAYLOAD_BYTES],
+            )),
+        }
+    }
+}
+
+fn random_channel(rng: &mut StdRng) -> ChannelId {
+    ChannelId::from(rng.r#gen::<[u8; 32]>())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng as _;
+    use testing_framework_config::topology::configs::wallet::WalletAccount;
+
+    use super::*;
+    use crate::{
+        util::tx::create_inscription_transaction_with_id,
+        workloads::transaction::build_wallet_transaction,
+    };
+
+    #[test]
+    fn bad_signature_hash_differs_from_valid_transfer() {
+        let account = WalletAccount::deterministic(0, 100);
+        let genesis = create_inscription_transaction_with_id(ChannelId::from([7; 32]))
+            .expect("inscription builds")
+            .hash();
+        let input = WalletInput {
+            utxo: Utxo::new(genesis, 0, Note::new(account.value, account.public_key())),
+            account,
+        };
+        let workload = Workload {
+            wallet: Some(input.clone()),
+            ..Workload::new()
+        };
+
+        let mutated = workload
+            .build(TxMutation::BadSignature, 0, &mut StdRng::seed_from_u64(0))
+            .expect("mutation builds");
+        let valid = build_wallet_transaction(&input).expect("transfer builds");
+
+        assert_ne!(mutated.hash(), valid.hash());
+    }
+}
@@ -1,4 +1,7 @@
+pub mod channel;
 pub mod chaos;
+pub mod double_spend;
+pub mod invalid_tx;
 pub mod transaction;
 pub mod util;
 
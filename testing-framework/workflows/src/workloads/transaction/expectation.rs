@@ -2,7 +2,7 @@ use std::{
     collections::HashSet,
     num::{NonZeroU64, NonZeroUsize},
     sync::{
-        Arc,
+        Arc, Mutex, Weak,
         atomic::{AtomicU64, Ordering},
     },
     time::Duration,
@@ -10,28 +10,46 @@ use std::{
 
 use async_trait::async_trait;
 use key_management_system_service::keys::ZkPublicKey;
-use nomos_core::{header::HeaderId, mantle::AuthenticatedMantleTx as _};
-use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use nomos_core::{
+    header::HeaderId,
+    mantle::{AuthenticatedMantleTx as _, Transaction as _},
+};
+use testing_framework_core::scenario::{DynError, Expectation, NodeClients, RunContext};
 use thiserror::Error;
-use tokio::{sync::broadcast, time::sleep};
+use tokio::{
+    sync::broadcast,
+    time::{Instant, sleep},
+};
 
-use super::workload::{SubmissionPlan, limited_user_count, submission_plan};
+use super::{
+    latency::{
+        LatencyLimit, LatencySummary, LatencyThreshold, LatencyTracker, Percentile, SubmissionLog,
+    },
+    workload::{SubmissionPlan, limited_user_count, submission_plan},
+};
 
 const MIN_INCLUSION_RATIO: f64 = 0.5;
 const CATCHUP_POLL_INTERVAL: Duration = Duration::from_secs(1);
 const MAX_CATCHUP_WAIT: Duration = Duration::from_secs(60);
+const FINALITY_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Clone)]
 pub struct TxInclusionExpectation {
     txs_per_block: NonZeroU64,
     user_limit: Option<NonZeroUsize>,
     capture_state: Option<CaptureState>,
+    submissions: SubmissionLog,
+    inclusion_thresholds: Vec<LatencyThreshold>,
+    finality_thresholds: Vec<LatencyThreshold>,
+    slot_duration: Option<Duration>,
+    latency: Option<(LatencySummary, LatencySummary)>,
 }
 
 #[derive(Clone)]
 struct CaptureState {
     observed: Arc<AtomicU64>,
     expected: u64,
+    latency: Arc<Mutex<LatencyTracker>>,
 }
 
 #[derive(Debug, Error)]
@@ -44,6 +62,20 @@ enum TxExpectationError {
     NotCaptured,
     #[error("transaction inclusion observed {observed} below required {required}")]
     InsufficientInclusions { observed: u64, required: u64 },
+    #[error("{kind} latency {percentile} is {observed:?}, limit {limit:?}")]
+    LatencyExceeded {
+        kind: &'static str,
+        percentile: &'static str,
+        observed: Duration,
+        limit: Duration,
+    },
+    #[error("{kind} latency {percentile} has no samples")]
+    NoLatencySamples {
+        kind: &'static str,
+        percentile: &'static str,
+    },
+    #[error("{kind} latency limit in slots needs a known slot duration")]
+    UnknownSlotDuration { kind: &'static str },
 }
 
 impl TxInclusionExpectation {
@@ -59,8 +91,67 @@ impl TxInclusionExpectation {
             txs_per_block,
             user_limit,
             capture_state: None,
+            submissions: SubmissionLog::default(),
+            inclusion_thresholds: Vec::new(),
+            finality_thresholds: Vec::new(),
+            slot_duration: None,
+            latency: None,
         }
     }
+
+    /// Require the submit-to-inclusion latency at `percentile` to stay below
+    /// `limit`.
+    #[must_use]
+    pub fn with_inclusion_latency(mut self, percentile: Percentile, limit: LatencyLimit) -> Self {
+        self.inclusion_thresholds
+            .push(LatencyThreshold { percentile, limit });
+        self
+    }
+
+    /// Require the submit-to-finality latency at `percentile` to stay below
+    /// `limit`.
+    #[must_use]
+    pub fn with_finality_latency(mut self, percentile: Percentile, limit: LatencyLimit) -> Self {
+        self.finality_thresholds
+            .push(LatencyThreshold { percentile, limit });
+        self
+    }
+
+    /// Share the workload's submission timestamps for latency tracking.
+    pub(super) fn with_submission_log(mut self, submissions: SubmissionLog) -> Self {
+        self.submissions = submissions;
+        self
+    }
+
+    fn check_latency(&self) -> Result<(), TxExpectationError> {
+        let Some((inclusion, finality)) = &self.latency else {
+            return Ok(());
+        };
+        for (kind, summary, thresholds) in [
+            ("inclusion", inclusion, &self.inclusion_thresholds),
+            ("finality", finality, &self.finality_thresholds),
+        ] {
+            for threshold in thresholds {
+                let percentile = threshold.percentile.as_str();
+                let limit = threshold
+                    .limit
+                    .as_duration(self.slot_duration)
+                    .ok_or(TxExpectationError::UnknownSlotDuration { kind })?;
+                let observed = summary
+                    .get(threshold.percentile)
+                    .ok_or(TxExpectationError::NoLatencySamples { kind, percentile })?;
+                if observed >= limit {
+                    return Err(TxExpectationError::LatencyExceeded {
+                        kind,
+                        percentile,
+                        observed,
+                        limit,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -86,16 +177,27 @@ impl Expectation for TxInclusionExpectation {
             "tx inclusion expectation starting capture"
         );
 
+        self.slot_duration = ctx.descriptors().slot_duration();
+
         let observed = Arc::new(AtomicU64::new(0));
+        let latency = Arc::new(Mutex::new(LatencyTracker::default()));
         spawn_tx_inclusion_capture(
             ctx.block_feed().subscribe(),
             Arc::new(tracked_accounts),
             Arc::clone(&observed),
+            Arc::clone(&self.submissions),
+            Arc::clone(&latency),
+        );
+        spawn_finality_tracker(
+            ctx.node_clients().clone(),
+            Arc::clone(&self.submissions),
+            Arc::downgrade(&latency),
         );
 
         self.capture_state = Some(CaptureState {
             observed,
             expected: plan.transaction_count as u64,
+            latency,
         });
 
         Ok(())
@@ -104,7 +206,7 @@ impl Expectation for TxInclusionExpectation {
     async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
         let state = self
             .capture_state
-            .as_ref()
+            .clone()
             .ok_or(TxExpectationError::NotCaptured)?;
 
         let required = ((state.expected as f64) * MIN_INCLUSION_RATIO).ceil() as u64;
@@ -127,7 +229,19 @@ impl Expectation for TxInclusionExpectation {
             }
         }
 
+        let latency = {
+            let tracker = lock(&state.latency);
+            (tracker.inclusion(), tracker.finality())
+        };
+        tracing::info!(
+            inclusion = ?latency.0,
+            finality = ?latency.1,
+            "tx inclusion latency"
+        );
+        self.latency = Some(latency);
+
         if observed >= required {
+            self.check_latency()?;
             tracing::info!(
                 observed,
                 required,
@@ -145,6 +259,14 @@ impl Expectation for TxInclusionExpectation {
             Err(TxExpectationError::InsufficientInclusions { observed, required }.into())
         }
     }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        let (inclusion, finality) = self.latency.as_ref()?;
+        Some(serde_json::json!({
+            "inclusion_latency": inclusion.to_json(self.slot_duration),
+            "finality_latency": finality.to_json(self.slot_duration),
+        }))
+    }
 }
 
 fn build_capture_plan(
@@ -172,6 +294,8 @@ fn spawn_tx_inclusion_capture(
     mut receiver: broadcast::Receiver<Arc<testing_framework_core::scenario::BlockRecord>>,
     tracked_accounts: Arc<HashSet<ZkPublicKey>>,
     observed: Arc<AtomicU64>,
+    submissions: SubmissionLog,
+    latency: Arc<Mutex<LatencyTracker>>,
 ) {
     tokio::spawn(async move {
         let genesis_parent = HeaderId::from([0; 32]);
@@ -185,6 +309,7 @@ fn spawn_tx_inclusion_capture(
                     }
 
                     capture_tx_outputs(record.as_ref(), &tracked_accounts, &observed);
+                    capture_tx_latency(record.as_ref(), &submissions, &latency);
                 }
                 Err(broadcast::error::RecvError::Lagged(skipped)) => {
                     tracing::debug!(skipped, "tx inclusion capture lagged");
@@ -215,3 +340,72 @@ fn capture_tx_outputs(
         }
     }
 }
+
+fn capture_tx_latency(
+    record: &testing_framework_core::scenario::BlockRecord,
+    submissions: &SubmissionLog,
+    latency: &Mutex<LatencyTracker>,
+) {
+    let now = Instant::now();
+    let submissions = lock(submissions);
+    let mut latency = lock(latency);
+    for tx in record.block.transactions() {
+        let hash = tx.hash();
+        latency.observe_inclusion(hash, record.header, submissions.get(&hash).copied(), now);
+    }
+}
+
+/// Poll the first node's LIB and record finality for included transactions
+/// until the expectation is dropped. Sticking to one node keeps consecutive
+/// LIBs on the same chain, so each range walked is exactly what was newly
+/// finalized.
+fn spawn_finality_tracker(
+    clients: NodeClients,
+    submissions: SubmissionLog,
+    latency: Weak<Mutex<LatencyTracker>>,
+) {
+    tokio::spawn(async move {
+        let mut last_lib: Option<HeaderId> = None;
+        loop {
+            sleep(FINALITY_POLL_INTERVAL).await;
+            let Some(tracker) = latency.upgrade() else {
+                break;
+            };
+            let Some(client) = clients.node_clients().into_iter().next() else {
+                continue;
+            };
+            let lib = match client.consensus_info().await {
+                Ok(info) => info.lib,
+                Err(err) => {
+                    tracing::debug!(%err, "finality tracker consensus info failed");
+                    continue;
+                }
+            };
+            let Some(previous) = last_lib.replace(lib) else {
+                continue;
+            };
+            if previous == lib {
+                continue;
+            }
+
+            match client.consensus_headers(Some(lib), Some(previous)).await {
+                Ok(headers) => {
+                    let finalized = headers.into_iter().collect::<HashSet<_>>();
+                    let now = Instant::now();
+                    let submissions = lock(&submissions);
+                    lock(&tracker).observe_finalized(&finalized, &submissions, now);
+                }
+                Err(err) => {
+                    tracing::debug!(%err, "finality tracker headers request failed");
+                    last_lib = Some(previous);
+                }
+            }
+        }
+    });
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
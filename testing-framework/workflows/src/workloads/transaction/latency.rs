@@ -0,0 +1,203 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use nomos_core::{header::HeaderId, mantle::TxHash};
+use tokio::time::Instant;
+
+/// Submission time of every transaction sent by a workload, by hash.
+pub(super) type SubmissionLog = Arc<Mutex<HashMap<TxHash, Instant>>>;
+
+/// Percentile of a latency distribution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Percentile {
+    P50,
+    P90,
+    P99,
+}
+
+impl Percentile {
+    const ALL: [Self; 3] = [Self::P50, Self::P90, Self::P99];
+
+    const fn fraction(self) -> f64 {
+        match self {
+            Self::P50 => 0.50,
+            Self::P90 => 0.90,
+            Self::P99 => 0.99,
+        }
+    }
+
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::P50 => "p50",
+            Self::P90 => "p90",
+            Self::P99 => "p99",
+        }
+    }
+}
+
+/// Upper bound for a latency percentile.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LatencyLimit {
+    /// Bound in consensus slots; converted using the topology's slot
+    /// duration.
+    Slots(u64),
+    Duration(Duration),
+}
+
+impl LatencyLimit {
+    pub(super) fn as_duration(self, slot_duration: Option<Duration>) -> Option<Duration> {
+        match self {
+            Self::Slots(slots) => slot_duration
+                .map(|slot| slot.saturating_mul(u32::try_from(slots).unwrap_or(u32::MAX))),
+            Self::Duration(duration) => Some(duration),
+        }
+    }
+}
+
+/// A `percentile < limit` requirement on inclusion or finality latency.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct LatencyThreshold {
+    pub percentile: Percentile,
+    pub limit: LatencyLimit,
+}
+
+/// Inclusion and finality progress for submitted transactions.
+#[derive(Default)]
+pub(super) struct LatencyTracker {
+    /// Block each transaction landed in, with its submit-to-inclusion latency.
+    included: HashMap<TxHash, (HeaderId, Duration)>,
+    finalized: HashSet<TxHash>,
+    inclusion: Vec<Duration>,
+    finality: Vec<Duration>,
+}
+
+impl LatencyTracker {
+    /// Record `tx` as included in `block` at `now`, if it was submitted by the
+    /// workload and not seen before.
+    pub fn observe_inclusion(
+        &mut self,
+        tx: TxHash,
+        block: HeaderId,
+        submitted: Option<Instant>,
+        now: Instant,
+    ) {
+        let Some(submitted) = submitted else {
+            return;
+        };
+        if self.included.contains_key(&tx) {
+            return;
+        }
+        let latency = now.saturating_duration_since(submitted);
+        self.included.insert(tx, (block, latency));
+        self.inclusion.push(latency);
+    }
+
+    /// Mark transactions in any of `blocks` as finalized at `now`.
+    pub fn observe_finalized(
+        &mut self,
+        blocks: &HashSet<HeaderId>,
+        submissions: &HashMap<TxHash, Instant>,
+        now: Instant,
+    ) {
+        for (tx, (block, _)) in &self.included {
+            if self.finalized.contains(tx) || !blocks.contains(block) {
+                continue;
+            }
+            if let Some(submitted) = submissions.get(tx) {
+                self.finalized.insert(*tx);
+                self.finality
+                    .push(now.saturating_duration_since(*submitted));
+            }
+        }
+    }
+
+    pub fn inclusion(&self) -> LatencySummary {
+        LatencySummary::from_samples(&self.inclusion)
+    }
+
+    pub fn finality(&self) -> LatencySummary {
+        LatencySummary::from_samples(&self.finality)
+    }
+}
+
+/// Percentiles of a latency distribution.
+#[derive(Clone, Debug, Default)]
+pub(super) struct LatencySummary {
+    pub samples: usize,
+    percentiles: Vec<(Percentile, Duration)>,
+    pub max: Option<Duration>,
+}
+
+impl LatencySummary {
+    fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        Self {
+            samples: sorted.len(),
+            percentiles: Percentile::ALL
+                .iter()
+                .filter_map(|percentile| {
+                    nearest_rank(&sorted, percentile.fraction()).map(|value| (*percentile, value))
+                })
+                .collect(),
+            max: sorted.last().copied(),
+        }
+    }
+
+    pub fn get(&self, percentile: Percentile) -> Option<Duration> {
+        self.percentiles
+            .iter()
+            .find(|(candidate, _)| *candidate == percentile)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn to_json(&self, slot_duration: Option<Duration>) -> serde_json::Value {
+        let mut summary = serde_json::Map::new();
+        summary.insert("samples".to_owned(), self.samples.into());
+        for (percentile, value) in &self.percentiles {
+            summary.insert(
+                format!("{}_secs", percentile.as_str()),
+                value.as_secs_f64().into(),
+            );
+            if let Some(slot) = slot_duration.filter(|slot| !slot.is_zero()) {
+                summary.insert(
+                    format!("{}_slots", percentile.as_str()),
+                    (value.as_secs_f64() / slot.as_secs_f64()).into(),
+                );
+            }
+        }
+        if let Some(max) = self.max {
+            summary.insert("max_secs".to_owned(), max.as_secs_f64().into());
+        }
+        serde_json::Value::Object(summary)
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn nearest_rank(sorted: &[Duration], fraction: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (fraction * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let samples = (1..=100).map(Duration::from_secs).collect::<Vec<_>>();
+        let summary = LatencySummary::from_samples(&samples);
+
+        assert_eq!(summary.get(Percentile::P50), Some(Duration::from_secs(50)));
+        assert_eq!(summary.get(Percentile::P90), Some(Duration::from_secs(90)));
+        assert_eq!(summary.get(Percentile::P99), Some(Duration::from_secs(99)));
+        assert_eq!(summary.max, Some(Duration::from_secs(100)));
+        assert_eq!(LatencySummary::from_samples(&[]).get(Percentile::P50), None);
+    }
+}
@@ -1,5 +1,14 @@
 mod expectation;
+mod latency;
+mod open_loop;
+mod sustained;
 mod workload;
 
 pub use expectation::TxInclusionExpectation;
+pub use latency::{LatencyLimit, Percentile};
+pub use open_loop::{Arrivals, OpenLoopWorkload};
+pub use sustained::SustainedWorkload;
 pub use workload::Workload;
+pub(crate) use workload::{
+    WalletInput, build_wallet_transaction, build_wallet_transaction_with_outputs, wallet_inputs,
+};
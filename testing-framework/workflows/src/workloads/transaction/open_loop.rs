@@ -0,0 +1,364 @@
+use std::{
+    collections::HashSet,
+    num::NonZeroUsize,
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use nomos_core::mantle::{SignedMantleTx, Transaction as _, TxHash, ops::channel::ChannelId};
+use rand::{Rng as _, rngs::StdRng};
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{BlockRecord, DynError, Expectation, RunContext, Workload as ScenarioWorkload},
+};
+use thiserror::Error;
+use tokio::{
+    sync::{Semaphore, broadcast},
+    task::JoinSet,
+    time::{Instant, sleep_until},
+};
+
+use crate::{
+    util::tx::create_inscription_transaction_with_id, workloads::util::submit_transaction_once,
+};
+
+const DEFAULT_MAX_IN_FLIGHT: usize = 1024;
+
+/// How arrivals are spread over time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Arrivals {
+    /// Evenly spaced arrivals.
+    Constant,
+    /// Exponentially distributed gaps between arrivals.
+    Poisson,
+    /// Groups of `size` arrivals sent back to back, spaced so the average
+    /// rate still matches the target.
+    Bursty { size: NonZeroUsize },
+}
+
+impl Arrivals {
+    /// Gap until the next arrival, given the arrival index and target rate.
+    fn gap(self, index: u64, tps: f64, rng: &mut StdRng) -> Duration {
+        let mean = 1.0 / tps;
+        let secs = match self {
+            Self::Constant => mean,
+            Self::Poisson => {
+                let uniform: f64 = rng.gen_range(f64::EPSILON..1.0);
+                -uniform.ln() * mean
+            }
+            Self::Bursty { size } => {
+                let size = size.get() as u64;
+                if (index + 1) % size == 0 {
+                    mean * size as f64
+                } else {
+                    0.0
+                }
+            }
+        };
+        Duration::from_secs_f64(secs)
+    }
+}
+
+/// Open-loop transaction generator driven by a target rate in transactions
+/// per second.
+///
+/// Each arrival inscribes to a fresh channel, so no wallets are needed.
+/// Arrivals follow a fixed schedule and each submission runs in its own task,
+/// so slow or failing submissions never delay the next arrival. Arrivals that
+/// find `max_in_flight` submissions outstanding are counted as dropped rather
+/// than queued.
+#[derive(Clone)]
+pub struct OpenLoopWorkload {
+    tps: f64,
+    arrivals: Arrivals,
+    duration: Option<Duration>,
+    max_in_flight: NonZeroUsize,
+    stats: Arc<OpenLoopStats>,
+}
+
+#[derive(Debug, Default)]
+struct OpenLoopStats {
+    offered: AtomicU64,
+    accepted: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+    included: AtomicU64,
+    elapsed_ms: AtomicU64,
+    /// Submitted transactions not yet seen in a block. Entries are added
+    /// before submitting so an inclusion observed ahead of the node's reply
+    /// still counts.
+    awaiting_inclusion: Mutex<HashSet<TxHash>>,
+}
+
+impl OpenLoopStats {
+    fn awaiting_inclusion(&self) -> std::sync::MutexGuard<'_, HashSet<TxHash>> {
+        self.awaiting_inclusion
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl OpenLoopWorkload {
+    /// Creates a generator targeting `tps` transactions per second, returning
+    /// `None` unless the rate is positive and finite.
+    #[must_use]
+    pub fn new(tps: f64) -> Option<Self> {
+        (tps.is_finite() && tps > 0.0).then(|| Self {
+            tps,
+            arrivals: Arrivals::Constant,
+            duration: None,
+            max_in_flight: NonZeroUsize::new(DEFAULT_MAX_IN_FLIGHT)
+                .expect("default in-flight limit is non-zero"),
+            stats: Arc::default(),
+        })
+    }
+
+    /// Returns the target rate in transactions per second.
+    #[must_use]
+    pub const fn tps(&self) -> f64 {
+        self.tps
+    }
+
+    #[must_use]
+    pub const fn with_arrivals(mut self, arrivals: Arrivals) -> Self {
+        self.arrivals = arrivals;
+        self
+    }
+
+    /// Stop generating after `duration` instead of running until the end of
+    /// the scenario.
+    #[must_use]
+    pub const fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Limit how many submissions may be outstanding at once.
+    #[must_use]
+    pub const fn with_max_in_flight(mut self, max_in_flight: NonZeroUsize) -> Self {
+        self.max_in_flight = max_in_flight;
+        self
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for OpenLoopWorkload {
+    fn name(&self) -> &'static str {
+        "tx_open_loop_workload"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(OpenLoopRateExpectation {
+            tps: self.tps,
+            stats: Arc::clone(&self.stats),
+        })]
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        tracing::info!(
+            tps = self.tps,
+            arrivals = ?self.arrivals,
+            duration_secs = self.duration.map(|d| d.as_secs_f64()),
+            max_in_flight = self.max_in_flight.get(),
+            "starting open-loop transaction workload"
+        );
+
+        let started = Instant::now();
+        let deadline = self.duration.map(|duration| started + duration);
+        let in_flight = Arc::new(Semaphore::new(self.max_in_flight.get()));
+        let mut next = started;
+        let mut index = 0u64;
+        let mut rng = ctx.rng(self.name());
+        let mut submissions = JoinSet::new();
+
+        loop {
+            if deadline.is_some_and(|deadline| next >= deadline) {
+                break;
+            }
+            sleep_until(next).await;
+            while submissions.try_join_next().is_some() {}
+
+            self.stats.offered.fetch_add(1, Ordering::Relaxed);
+            self.stats
+                .elapsed_ms
+                .store(started.elapsed().as_millis() as u64, Ordering::Relaxed);
+
+            match Arc::clone(&in_flight).try_acquire_owned() {
+                Ok(permit) => {
+                    let client = ctx.node_clients().random_node_with(&mut rng);
+                    let channel = ChannelId::from(rng.r#gen::<[u8; 32]>());
+                    let stats = Arc::clone(&self.stats);
+                    submissions.spawn(async move {
+                        let _permit = permit;
+                        submit_arrival(client, channel, &stats).await;
+                    });
+                }
+                Err(_) => {
+                    self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+
+            // Schedule from the previous arrival, not from now, so lagging
+            // submissions never stretch the schedule.
+            next += self.arrivals.gap(index, self.tps, &mut rng);
+            index += 1;
+        }
+
+        // Let outstanding submissions settle so their outcome is counted.
+        while submissions.join_next().await.is_some() {}
+
+        tracing::info!(
+            offered = self.stats.offered.load(Ordering::Relaxed),
+            dropped = self.stats.dropped.load(Ordering::Relaxed),
+            "open-loop transaction workload finished"
+        );
+        Ok(())
+    }
+}
+
+async fn submit_arrival(client: Option<ApiClient>, channel: ChannelId, stats: &OpenLoopStats) {
+    let result = match (client, create_inscription_transaction_with_id(channel)) {
+        (Some(client), Ok(tx)) => submit_tracked(&client, &tx, stats).await,
+        (None, _) => Err("no node clients available for transaction submission".into()),
+        (_, Err(err)) => Err(err),
+    };
+    match result {
+        Ok(()) => stats.accepted.fetch_add(1, Ordering::Relaxed),
+        Err(err) => {
+            tracing::debug!(error = %err, "open-loop submission failed");
+            stats.failed.fetch_add(1, Ordering::Relaxed)
+        }
+    };
+}
+
+async fn submit_tracked(
+    client: &ApiClient,
+    tx: &SignedMantleTx,
+    stats: &OpenLoopStats,
+) -> Result<(), DynError> {
+    let hash = tx.hash();
+    stats.awaiting_inclusion().insert(hash);
+    let result = submit_transaction_once(client, tx).await;
+    if result.is_err() {
+        stats.awaiting_inclusion().remove(&hash);
+    }
+    result
+}
+
+#[derive(Debug, Error)]
+enum OpenLoopError {
+    #[error(
+        "open-loop workload had no transactions included ({accepted} accepted out of {offered} offered)"
+    )]
+    NothingIncluded { offered: u64, accepted: u64 },
+}
+
+/// Reports achieved versus target rate for an [`OpenLoopWorkload`], where the
+/// achieved rate counts transactions included in blocks; fails only when none
+/// was included at all.
+struct OpenLoopRateExpectation {
+    tps: f64,
+    stats: Arc<OpenLoopStats>,
+}
+
+impl OpenLoopRateExpectation {
+    fn elapsed_secs(&self) -> f64 {
+        self.stats.elapsed_ms.load(Ordering::Relaxed) as f64 / 1_000.0
+    }
+
+    fn rate(&self, count: &AtomicU64) -> f64 {
+        let elapsed = self.elapsed_secs();
+        if elapsed > 0.0 {
+            count.load(Ordering::Relaxed) as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+#[async_trait]
+impl Expectation for OpenLoopRateExpectation {
+    fn name(&self) -> &'static str {
+        "tx_open_loop_rate"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        spawn_inclusion_capture(ctx.block_feed().subscribe(), Arc::downgrade(&self.stats));
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+        let offered = self.stats.offered.load(Ordering::Relaxed);
+        let accepted = self.stats.accepted.load(Ordering::Relaxed);
+        let included = self.stats.included.load(Ordering::Relaxed);
+
+        tracing::info!(
+            target_tps = self.tps,
+            offered_tps = self.rate(&self.stats.offered),
+            accepted_tps = self.rate(&self.stats.accepted),
+            achieved_tps = self.rate(&self.stats.included),
+            offered,
+            accepted,
+            included,
+            failed = self.stats.failed.load(Ordering::Relaxed),
+            dropped = self.stats.dropped.load(Ordering::Relaxed),
+            "open-loop transaction rate"
+        );
+
+        if included == 0 {
+            return Err(OpenLoopError::NothingIncluded { offered, accepted }.into());
+        }
+        Ok(())
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "target_tps": self.tps,
+            "offered_tps": self.rate(&self.stats.offered),
+            "accepted_tps": self.rate(&self.stats.accepted),
+            "achieved_tps": self.rate(&self.stats.included),
+            "elapsed_secs": self.elapsed_secs(),
+            "offered": self.stats.offered.load(Ordering::Relaxed),
+            "accepted": self.stats.accepted.load(Ordering::Relaxed),
+            "included": self.stats.included.load(Ordering::Relaxed),
+            "failed": self.stats.failed.load(Ordering::Relaxed),
+            "dropped": self.stats.dropped.load(Ordering::Relaxed),
+        }))
+    }
+}
+
+/// Count blocks' transactions that the generator submitted, until the
+/// workload's stats are dropped.
+fn spawn_inclusion_capture(
+    mut receiver: broadcast::Receiver<Arc<BlockRecord>>,
+    stats: Weak<OpenLoopStats>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    let Some(stats) = stats.upgrade() else {
+                        break;
+                    };
+                    let mut awaiting = stats.awaiting_inclusion();
+                    for tx in record.block.transactions() {
+                        if awaiting.remove(&tx.hash()) {
+                            stats.included.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "open-loop inclusion capture lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::debug!("open-loop inclusion capture feed closed");
+                    break;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,395 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    num::{NonZeroU64, NonZeroUsize},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use key_management_system_service::keys::ZkPublicKey;
+use nomos_core::mantle::{AuthenticatedMantleTx as _, Note, Transaction as _, TxHash, Utxo};
+use rand::rngs::StdRng;
+use testing_framework_core::{
+    scenario::{
+        BlockRecord, DynError, Expectation, RunContext, RunMetrics, Workload as ScenarioWorkload,
+    },
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::{
+    sync::broadcast,
+    time::{Instant, MissedTickBehavior, interval},
+};
+
+use super::{
+    WalletInput, build_wallet_transaction, build_wallet_transaction_with_outputs, wallet_inputs,
+};
+use crate::workloads::util::submit_transaction_via_cluster;
+
+const FALLBACK_BLOCK_INTERVAL: Duration = Duration::from_secs(2);
+/// Blocks to wait for a submitted transaction before giving up on it.
+const PENDING_TIMEOUT_BLOCKS: u32 = 10;
+/// Blocks of submissions to keep UTXOs for, covering inclusion latency.
+const FAN_OUT_BLOCKS: u64 = 4;
+const MIN_INCLUSION_RATIO: f64 = 0.5;
+
+/// Transaction workload that keeps spending for the whole run.
+///
+/// Each wallet starts from its genesis UTXO; once a transaction lands in a
+/// block its outputs are tracked as the wallet's next inputs. Spends split
+/// their output in two while there are fewer UTXOs than the target rate keeps
+/// in flight, so the submission rate does not depend on how many wallets are
+/// seeded.
+#[derive(Clone)]
+pub struct SustainedWorkload {
+    txs_per_block: NonZeroU64,
+    user_limit: Option<NonZeroUsize>,
+    accounts: Vec<WalletInput>,
+    stats: Arc<SustainedStats>,
+}
+
+#[derive(Debug, Default)]
+struct SustainedStats {
+    submitted: AtomicU64,
+    included: AtomicU64,
+}
+
+impl SustainedWorkload {
+    /// Creates a sustained workload that targets the provided transactions
+    /// per block rate.
+    #[must_use]
+    pub fn new(txs_per_block: NonZeroU64) -> Self {
+        Self {
+            txs_per_block,
+            user_limit: None,
+            accounts: Vec::new(),
+            stats: Arc::default(),
+        }
+    }
+
+    /// Returns the configured transactions per block rate.
+    #[must_use]
+    pub const fn txs_per_block(&self) -> NonZeroU64 {
+        self.txs_per_block
+    }
+
+    /// Limits the number of distinct users that will submit transactions.
+    #[must_use]
+    pub const fn with_user_limit(mut self, user_limit: Option<NonZeroUsize>) -> Self {
+        self.user_limit = user_limit;
+        self
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for SustainedWorkload {
+    fn name(&self) -> &'static str {
+        "tx_sustained_workload"
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(SustainedInclusionExpectation {
+            stats: Arc::clone(&self.stats),
+        })]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        tracing::info!("initializing sustained transaction workload");
+
+        self.accounts = wallet_inputs(descriptors, self.user_limit)?;
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let block_interval = ctx
+            .run_metrics()
+            .block_interval_hint()
+            .unwrap_or(FALLBACK_BLOCK_INTERVAL);
+        // `interval` panics on a zero period, which very high rates round to.
+        let submission_interval = block_interval
+            .div_f64(self.txs_per_block.get() as f64)
+            .max(Duration::from_millis(1));
+        let pending_timeout = block_interval * PENDING_TIMEOUT_BLOCKS;
+        let target_utxos =
+            usize::try_from(self.txs_per_block.get() * FAN_OUT_BLOCKS).unwrap_or(usize::MAX);
+
+        tracing::info!(
+            txs_per_block = self.txs_per_block.get(),
+            wallets = self.accounts.len(),
+            interval_ms = submission_interval.as_millis(),
+            pending_timeout_ms = pending_timeout.as_millis(),
+            target_utxos,
+            "starting sustained transaction workload"
+        );
+
+        let mut rng = ctx.rng(self.name());
+        let mut blocks = ctx.block_feed().subscribe();
+        let mut ledger = UtxoLedger::new(&self.accounts);
+        let mut ticker = interval(submission_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    ledger.expire(pending_timeout);
+                    if let Some((wallet, input)) = ledger.next_input() {
+                        let fan_out = ledger.utxo_count() < target_utxos;
+                        self.submit(ctx, &mut rng, &mut ledger, wallet, input, fan_out).await;
+                    }
+                }
+                record = blocks.recv() => match record {
+                    Ok(record) => {
+                        let included = ledger.observe_block(&record);
+                        self.stats.included.fetch_add(included, Ordering::Relaxed);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "sustained transaction workload lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err("sustained transaction workload: block feed closed".into());
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl SustainedWorkload {
+    async fn submit(
+        &self,
+        ctx: &RunContext,
+        rng: &mut StdRng,
+        ledger: &mut UtxoLedger,
+        wallet: usize,
+        input: SpendableUtxo,
+        fan_out: bool,
+    ) {
+        let wallet_input = WalletInput {
+            account: self.accounts[wallet].account.clone(),
+            utxo: input.utxo,
+        };
+        let value = input.utxo.note.value;
+        let built = if fan_out && value >= 2 {
+            let owner = wallet_input.account.public_key();
+            build_wallet_transaction_with_outputs(
+                &wallet_input,
+                vec![
+                    Note::new(value / 2, owner),
+                    Note::new(value - value / 2, owner),
+                ],
+            )
+        } else {
+            build_wallet_transaction(&wallet_input)
+        };
+        let signed_tx = match built {
+            Ok(tx) => Arc::new(tx),
+            Err(err) => {
+                tracing::warn!(wallet, error = %err, "dropping unspendable wallet input");
+                return;
+            }
+        };
+        let ledger_hash = signed_tx.mantle_tx().ledger_tx.hash();
+
+        match submit_transaction_via_cluster(ctx, rng, Arc::clone(&signed_tx)).await {
+            Ok(()) => {
+                self.stats.submitted.fetch_add(1, Ordering::Relaxed);
+                ledger.mark_pending(ledger_hash, wallet);
+            }
+            Err(err) => {
+                tracing::warn!(
+                    wallet,
+                    tx_hash = ?signed_tx.hash(),
+                    error = %err,
+                    "sustained transaction submission failed; requeueing input"
+                );
+                ledger.requeue(wallet, input);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SpendableUtxo {
+    utxo: Utxo,
+}
+
+struct PendingSpend {
+    wallet: usize,
+    submitted_at: Instant,
+}
+
+/// The workload's view of which wallet outputs are spendable or in flight.
+struct UtxoLedger {
+    wallets: Vec<VecDeque<SpendableUtxo>>,
+    owners: HashMap<ZkPublicKey, usize>,
+    pending: HashMap<TxHash, PendingSpend>,
+    /// Spends that timed out. Their inputs are never spent again, since the
+    /// original may still land; their outputs are credited if it does.
+    expired: HashSet<TxHash>,
+    next_wallet: usize,
+}
+
+impl UtxoLedger {
+    fn new(accounts: &[WalletInput]) -> Self {
+        let wallets = accounts
+            .iter()
+            .map(|input| VecDeque::from([SpendableUtxo { utxo: input.utxo }]))
+            .collect();
+        let owners = accounts
+            .iter()
+            .enumerate()
+            .map(|(wallet, input)| (input.account.public_key(), wallet))
+            .collect();
+
+        Self {
+            wallets,
+            owners,
+            pending: HashMap::new(),
+            expired: HashSet::new(),
+            next_wallet: 0,
+        }
+    }
+
+    /// Next spendable input, rotating across wallets.
+    fn next_input(&mut self) -> Option<(usize, SpendableUtxo)> {
+        let count = self.wallets.len();
+        for offset in 0..count {
+            let wallet = (self.next_wallet + offset) % count;
+            if let Some(input) = self.wallets[wallet].pop_front() {
+                self.next_wallet = (wallet + 1) % count;
+                return Some((wallet, input));
+            }
+        }
+        None
+    }
+
+    /// Spendable and in-flight UTXOs across all wallets.
+    fn utxo_count(&self) -> usize {
+        self.wallets.iter().map(VecDeque::len).sum::<usize>() + self.pending.len()
+    }
+
+    fn mark_pending(&mut self, ledger_hash: TxHash, wallet: usize) {
+        self.pending.insert(
+            ledger_hash,
+            PendingSpend {
+                wallet,
+                submitted_at: Instant::now(),
+            },
+        );
+    }
+
+    fn requeue(&mut self, wallet: usize, input: SpendableUtxo) {
+        self.wallets[wallet].push_back(input);
+    }
+
+    /// Stop waiting for transactions that have not landed within `timeout`.
+    fn expire(&mut self, timeout: Duration) {
+        let now = Instant::now();
+        let expired = self
+            .pending
+            .iter()
+            .filter(|(_, spend)| now.duration_since(spend.submitted_at) >= timeout)
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+
+        for hash in expired {
+            if let Some(PendingSpend { wallet, .. }) = self.pending.remove(&hash) {
+                tracing::debug!(
+                    wallet,
+                    ?hash,
+                    "wallet spend not included in time; tracking it as expired"
+                );
+                self.expired.insert(hash);
+            }
+        }
+    }
+
+    /// Track outputs of our transactions included in `record`; returns how
+    /// many of them landed.
+    fn observe_block(&mut self, record: &BlockRecord) -> u64 {
+        let mut included = 0;
+        for tx in record.block.transactions() {
+            let ledger_tx = &tx.mantle_tx().ledger_tx;
+            let ledger_hash = ledger_tx.hash();
+            if self.pending.remove(&ledger_hash).is_none() && !self.expired.remove(&ledger_hash) {
+                continue;
+            }
+            included += 1;
+
+            for (idx, note) in ledger_tx.outputs.iter().enumerate() {
+                if let Some(&wallet) = self.owners.get(&note.pk) {
+                    self.requeue(
+                        wallet,
+                        SpendableUtxo {
+                            utxo: Utxo::new(ledger_hash, idx, *note),
+                        },
+                    );
+                }
+            }
+        }
+        included
+    }
+}
+
+#[derive(Debug, Error)]
+enum SustainedExpectationError {
+    #[error("sustained transaction workload submitted no transactions")]
+    NothingSubmitted,
+    #[error("sustained transaction inclusion observed {included} below required {required}")]
+    InsufficientInclusions { included: u64, required: u64 },
+}
+
+/// Checks that a minimum fraction of the sustained workload's transactions
+/// were included.
+struct SustainedInclusionExpectation {
+    stats: Arc<SustainedStats>,
+}
+
+#[async_trait]
+impl Expectation for SustainedInclusionExpectation {
+    fn name(&self) -> &'static str {
+        "tx_sustained_inclusion_expectation"
+    }
+
+    async fn evaluate(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+        let submitted = self.stats.submitted.load(Ordering::Relaxed);
+        let included = self.stats.included.load(Ordering::Relaxed);
+        if submitted == 0 {
+            return Err(SustainedExpectationError::NothingSubmitted.into());
+        }
+
+        let required = ((submitted as f64) * MIN_INCLUSION_RATIO).ceil() as u64;
+        if included < required {
+            tracing::warn!(
+                submitted,
+                included,
+                required,
+                "sustained tx inclusion expectation failed"
+            );
+            return Err(
+                SustainedExpectationError::InsufficientInclusions { included, required }.into(),
+            );
+        }
+
+        tracing::info!(
+            submitted,
+            included,
+            "sustained tx inclusion expectation satisfied"
+        );
+        Ok(())
+    }
+
+    fn report_details(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "submitted": self.stats.submitted.load(Ordering::Relaxed),
+            "included": self.stats.included.load(Ordering::Relaxed),
+        }))
+    }
+}
@@ -10,6 +10,7 @@ use key_management_system_service::keys::{ZkKey, ZkPublicKey};
 use nomos_core::mantle::{
     GenesisTx as _, Note, SignedMantleTx, Transaction as _, Utxo, tx_builder::MantleTxBuilder,
 };
+use rand::rngs::StdRng;
 use testing_framework_config::topology::configs::wallet::WalletAccount;
 use testing_framework_core::{
     scenario::{DynError, Expectation, RunContext, RunMetrics, Workload as ScenarioWorkload},
@@ -24,9 +25,12 @@ pub(super) struct SubmissionPlan {
     /// Time interval between submissions  
     pub submission_interval: Duration,
 }
-use tokio::time::sleep;
+use tokio::time::{Instant, sleep};
 
-use super::expectation::TxInclusionExpectation;
+use super::{
+    expectation::TxInclusionExpectation,
+    latency::{LatencyLimit, Percentile, SubmissionLog},
+};
 use crate::workloads::util::submit_transaction_via_cluster;
 
 const MAX_SUBMISSION_INTERVAL: Duration = Duration::from_secs(1);
@@ -36,12 +40,21 @@ pub struct Workload {
     txs_per_block: NonZeroU64,
     user_limit: Option<NonZeroUsize>,
     accounts: Vec<WalletInput>,
+    latency_thresholds: Vec<(LatencyKind, Percentile, LatencyLimit)>,
+    submissions: SubmissionLog,
+}
+
+/// Which latency a threshold applies to.
+#[derive(Clone, Copy, Debug)]
+enum LatencyKind {
+    Inclusion,
+    Finality,
 }
 
 #[derive(Clone)]
-struct WalletInput {
-    account: WalletAccount,
-    utxo: Utxo,
+pub(crate) struct WalletInput {
+    pub account: WalletAccount,
+    pub utxo: Utxo,
 }
 
 #[async_trait]
@@ -51,10 +64,15 @@ impl ScenarioWorkload for Workload {
     }
 
     fn expectations(&self) -> Vec<Box<dyn Expectation>> {
-        vec![Box::new(TxInclusionExpectation::new(
-            self.txs_per_block,
-            self.user_limit,
-        ))]
+        let expectation = self.latency_thresholds.iter().fold(
+            TxInclusionExpectation::new(self.txs_per_block, self.user_limit)
+                .with_submission_log(Arc::clone(&self.submissions)),
+            |expectation, (kind, percentile, limit)| match kind {
+                LatencyKind::Inclusion => expectation.with_inclusion_latency(*percentile, *limit),
+                LatencyKind::Finality => expectation.with_finality_latency(*percentile, *limit),
+            },
+        );
+        vec![Box::new(expectation)]
     }
 
     fn init(
@@ -64,51 +82,7 @@ impl ScenarioWorkload for Workload {
     ) -> Result<(), DynError> {
         tracing::info!("initializing transaction workload");
 
-        let wallet_accounts = descriptors.config().wallet().accounts.clone();
-        if wallet_accounts.is_empty() {
-            return Err(
-                "Transaction workload initialization failed: no seeded wallet accounts configured"
-                    .into(),
-            );
-        }
-
-        let reference_node = descriptors
-            .nodes()
-            .first()
-            .ok_or("transaction workload requires at least one node in the topology")?;
-
-        let utxo_map = wallet_utxo_map(reference_node);
-
-        fn match_account_to_utxo(
-            account: WalletAccount,
-            utxo_map: &HashMap<ZkPublicKey, Utxo>,
-        ) -> Option<WalletInput> {
-            utxo_map
-                .get(&account.public_key())
-                .copied()
-                .map(|utxo| WalletInput { account, utxo })
-        }
-
-        let mut accounts = wallet_accounts
-            .into_iter()
-            .filter_map(|account| match_account_to_utxo(account, &utxo_map))
-            .collect::<Vec<_>>();
-
-        apply_user_limit(&mut accounts, self.user_limit);
-
-        if accounts.is_empty() {
-            return Err(
-                "Transaction workload initialization failed: could not match any wallet accounts to genesis UTXOs".into(),
-            );
-        }
-
-        tracing::info!(
-            available_accounts = accounts.len(),
-            user_limit = self.user_limit.map(|u| u.get()),
-            "transaction workload accounts prepared"
-        );
-
-        self.accounts = accounts;
+        self.accounts = wallet_inputs(descriptors, self.user_limit)?;
         Ok(())
     }
 
@@ -126,11 +100,13 @@ impl Workload {
     /// Creates a workload that targets the provided transactions per block
     /// rate.
     #[must_use]
-    pub const fn new(txs_per_block: NonZeroU64) -> Self {
+    pub fn new(txs_per_block: NonZeroU64) -> Self {
         Self {
             txs_per_block,
             user_limit: None,
             accounts: Vec::new(),
+            latency_thresholds: Vec::new(),
+            submissions: SubmissionLog::default(),
         }
     }
 
@@ -152,6 +128,24 @@ impl Workload {
         self.user_limit = user_limit;
         self
     }
+
+    /// Fail the inclusion expectation when submit-to-inclusion latency at
+    /// `percentile` reaches `limit`.
+    #[must_use]
+    pub fn with_inclusion_latency(mut self, percentile: Percentile, limit: LatencyLimit) -> Self {
+        self.latency_thresholds
+            .push((LatencyKind::Inclusion, percentile, limit));
+        self
+    }
+
+    /// Fail the inclusion expectation when submit-to-finality latency at
+    /// `percentile` reaches `limit`.
+    #[must_use]
+    pub fn with_finality_latency(mut self, percentile: Percentile, limit: LatencyLimit) -> Self {
+        self.latency_thresholds
+            .push((LatencyKind::Finality, percentile, limit));
+        self
+    }
 }
 
 impl Default for Workload {
@@ -163,7 +157,9 @@ impl Default for Workload {
 struct Submission<'a> {
     plan: VecDeque<WalletInput>,
     ctx: &'a RunContext,
+    rng: StdRng,
     interval: Duration,
+    submissions: SubmissionLog,
 }
 
 impl<'a> Submission<'a> {
@@ -192,7 +188,9 @@ impl<'a> Submission<'a> {
         Ok(Self {
             plan,
             ctx,
+            rng: ctx.rng(workload.name()),
             interval: submission_plan.submission_interval,
+            submissions: Arc::clone(&workload.submissions),
         })
     }
 
@@ -205,7 +203,7 @@ impl<'a> Submission<'a> {
         );
 
         while let Some(input) = self.plan.pop_front() {
-            submit_wallet_transaction(self.ctx, &input).await?;
+            submit_wallet_transaction(self.ctx, &mut self.rng, &input, &self.submissions).await?;
 
             if !self.interval.is_zero() {
                 sleep(self.interval).await;
@@ -218,20 +216,97 @@ impl<'a> Submission<'a> {
     }
 }
 
-async fn submit_wallet_transaction(ctx: &RunContext, input: &WalletInput) -> Result<(), DynError> {
+async fn submit_wallet_transaction(
+    ctx: &RunContext,
+    rng: &mut StdRng,
+    input: &WalletInput,
+    submissions: &SubmissionLog,
+) -> Result<(), DynError> {
     let signed_tx = Arc::new(build_wallet_transaction(input)?);
+    let tx_hash = signed_tx.hash();
     tracing::debug!(
-        tx_hash = ?signed_tx.hash(),
+        ?tx_hash,
         user = ?input.account.public_key(),
         "submitting wallet transaction"
     );
-    submit_transaction_via_cluster(ctx, signed_tx).await
+    // Recorded up front so a block landing before the submit call returns
+    // still yields a latency sample.
+    submissions
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(tx_hash, Instant::now());
+    submit_transaction_via_cluster(ctx, rng, signed_tx).await
 }
 
-fn build_wallet_transaction(input: &WalletInput) -> Result<SignedMantleTx, DynError> {
-    let builder = MantleTxBuilder::new()
-        .add_ledger_input(input.utxo)
-        .add_ledger_output(Note::new(input.utxo.note.value, input.account.public_key()));
+/// Match seeded wallet accounts to their genesis UTXOs, honouring the user
+/// limit.
+pub(crate) fn wallet_inputs(
+    descriptors: &GeneratedTopology,
+    user_limit: Option<NonZeroUsize>,
+) -> Result<Vec<WalletInput>, DynError> {
+    let wallet_accounts = descriptors.config().wallet().accounts.clone();
+    if wallet_accounts.is_empty() {
+        return Err(
+            "Transaction workload initialization failed: no seeded wallet accounts configured"
+                .into(),
+        );
+    }
+
+    let reference_node = descriptors
+        .nodes()
+        .first()
+        .ok_or("transaction workload requires at least one node in the topology")?;
+
+    let utxo_map = wallet_utxo_map(reference_node);
+
+    fn match_account_to_utxo(
+        account: WalletAccount,
+        utxo_map: &HashMap<ZkPublicKey, Utxo>,
+    ) -> Option<WalletInput> {
+        utxo_map
+            .get(&account.public_key())
+            .copied()
+            .map(|utxo| WalletInput { account, utxo })
+    }
+
+    let mut accounts = wallet_accounts
+        .into_iter()
+        .filter_map(|account| match_account_to_utxo(account, &utxo_map))
+        .collect::<Vec<_>>();
+
+    apply_user_limit(&mut accounts, user_limit);
+
+    if accounts.is_empty() {
+        return Err(
+            "Transaction workload initialization failed: could not match any wallet accounts to genesis UTXOs".into(),
+        );
+    }
+
+    tracing::info!(
+        available_accounts = accounts.len(),
+        user_limit = user_limit.map(|u| u.get()),
+        "transaction workload accounts prepared"
+    );
+
+    Ok(accounts)
+}
+
+pub(crate) fn build_wallet_transaction(input: &WalletInput) -> Result<SignedMantleTx, DynError> {
+    build_wallet_transaction_with_outputs(
+        input,
+        vec![Note::new(input.utxo.note.value, input.account.public_key())],
+    )
+}
+
+/// Spend `input` into `outputs`, signed by the owning wallet.
+pub(crate) fn build_wallet_transaction_with_outputs(
+    input: &WalletInput,
+    outputs: Vec<Note>,
+) -> Result<SignedMantleTx, DynError> {
+    let builder = outputs.into_iter().fold(
+        MantleTxBuilder::new().add_ledger_input(input.utxo),
+        |builder, note| builder.add_ledger_output(note),
+    );
 
     let mantle_tx = builder.build();
 
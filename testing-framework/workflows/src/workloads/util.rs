@@ -7,8 +7,11 @@ use nomos_core::{
         ops::{Op, channel::MsgId},
     },
 };
-use rand::{seq::SliceRandom as _, thread_rng};
-use testing_framework_core::scenario::{DynError, RunContext};
+use rand::Rng;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, RunContext},
+};
 use tracing::debug;
 
 const SUBMIT_RETRIES: usize = 5;
@@ -35,22 +38,33 @@ where
     None
 }
 
-/// Submits a transaction to the cluster, fanning out across clients until one
-/// succeeds.
+/// Submits a transaction to a single node without retrying.
+pub async fn submit_transaction_once(
+    client: &ApiClient,
+    tx: &SignedMantleTx,
+) -> Result<(), DynError> {
+    debug!(tx_hash = ?tx.hash(), url = %client.base_url(), "submitting transaction once");
+    client
+        .submit_transaction(tx)
+        .await
+        .map_err(|err| -> DynError { err.into() })
+}
+
+/// Submits a transaction to the cluster, fanning out across clients in an
+/// order shuffled by `rng` until one succeeds.
 pub async fn submit_transaction_via_cluster(
     ctx: &RunContext,
+    rng: &mut (impl Rng + Send),
     tx: Arc<SignedMantleTx>,
 ) -> Result<(), DynError> {
     let tx_hash = tx.hash();
     debug!(?tx_hash, "submitting transaction via cluster (nodes first)");
 
     let node_clients = ctx.node_clients();
-    let mut clients = node_clients.node_clients();
-    clients.shuffle(&mut thread_rng());
 
     let mut last_err = None;
     for attempt in 0..SUBMIT_RETRIES {
-        clients.shuffle(&mut thread_rng());
+        let clients = node_clients.shuffled_clients_with(rng);
 
         for client in &clients {
             let url = client.base_url().clone();